@@ -0,0 +1,141 @@
+use crate::script::{self, Incident};
+use pitwall::packet::TrackId;
+use std::env;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Command line options, anything not given keeps its default.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub target: String,           // --target, host:port the telemetry is sent to
+    pub cars: u8,                 // --cars, size of the field
+    pub laps: u8,                 // --laps, race distance
+    pub trackId: TrackId,         // --track, track id from the appendix
+    pub trackLength: u16,         // --track-length, in metres
+    pub lapTime: f32,             // --lap-time, dry pace of the quickest car in seconds
+    pub rate: f32,                // --rate, Lap / CarTelemetry / CarStatus packets per second
+    pub speed: f32,               // --speed, how many times faster than real time to run
+    pub seed: u64,                // --seed, the same seed runs the same race
+    pub incidents: Vec<Incident>, // --incident and --script
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            target: "127.0.0.1:20777".to_string(),
+            cars: 20,
+            laps: 10,
+            trackId: TrackId::Silverstone,
+            trackLength: 5891,
+            lapTime: 88.0,
+            rate: 20.0,
+            speed: 1.0,
+            seed: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(1, |d| d.as_nanos() as u64),
+            incidents: vec![],
+        }
+    }
+}
+
+impl Config {
+    pub fn from_args() -> Self {
+        let mut config = Self::default();
+        let mut args = env::args().skip(1);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--target" => config.target = Self::value(&arg, args.next()),
+                "--cars" => config.cars = Self::value(&arg, args.next()),
+                "--laps" => config.laps = Self::value(&arg, args.next()),
+                "--track" => {
+                    let id: i8 = Self::value(&arg, args.next());
+                    config.trackId = TrackId::from_u8_to_i8(&(id as u8));
+                }
+                "--track-length" => config.trackLength = Self::value(&arg, args.next()),
+                "--lap-time" => config.lapTime = Self::value(&arg, args.next()),
+                "--rate" => config.rate = Self::value(&arg, args.next()),
+                "--speed" => config.speed = Self::value(&arg, args.next()),
+                "--seed" => config.seed = Self::value(&arg, args.next()),
+                "--incident" => match args.next().map(|v| v.parse()) {
+                    Some(Ok(incident)) => config.incidents.push(incident),
+                    Some(Err(err)) => Self::usage(&err),
+                    None => Self::usage(&format!("Missing or invalid value for {arg}")),
+                },
+                "--script" => match args.next().map(|path| script::load(&path)) {
+                    Some(Ok(incidents)) => config.incidents.extend(incidents),
+                    Some(Err(err)) => Self::usage(&err),
+                    None => Self::usage(&format!("Missing or invalid value for {arg}")),
+                },
+                _ => Self::usage(&format!("Unknown argument {arg}")),
+            }
+        }
+
+        config.check();
+        config
+    }
+
+    fn check(&self) {
+        if !(1..=22).contains(&self.cars) {
+            Self::usage("--cars must be between 1 and 22");
+        }
+        if !(1..=99).contains(&self.laps) {
+            Self::usage("--laps must be between 1 and 99");
+        }
+        if self.trackId.name().is_none() {
+            Self::usage("--track is not a known track id");
+        }
+        if self.trackLength < 1000 {
+            Self::usage("--track-length must be at least 1000 metres");
+        }
+        if self.lapTime < 30.0 || self.rate <= 0.0 || self.speed <= 0.0 {
+            Self::usage("--lap-time, --rate and --speed must be sensible positive numbers");
+        }
+        if let Some(car) = self
+            .incidents
+            .iter()
+            .filter_map(|i| i.car())
+            .find(|car| *car >= self.cars)
+        {
+            Self::usage(&format!(
+                "Incident for car {car}, but there are only {} cars",
+                self.cars
+            ));
+        }
+    }
+
+    fn value<T: std::str::FromStr>(arg: &str, value: Option<String>) -> T {
+        match value.map(|v| v.parse()) {
+            Some(Ok(v)) => v,
+            _ => Self::usage(&format!("Missing or invalid value for {arg}")),
+        }
+    }
+
+    fn usage(error: &str) -> ! {
+        eprintln!("{error}");
+        eprintln!();
+        eprintln!("Usage: pitwall-sim [options]");
+        eprintln!(
+            "  --target <host:port>      Where to send the telemetry (default 127.0.0.1:20777)"
+        );
+        eprintln!("  --cars <1-22>             Size of the field (default 20)");
+        eprintln!("  --laps <1-99>             Race distance (default 10)");
+        eprintln!(
+            "  --track <id>              Track id from the appendix (default 7, Silverstone)"
+        );
+        eprintln!("  --track-length <metres>   Length of a lap (default 5891)");
+        eprintln!("  --lap-time <seconds>      Dry pace of the quickest car (default 88)");
+        eprintln!(
+            "  --rate <hz>               Lap, telemetry and status packets per second (default 20)"
+        );
+        eprintln!("  --speed <multiplier>      Run faster than real time (default 1)");
+        eprintln!("  --seed <number>           Repeat a race exactly (default from the clock)");
+        eprintln!("  --incident \"<lap> <kind> [args]\"  Script an incident, may be repeated:");
+        eprintln!("                            sc <laps>, vsc <laps>, pit <car> [tyre], penalty <car> <secs>,");
+        eprintln!("                            drivethrough <car>, stopgo <car> <secs>, rain <percent>, retire <car>");
+        eprintln!(
+            "  --script <file>           Incidents from a file, one per line, # for comments"
+        );
+        process::exit(2);
+    }
+}
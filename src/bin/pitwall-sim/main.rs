@@ -0,0 +1,127 @@
+#![allow(non_snake_case)]
+
+// pitwall-sim: races a field of cars round a track and sends the telemetry the
+// game would, so pitwall (or anything else listening) can be run without the game.
+
+use pitwall::packet::*;
+use std::net::UdpSocket;
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod config;
+mod packets;
+mod race;
+mod script;
+use config::Config;
+use race::Race;
+
+// Something sent every `period` seconds of session time.
+struct Every {
+    period: f32,
+    next: f32,
+}
+
+impl Every {
+    fn new(period: f32) -> Self {
+        Self { period, next: 0.0 }
+    }
+
+    fn due(&mut self, time: f32) -> bool {
+        if time < self.next {
+            return false;
+        }
+
+        self.next += self.period;
+        true
+    }
+}
+
+fn main() {
+    let config = Config::from_args();
+
+    let socket = UdpSocket::bind("0.0.0.0:0").expect("Couldn't bind to address.");
+    let send = |bytes: Vec<u8>| {
+        socket
+            .send_to(&bytes, &config.target)
+            .expect("Couldn't send packet.");
+    };
+    println!(
+        "Racing {} cars over {} laps of {}, sending to {}",
+        config.cars, config.laps, config.trackId, config.target
+    );
+
+    let mut race = Race::new(&config);
+    let dt = 1.0 / config.rate;
+    let tick = Duration::from_secs_f32(dt / config.speed);
+
+    // Game frequencies, Lap / CarTelemetry / CarStatus go out every tick at --rate.
+    let mut session = Every::new(0.5);
+    let mut damage = Every::new(0.5);
+    let mut participants = Every::new(5.0);
+    let mut history = Every::new(0.05);
+    let mut historyIdx = 0;
+    let mut leaderLap = 0;
+
+    let mut next = Instant::now();
+    loop {
+        race.step(dt);
+
+        for event in std::mem::take(&mut race.events) {
+            send(packets::event(&race, &event).to_bytes());
+        }
+
+        if session.due(race.time) {
+            send(packets::session(&race).to_bytes());
+        }
+        if participants.due(race.time) {
+            send(packets::participants(&race).to_bytes());
+        }
+        if damage.due(race.time) {
+            send(packets::carDamage(&race).to_bytes());
+        }
+
+        send(packets::lap(&race).to_bytes());
+        send(packets::carTelemetry(&race).to_bytes());
+        send(packets::carStatus(&race).to_bytes());
+
+        // One car at a time, round and round the field.
+        if history.due(race.time) {
+            send(packets::sessionHistory(&race, historyIdx).to_bytes());
+            historyIdx = (historyIdx + 1) % race.cars.len();
+        }
+
+        if race.leaderLap != leaderLap && race.phase == race::Phase::Racing {
+            leaderLap = race.leaderLap;
+            let leader = &race.cars[race.order[0]];
+            println!("L{leaderLap:<2} {} leads", leader.name());
+        }
+
+        if race.isOver() {
+            send(packets::finalClassification(&race).to_bytes());
+            let ended = race::Event {
+                code: *b"SEND",
+                details: EventDetails {
+                    sessionEnded: SessionEnded {},
+                },
+            };
+            send(packets::event(&race, &ended).to_bytes());
+            break;
+        }
+
+        next += tick;
+        if let Some(wait) = next.checked_duration_since(Instant::now()) {
+            thread::sleep(wait);
+        }
+    }
+
+    for idx in &race.order {
+        let car = &race.cars[*idx];
+        println!(
+            "P{:<2} {:<22} {:?} {} stops",
+            car.position,
+            car.name(),
+            car.status,
+            car.numPitStops
+        );
+    }
+}
@@ -0,0 +1,477 @@
+use crate::race::{Entrant, Event, Pit, Race, ERS_STORE, FUEL_PER_LAP, PIT_LIMIT, ZONES};
+use pitwall::packet::*;
+
+// Milliseconds split into the (ms, minutes) pairs the lap packets use.
+fn split(ms: u32) -> (u16, u8) {
+    ((ms % 60_000) as u16, (ms / 60_000) as u8)
+}
+
+fn header(race: &Race, packetId: PacketId) -> Header {
+    Header {
+        packetFormat: 2025,
+        gameYear: 25,
+        gameMajorVersion: 1,
+        gameMinorVersion: 0,
+        packetVersion: 1,
+        packetId,
+        sessionUID: race.sessionUID,
+        sessionTime: race.time,
+        frameIdentifier: race.frame,
+        overallFrameIdentifier: race.frame,
+        playerCarIndex: race.cars.len() as u8 - 1, // The player is always the last car
+        secondaryPlayerCarIndex: 255,
+    }
+}
+
+pub fn event(race: &Race, event: &Event) -> PacketEvent {
+    PacketEvent {
+        header: header(race, PacketId::Event),
+        eventStringCode: EventTag { tag: event.code },
+        eventDetails: event.details,
+    }
+}
+
+pub fn session(race: &Race) -> PacketSession {
+    let mut s = PacketSession::unpack(&[0; size_of::<PacketSession>()]);
+    let duration = 7200;
+
+    s.header = header(race, PacketId::Session);
+    s.weather = crate::race::weather(race.rain);
+    s.trackTemperature = 34 - (race.rain / 5) as i8;
+    s.airTemperature = 24 - (race.rain / 12) as i8;
+    s.totalLaps = race.totalLaps;
+    s.trackLength = race.trackLength as u16;
+    s.sessionType = Session::Race;
+    s.trackId = race.trackId;
+    s.formula = Formula::Modern;
+    s.sessionTimeLeft = SessionTime {
+        time: duration - (race.time as u16).min(duration),
+    };
+    s.sessionDuration = SessionTime { time: duration };
+    s.pitSpeedLimit = PIT_LIMIT as u8;
+    s.spectatorCarIndex = 255;
+
+    let mut zones = [MarshalZone::default(); 21];
+    for (i, zone) in zones.iter_mut().take(ZONES).enumerate() {
+        *zone = MarshalZone {
+            zoneStart: i as f32 / ZONES as f32,
+            zoneFlag: race.zones[i],
+        };
+    }
+    s.numMarshalZones = ZONES as u8;
+    s.marshalZones = zones;
+    s.safetyCarStatus = race.safetyCar;
+
+    let forecast = race.forecast();
+    let mut samples = [WeatherForecast::default(); 64];
+    samples[..forecast.len()].copy_from_slice(&forecast);
+    s.numWeatherForecasts = forecast.len() as u8;
+    s.weatherForecast = samples;
+    s.forecastAccuracy = Accuracy::Perfect;
+
+    s.aiDifficulty = 90;
+    s.sessionLinkIdentifier = race.sessionUID as u32;
+    s.gameMode = GameMode::GrandPrix;
+    s.ruleSet = RuleSet::Race;
+    s.timeOfDay = 14 * 60 + race.time as u32 / 60;
+    s.sessionLength = SessionLength::Medium;
+    s.speedUnitsLeadPlayer = 1;
+    s.speedUnitsSecondaryPlayer = 1;
+    s.numSafetyCarPeriods = race.numSafetyCarPeriods;
+    s.numVirtualSafetyCarPeriods = race.numVirtualSafetyCarPeriods;
+    s.carDamage = 2;
+    s.collisions = 2;
+    s.safetyCar = 2;
+    s.redFlags = 2;
+    s.numSessionsInWeekend = 1;
+    s.weekendStructure[0] = Session::Race;
+    s.sector2LapDistanceStart = race.trackLength / 3.0;
+    s.sector3LapDistanceStart = race.trackLength * 2.0 / 3.0;
+
+    s
+}
+
+pub fn participants(race: &Race) -> PacketParticipants {
+    let mut participants = [Participant::default(); 22];
+    let player = race.cars.len() - 1;
+
+    for (idx, car) in race.cars.iter().enumerate() {
+        let mut name = [0; 32];
+        let bytes = car.name().as_bytes();
+        name[..bytes.len().min(31)].copy_from_slice(&bytes[..bytes.len().min(31)]);
+
+        participants[idx] = Participant {
+            aiControlled: (idx != player) as u8,
+            driverId: car.driverId,
+            networkId: idx as u8,
+            teamId: car.teamId,
+            myTeam: 0,
+            raceNumber: car.raceNumber,
+            nationality: car.nationality,
+            name,
+            yourTelemetry: 1,
+            showOnlineNames: 1,
+            techLevel: 0,
+            platform: 255,
+            numColours: 0,
+            liveryColours: [LiveryColour::default(); 4],
+        };
+    }
+
+    PacketParticipants {
+        header: header(race, PacketId::Participants),
+        numActiveCars: race.cars.len() as u8,
+        participants,
+    }
+}
+
+pub fn lap(race: &Race) -> PacketLap {
+    let mut cars = [Car::default(); 22];
+
+    for (idx, car) in race.cars.iter().enumerate() {
+        let (sector1TimeMSPart, sector1TimeMinutesPart) = split(car.sector1MS);
+        let (sector2TimeMSPart, sector2TimeMinutesPart) = split(car.sector2MS);
+        let (deltaToCarInFrontMSPart, deltaToCarInFrontMinutesPart) =
+            split((car.gapAhead * 1000.0) as u32);
+        let (deltaToRaceLeaderMSPart, deltaToRaceLeaderMinutesPart) =
+            split((car.gapLeader * 1000.0) as u32);
+
+        let driverStatus = match (car.status, car.pit) {
+            (ResultStatus::Retired, _) => CarState::InGarage,
+            (ResultStatus::Finished, _) | (_, Pit::Entering) => CarState::InLap,
+            (_, Pit::Stopped(_) | Pit::Leaving) => CarState::OutLap,
+            _ => CarState::OnTrack,
+        };
+
+        cars[idx] = Car {
+            lastLapTimeInMS: car.lastLapMS,
+            currentLapTimeInMS: (car.lapTime * 1000.0) as u32,
+            sector1TimeMSPart,
+            sector1TimeMinutesPart,
+            sector2TimeMSPart,
+            sector2TimeMinutesPart,
+            deltaToCarInFrontMSPart,
+            deltaToCarInFrontMinutesPart,
+            deltaToRaceLeaderMSPart,
+            deltaToRaceLeaderMinutesPart,
+            lapDistance: car.lapDistance,
+            totalDistance: car.totalDistance,
+            safetyCarDelta: car.safetyCarDelta,
+            racePosition: car.position,
+            currentLapNum: car.lapNum,
+            pitStatus: match car.pit {
+                Pit::None => PitStatus::None,
+                Pit::Stopped(_) => PitStatus::InPitArea,
+                _ => PitStatus::Pitting,
+            },
+            numPitStops: car.numPitStops,
+            sector: car.sector,
+            currentLapInvalid: 0,
+            penalties: car.penalties,
+            totalWarnings: 0,
+            cornerCuttingWarnings: 0,
+            numUnservedDriveThroughPens: car.driveThroughs,
+            numUnservedStopGoPens: car.stopGoes.len() as u8,
+            gridPosition: car.grid,
+            driverStatus,
+            resultStatus: car.status,
+            pitLaneTimerActive: car.isInPitLane() as u8,
+            pitLaneTimeInLaneInMS: (car.pitLaneTime * 1000.0) as u16,
+            pitStopTimerInMS: (car.pitStopTime * 1000.0) as u16,
+            pitStopShouldServePen: (car.boxThisLap && car.penalties > 0) as u8,
+            speedTrapFastestSpeed: car.speedTrap,
+            speedTrapFastestLap: car.speedTrapLap,
+        };
+    }
+
+    PacketLap {
+        header: header(race, PacketId::Lap),
+        cars,
+        timeTrialPBCarIdx: 255,
+        timeTrialRivalCarIdx: 255,
+    }
+}
+
+fn telemetry(car: &Entrant) -> CarTelemetry {
+    let kph = car.speed * 3.6;
+    let gear = if kph < 1.0 {
+        0
+    } else {
+        ((kph / 42.0) as u8 + 1).min(8)
+    };
+    let window = ((kph - (gear.max(1) - 1) as f32 * 42.0) / 42.0).clamp(0.0, 1.0);
+    let rpm = if gear == 0 {
+        4000.0
+    } else {
+        4000.0 + window * 8500.0
+    };
+    let revLightsPercent = ((rpm - 4000.0) / 8500.0 * 100.0) as u8;
+    let leds = revLightsPercent as u32 * 15 / 100;
+
+    let brake = if car.accel < -2.0 {
+        (-car.accel / 8.0).min(1.0)
+    } else {
+        0.0
+    };
+    let throttle = match car.accel {
+        a if a > 0.3 => 1.0,
+        a if a < -2.0 => 0.0,
+        _ => 0.7,
+    };
+    let steer = (car.lapDistance * 0.01).sin() * if brake > 0.0 { 0.4 } else { 0.1 };
+
+    let brakeTemp = (350.0 + brake * 550.0) as u16;
+    let surface = (88.0 + car.wear / 5.0 + brake * 6.0) as u8;
+    let pressure = |cold: f32| cold + (surface as f32 - 88.0) * 0.05;
+
+    CarTelemetry {
+        speed: Kph { kph: kph as u16 },
+        throttle,
+        steer,
+        brake,
+        clutch: 0,
+        gear: Gear::from_u8_to_i8(&gear),
+        engineRPM: rpm as u16,
+        drs: car.drs as u8,
+        revLightsPercent,
+        revLightsBitValue: RevLights {
+            LEDs: ((1u32 << leds) - 1) as u16,
+        },
+        brakesTemperature: [brakeTemp, brakeTemp, brakeTemp + 40, brakeTemp + 40],
+        tyresSurfaceTemperature: [surface, surface, surface + 3, surface + 3],
+        tyresInnerTemperature: [100, 100, 102, 102],
+        engineTemperature: 108,
+        tyresPressure: [
+            pressure(21.5),
+            pressure(21.5),
+            pressure(23.0),
+            pressure(23.0),
+        ],
+        surfaceType: [Surface::Tarmac; 4],
+    }
+}
+
+pub fn carTelemetry(race: &Race) -> PacketCarTelemetry {
+    let mut carTelemetry = [CarTelemetry::default(); 22];
+
+    for (idx, car) in race.cars.iter().enumerate() {
+        carTelemetry[idx] = telemetry(car);
+    }
+
+    PacketCarTelemetry {
+        header: header(race, PacketId::CarTelemetry),
+        carTelemetry,
+        mfdFirstPlayer: MFDPanel::Closed,
+        mfdSecondaryPlayer: MFDPanel::Closed,
+        suggestedGear: Gear::Neutral,
+    }
+}
+
+pub fn carStatus(race: &Race) -> PacketCarStatus {
+    let mut carStatus = [CarStatus::default(); 22];
+
+    for (idx, car) in race.cars.iter().enumerate() {
+        let (actualTyre, visualTyre) = car.tyre.compound();
+        let lapsToGo = (race.totalLaps - car.lapNum) as f32 + 1.0
+            - car.lapDistance.max(0.0) / race.trackLength;
+        let throttle = telemetry(car).throttle;
+
+        carStatus[idx] = CarStatus {
+            tractionControl: TC::Off,
+            antiLockBrakes: Assist::Off,
+            fuelMix: FuelMix::Standard,
+            frontBrakeBias: 56,
+            pitLimiterStatus: car.isInPitLane() as u8,
+            fuelInTank: car.fuel,
+            fuelCapacity: 110.0,
+            fuelRemainingLaps: car.fuel / FUEL_PER_LAP - lapsToGo, // The MFD shows what's spare
+            maxRPM: 13000,
+            idleRPM: 4000,
+            maxGears: 8,
+            drsAllowed: car.drsAllowed as u8,
+            drsActivationDistance: 0,
+            actualTyre,
+            visualTyre,
+            tyresAgeLaps: car.tyreAge,
+            vehicleFiaFlags: car.flag,
+            enginePowerICE: throttle * 560_000.0,
+            enginePowerMGUK: if car.accel > 0.5 && car.ers > 0.0 {
+                120_000.0
+            } else {
+                0.0
+            },
+            ersStoreEnergy: car.ers,
+            ersDeployMode: if car.drsAllowed || car.ers > ERS_STORE * 0.9 {
+                ErsDeployMode::Overtake
+            } else {
+                ErsDeployMode::Medium
+            },
+            ersHarvestedThisLapMGUK: car.ersHarvested,
+            ersHarvestedThisLapMGUH: 0.0,
+            ersDeployedThisLap: car.ersDeployed,
+            networkPaused: 0,
+        };
+    }
+
+    PacketCarStatus {
+        header: header(race, PacketId::CarStatus),
+        carStatus,
+    }
+}
+
+pub fn carDamage(race: &Race) -> PacketCarDamage {
+    let mut carDamageData = [CarDamage::default(); 22];
+
+    for (idx, car) in race.cars.iter().enumerate() {
+        let tyresWear = Wheels {
+            RL: car.wear * 1.04,
+            RR: car.wear,
+            FL: car.wear * 0.95,
+            FR: car.wear * 0.98,
+        };
+        let brakes = (car.laps.len() / 4) as u8;
+        let engine = car.engineWear + (car.totalDistance.max(0.0) / 50_000.0) as u8;
+
+        carDamageData[idx] = CarDamage {
+            tyresWear,
+            tyresDamage: [
+                tyresWear.RL as u8,
+                tyresWear.RR as u8,
+                tyresWear.FL as u8,
+                tyresWear.FR as u8,
+            ],
+            brakesDamage: [brakes; 4],
+            engineMGUHWear: engine,
+            engineESWear: engine,
+            engineCEWear: engine,
+            engineICEWear: engine,
+            engineMGUKWear: engine,
+            engineTCWear: engine,
+            ..CarDamage::default()
+        };
+    }
+
+    PacketCarDamage {
+        header: header(race, PacketId::CarDamage),
+        carDamageData,
+    }
+}
+
+// Lap with the lowest of `time`, numbered from 1, 0 before there is one.
+fn best(laps: &[LapHistory], time: impl Fn(&LapHistory) -> u32) -> u8 {
+    laps.iter()
+        .enumerate()
+        .min_by_key(|(_, lap)| time(lap))
+        .map_or(0, |(i, _)| i as u8 + 1)
+}
+
+pub fn sessionHistory(race: &Race, idx: usize) -> PacketSessionHistory {
+    let mut p = PacketSessionHistory::unpack(&[0; size_of::<PacketSessionHistory>()]);
+    let car = &race.cars[idx];
+
+    let mut laps = car.laps.clone();
+    if car.isRunning() {
+        // The lap in progress, with whichever sectors are done.
+        let (s1, m1) = split(if car.sector > 0 { car.sector1MS } else { 0 });
+        let (s2, m2) = split(if car.sector > 1 { car.sector2MS } else { 0 });
+        laps.push(LapHistory {
+            sector1TimeMSPart: s1,
+            sector1TimeMinutesPart: m1,
+            sector2TimeMSPart: s2,
+            sector2TimeMinutesPart: m2,
+            lapValidBitFlags: Valid::All,
+            ..LapHistory::default()
+        });
+    }
+    laps.truncate(100);
+
+    let mut lapHistory = [LapHistory::default(); 100];
+    lapHistory[..laps.len()].copy_from_slice(&laps);
+
+    let mut stints: Vec<TyreStintHistory> = car
+        .stints
+        .iter()
+        .chain([(255, car.tyre)].iter())
+        .map(|(endLap, tyre)| {
+            let (tyreActualCompound, tyreVisualCompound) = tyre.compound();
+            TyreStintHistory {
+                endLap: *endLap,
+                tyreActualCompound,
+                tyreVisualCompound,
+            }
+        })
+        .collect();
+    stints.truncate(8);
+
+    let mut tyreStintsHistory = [TyreStintHistory::default(); 8];
+    tyreStintsHistory[..stints.len()].copy_from_slice(&stints);
+
+    p.header = header(race, PacketId::SessionHistory);
+    p.carIdx = idx as u8;
+    p.numLaps = laps.len() as u8;
+    p.numTyreStints = stints.len() as u8;
+    p.bestLapTimeLapNum = best(&car.laps, |l| l.lapTimeInMS);
+    p.bestSector1LapNum = best(&car.laps, |l| l.sector1TimeInMS());
+    p.bestSector2LapNum = best(&car.laps, |l| l.sector2TimeInMS());
+    p.bestSector3LapNum = best(&car.laps, |l| l.sector3TimeInMS());
+    p.lapHistory = lapHistory;
+    p.tyreStintsHistory = tyreStintsHistory;
+
+    p
+}
+
+pub fn finalClassification(race: &Race) -> PacketFinalClassification {
+    let mut classificationData = [FinalClassification::default(); 22];
+
+    for (idx, car) in race.cars.iter().enumerate() {
+        let mut tyreStintsActual = [ActualCompound::default(); 8];
+        let mut tyreStintsVisual = [VisualCompound::default(); 8];
+        let mut tyreStintsEndLaps = [0; 8];
+
+        let stints: Vec<(u8, _)> = car
+            .stints
+            .iter()
+            .copied()
+            .chain([(car.laps.len() as u8, car.tyre)])
+            .take(8)
+            .collect();
+        for (i, (endLap, tyre)) in stints.iter().enumerate() {
+            (tyreStintsActual[i], tyreStintsVisual[i]) = tyre.compound();
+            tyreStintsEndLaps[i] = *endLap;
+        }
+
+        let isFinished = matches!(car.status, ResultStatus::Finished);
+
+        classificationData[idx] = FinalClassification {
+            position: car.position,
+            numLaps: car.laps.len() as u8,
+            gridPosition: car.grid,
+            points: if isFinished {
+                Race::points(car.position)
+            } else {
+                0
+            },
+            numPitStops: car.numPitStops,
+            resultStatus: car.status,
+            resultReason: if isFinished { 2 } else { 1 },
+            bestLapTimeInMS: car.bestLapMS(),
+            totalRaceTime: if isFinished {
+                (car.finishTime - race.lightsOut) as f64
+            } else {
+                0.0
+            },
+            penaltiesTime: car.penaltiesTime,
+            numPenalties: car.numPenalties,
+            numTyreStints: stints.len() as u8,
+            tyreStintsActual,
+            tyreStintsVisual,
+            tyreStintsEndLaps,
+        };
+    }
+
+    PacketFinalClassification {
+        header: header(race, PacketId::FinalClassification),
+        numCars: race.cars.len() as u8,
+        classificationData,
+    }
+}
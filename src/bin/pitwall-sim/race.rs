@@ -0,0 +1,1184 @@
+use crate::config::Config;
+use crate::script::{Action, Incident};
+use pitwall::packet::*;
+use std::f32::consts::TAU;
+use std::str::FromStr;
+
+pub const ZONES: usize = 12; // Marshal zones, evenly spaced
+pub const FORECAST: [u8; 7] = [0, 5, 10, 15, 30, 45, 60]; // Minutes ahead, as the game samples a race
+pub const PIT_LIMIT: f32 = 80.0; // kph
+pub const FUEL_PER_LAP: f32 = 1.6; // kg
+pub const ERS_STORE: f32 = 4_000_000.0; // J
+
+const GRID_START: f32 = 10.0; // Pole sits this far behind the line...
+const GRID_GAP: f32 = 8.0; // ...and everyone else this much further back per slot
+const MARK: f32 = 50.0; // Timing loop spacing for the gaps, in metres
+const MARK_OFFSET: f32 = 500.0; // Keeps the grid, behind the line, at a positive mark
+const PIT_ENTRY: f32 = 200.0; // Pit lane starts this far before the line, the box is on the line...
+const PIT_EXIT: f32 = 200.0; // ...and it rejoins this far after it
+const PIT_STOP: f32 = 2.5; // Seconds stationary for a set of tyres
+const SHAPE: f32 = 0.35; // How much quicker the straights are than the corners
+const CORNERS: f32 = 3.0; // Slow / fast cycles per lap
+const SPEED_TRAP: f32 = 1.0 / 12.0; // Fraction of the lap at the end of the first straight
+const POINTS: [u8; 10] = [25, 18, 15, 12, 10, 8, 6, 4, 2, 1];
+
+// Driver id, team, race number, nationality id and pace over the quickest car.
+const ROSTER: [(u8, TeamId, u8, u8, f32); 22] = [
+    (54, TeamId::McLaren, 4, 10, 0.0),
+    (112, TeamId::McLaren, 81, 3, 0.1),
+    (9, TeamId::RedBullRacing, 1, 22, 0.05),
+    (94, TeamId::RedBullRacing, 22, 43, 0.6),
+    (58, TeamId::Ferrari, 16, 53, 0.2),
+    (7, TeamId::Ferrari, 44, 10, 0.3),
+    (50, TeamId::Mercedes, 63, 10, 0.25),
+    (165, TeamId::Mercedes, 12, 41, 0.45),
+    (62, TeamId::Williams, 23, 80, 0.7),
+    (0, TeamId::Williams, 55, 77, 0.75),
+    (3, TeamId::AstonMartin, 14, 77, 0.8),
+    (19, TeamId::AstonMartin, 18, 13, 1.1),
+    (113, TeamId::RB, 30, 54, 0.9),
+    (149, TeamId::RB, 6, 28, 0.85),
+    (17, TeamId::Haas, 31, 28, 0.95),
+    (147, TeamId::Haas, 87, 10, 0.9),
+    (10, TeamId::Sauber, 27, 29, 1.0),
+    (161, TeamId::Sauber, 5, 9, 1.1),
+    (59, TeamId::Alpine, 10, 28, 1.0),
+    (162, TeamId::Alpine, 43, 2, 1.2),
+    (170, TeamId::APXGP25, 7, 1, 1.3),
+    (171, TeamId::APXGP25, 9, 10, 1.4),
+];
+
+// xorshift64*, plenty for lap time noise and keeps a race repeatable with --seed.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    pub fn roll(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // Uniform between lo and hi.
+    pub fn range(&mut self, lo: f32, hi: f32) -> f32 {
+        lo + (hi - lo) * (self.roll() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tyre {
+    Soft,
+    Medium,
+    Hard,
+    Inter,
+    Wet,
+}
+
+impl Tyre {
+    pub fn compound(self) -> (ActualCompound, VisualCompound) {
+        match self {
+            Tyre::Soft => (ActualCompound::C4, VisualCompound::Soft),
+            Tyre::Medium => (ActualCompound::C3, VisualCompound::Medium),
+            Tyre::Hard => (ActualCompound::C2, VisualCompound::Hard),
+            Tyre::Inter => (ActualCompound::Inter, VisualCompound::Inter),
+            Tyre::Wet => (ActualCompound::Wet, VisualCompound::Wet),
+        }
+    }
+
+    // Percent of the tread gone per lap.
+    fn wear(self) -> f32 {
+        match self {
+            Tyre::Soft => 4.0,
+            Tyre::Medium => 2.8,
+            Tyre::Hard => 2.0,
+            Tyre::Inter => 2.6,
+            Tyre::Wet => 2.2,
+        }
+    }
+
+    // What the conditions call for, None while slicks will do.
+    fn wetFor(rain: u8) -> Option<Tyre> {
+        match weather(rain) {
+            Weather::RainLight => Some(Tyre::Inter),
+            Weather::RainHeavy | Weather::RainStorm => Some(Tyre::Wet),
+            _ => None,
+        }
+    }
+
+    // Seconds a lap lost for being on the wrong rubber.
+    fn mismatch(self, rain: u8) -> f32 {
+        match (self, Tyre::wetFor(rain)) {
+            (Tyre::Inter, Some(Tyre::Inter)) | (Tyre::Wet, Some(Tyre::Wet)) => 0.0,
+            (Tyre::Inter, None) => 4.0,
+            (Tyre::Wet, None) => 7.0,
+            (Tyre::Wet, Some(_)) => 2.0,
+            (Tyre::Inter, Some(_)) => 4.0,
+            (_, Some(Tyre::Inter)) => 8.0,
+            (_, Some(_)) => 15.0,
+            (_, None) => 0.0,
+        }
+    }
+
+    // Seconds a lap against fresh mediums in the dry.
+    fn delta(self, rain: u8) -> f32 {
+        let compound = match self {
+            Tyre::Soft => -0.6,
+            Tyre::Hard => 0.5,
+            _ => 0.0,
+        };
+
+        compound + rain as f32 * 0.06 + self.mismatch(rain)
+    }
+}
+
+impl FromStr for Tyre {
+    type Err = String;
+
+    fn from_str(word: &str) -> Result<Self, Self::Err> {
+        match word {
+            "soft" => Ok(Tyre::Soft),
+            "medium" => Ok(Tyre::Medium),
+            "hard" => Ok(Tyre::Hard),
+            "inter" => Ok(Tyre::Inter),
+            "wet" => Ok(Tyre::Wet),
+            _ => Err(format!("unknown tyre '{word}'")),
+        }
+    }
+}
+
+// What the sky is doing at this chance of rain.
+pub fn weather(rain: u8) -> Weather {
+    match rain {
+        0 => Weather::Clear,
+        1..=39 => Weather::Overcast,
+        40..=74 => Weather::RainLight,
+        75..=89 => Weather::RainHeavy,
+        _ => Weather::RainStorm,
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Pit {
+    #[default]
+    None,
+    Entering,     // In the lane, heading for the box
+    Stopped(f32), // In the box, seconds still to go
+    Leaving,      // In the lane, heading for the exit
+}
+
+#[derive(Debug, Clone)]
+pub struct Entrant {
+    pub driverId: DriverId,
+    pub teamId: TeamId,
+    pub raceNumber: u8,
+    pub nationality: Nationality,
+    pub pace: f32, // Dry lap on fresh mediums, in seconds
+    pub grid: u8,
+    pub position: u8,
+
+    // On track
+    pub lapNum: u8,
+    pub lapDistance: f32,
+    pub totalDistance: f32,
+    pub sector: u8,
+    pub speed: f32, // m/s
+    pub accel: f32, // m/s², for throttle and brake
+    pub gapAhead: f32,
+    pub gapLeader: f32,
+    pub safetyCarDelta: f32,
+    marks: Vec<f32>, // Session time at every MARK metres of totalDistance
+
+    // Timing
+    pub lapTime: f32,
+    pub sector1MS: u32,
+    pub sector2MS: u32,
+    pub lastLapMS: u32,
+    pub laps: Vec<LapHistory>,
+    variation: f32, // This lap's noise, in seconds
+    pub speedTrap: f32,
+    pub speedTrapLap: u8,
+
+    // Car
+    pub tyre: Tyre,
+    pub tyreAge: u8,
+    pub wear: f32,
+    pub stints: Vec<(u8, Tyre)>, // Finished stints, end lap and tyre
+    pub fuel: f32,
+    pub ers: f32,
+    pub ersDeployed: f32,
+    pub ersHarvested: f32,
+    pub engineWear: u8,
+    pub drsAllowed: bool,
+    pub drs: bool,
+    pub flag: ZoneFlag,
+
+    // Pit lane
+    pub pit: Pit,
+    pub boxThisLap: bool,
+    pub nextTyre: Option<Tyre>,
+    pub numPitStops: u8,
+    pub pitLaneTime: f32,
+    pub pitStopTime: f32,
+
+    // Stewards
+    pub penalties: u8,
+    pub penaltiesTime: u8,
+    pub numPenalties: u8,
+    pub driveThroughs: u8,
+    pub stopGoes: Vec<u8>,
+
+    pub status: ResultStatus,
+    pub finishTime: f32,
+}
+
+impl Entrant {
+    pub fn name(&self) -> &'static str {
+        self.driverId.name().unwrap_or("Unknown")
+    }
+
+    pub fn isRunning(&self) -> bool {
+        matches!(self.status, ResultStatus::Active)
+    }
+
+    pub fn isInPitLane(&self) -> bool {
+        self.pit != Pit::None
+    }
+
+    pub fn bestLapMS(&self) -> u32 {
+        self.laps.iter().map(|l| l.lapTimeInMS).min().unwrap_or(0)
+    }
+
+    fn mark(&mut self, time: f32) {
+        while (self.totalDistance + MARK_OFFSET) / MARK >= self.marks.len() as f32 {
+            self.marks.push(time);
+        }
+    }
+
+    // At the flag, with any time penalties not served in the pits added on.
+    fn raceTime(&self) -> f32 {
+        self.finishTime + self.penalties as f32
+    }
+
+    // Seconds behind `other` at the last timing loop we both went through,
+    // or at the flag, penalties included, once both have finished on the same lap.
+    fn gapTo(&self, other: &Entrant) -> f32 {
+        let isFinished = |car: &Entrant| matches!(car.status, ResultStatus::Finished);
+        if isFinished(self) && isFinished(other) && self.laps.len() == other.laps.len() {
+            return (self.raceTime() - other.raceTime()).max(0.0);
+        }
+
+        let idx = self.marks.len() - 1;
+
+        match other.marks.get(idx) {
+            Some(t) if *t > 0.0 => (self.marks[idx] - t).max(0.0),
+            _ => 0.0,
+        }
+    }
+
+    // Ordered by laps done, then distance, finishers on the same lap by race time.
+    fn standing(&self, length: f32) -> (u8, f32, f32) {
+        match self.status {
+            ResultStatus::Finished => (0, -(self.laps.len() as f32 * length), self.raceTime()),
+            ResultStatus::Active => (0, -self.totalDistance, f32::MAX),
+            _ => (1, -self.totalDistance, f32::MAX),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Grid, // Waiting for the lights
+    Lights { lit: u8, next: f32 },
+    Racing,
+    Finished,
+}
+
+// An event packet waiting to go out.
+pub struct Event {
+    pub code: [u8; 4],
+    pub details: EventDetails,
+}
+
+pub struct Race {
+    pub sessionUID: u64,
+    pub trackId: TrackId,
+    pub trackLength: f32,
+    pub totalLaps: u8,
+    pub baseLap: f32,
+    pub time: f32,
+    pub frame: u32,
+    pub phase: Phase,
+    pub cars: Vec<Entrant>,
+    pub order: Vec<usize>, // Car indices by race position
+    pub leaderLap: u8,
+    pub lightsOut: f32,
+    pub isChequered: bool,
+
+    // Race control
+    pub safetyCar: SafetyCar,
+    safetyCarEnd: u8, // Leader lap it comes in at
+    isSafetyCarReturning: bool,
+    pub numSafetyCarPeriods: u8,
+    pub numVirtualSafetyCarPeriods: u8,
+    pub zones: [ZoneFlag; ZONES],
+    yellows: Vec<(usize, u8)>, // Zone, and the leader lap it goes green again
+    pub isDrsEnabled: bool,
+    drsLap: u8,
+
+    pub rain: u8,
+    fastestLapMS: u32,
+    fastestTrap: (u8, f32),
+    incidents: Vec<Incident>, // Still to come
+    pub events: Vec<Event>,
+    rng: Rng,
+}
+
+impl Race {
+    pub fn new(config: &Config) -> Self {
+        let mut rng = Rng::new(config.seed);
+        let length = config.trackLength as f32;
+
+        let mut cars: Vec<Entrant> = ROSTER[..config.cars as usize]
+            .iter()
+            .map(|(driverId, teamId, raceNumber, nationality, pace)| {
+                let tyre =
+                    [Tyre::Soft, Tyre::Medium, Tyre::Medium, Tyre::Hard][(rng.roll() % 4) as usize];
+
+                Entrant {
+                    driverId: DriverId { id: *driverId },
+                    teamId: *teamId,
+                    raceNumber: *raceNumber,
+                    nationality: Nationality { id: *nationality },
+                    pace: config.lapTime + pace + rng.range(0.0, 0.3),
+                    grid: 0,
+                    position: 0,
+                    lapNum: 1,
+                    lapDistance: 0.0,
+                    totalDistance: 0.0,
+                    sector: 0,
+                    speed: 0.0,
+                    accel: 0.0,
+                    gapAhead: 0.0,
+                    gapLeader: 0.0,
+                    safetyCarDelta: 0.0,
+                    marks: vec![],
+                    lapTime: 0.0,
+                    sector1MS: 0,
+                    sector2MS: 0,
+                    lastLapMS: 0,
+                    laps: vec![],
+                    variation: rng.range(-0.3, 0.3),
+                    speedTrap: 0.0,
+                    speedTrapLap: 255,
+                    tyre,
+                    tyreAge: 0,
+                    wear: 0.0,
+                    stints: vec![],
+                    fuel: (config.laps as f32 * FUEL_PER_LAP + 1.0).min(110.0),
+                    ers: ERS_STORE,
+                    ersDeployed: 0.0,
+                    ersHarvested: 0.0,
+                    engineWear: rng.range(2.0, 20.0) as u8,
+                    drsAllowed: false,
+                    drs: false,
+                    flag: ZoneFlag::Green,
+                    pit: Pit::None,
+                    boxThisLap: false,
+                    nextTyre: None,
+                    numPitStops: 0,
+                    pitLaneTime: 0.0,
+                    pitStopTime: 0.0,
+                    penalties: 0,
+                    penaltiesTime: 0,
+                    numPenalties: 0,
+                    driveThroughs: 0,
+                    stopGoes: vec![],
+                    status: ResultStatus::Active,
+                    finishTime: 0.0,
+                }
+            })
+            .collect();
+
+        // Qualifying, near enough: pace plus a bit of luck.
+        let mut quali: Vec<(usize, f32)> = cars
+            .iter()
+            .enumerate()
+            .map(|(idx, car)| (idx, car.pace + rng.range(0.0, 0.6)))
+            .collect();
+        quali.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        for (slot, (idx, _)) in quali.iter().enumerate() {
+            let car = &mut cars[*idx];
+            car.grid = slot as u8 + 1;
+            car.position = car.grid;
+            car.lapDistance = -(GRID_START + slot as f32 * GRID_GAP);
+            car.totalDistance = car.lapDistance;
+            car.mark(0.0);
+        }
+
+        let mut incidents = config.incidents.clone();
+        incidents.sort_by_key(|i| i.lap);
+
+        let mut race = Self {
+            sessionUID: rng.roll(),
+            trackId: config.trackId,
+            trackLength: length,
+            totalLaps: config.laps,
+            baseLap: config.lapTime,
+            time: 0.0,
+            frame: 0,
+            phase: Phase::Grid,
+            order: quali.iter().map(|(idx, _)| *idx).collect(),
+            cars,
+            leaderLap: 1,
+            lightsOut: 0.0,
+            isChequered: false,
+            safetyCar: SafetyCar::Ready,
+            safetyCarEnd: 0,
+            isSafetyCarReturning: false,
+            numSafetyCarPeriods: 0,
+            numVirtualSafetyCarPeriods: 0,
+            zones: [ZoneFlag::Green; ZONES],
+            yellows: vec![],
+            isDrsEnabled: false,
+            drsLap: 3,
+            rain: 0,
+            fastestLapMS: u32::MAX,
+            fastestTrap: (255, 0.0),
+            incidents,
+            events: vec![],
+            rng,
+        };
+
+        race.event(
+            *b"SSTA",
+            EventDetails {
+                sessionStarted: SessionStarted {},
+            },
+        );
+        race
+    }
+
+    pub fn isOver(&self) -> bool {
+        self.phase == Phase::Finished
+    }
+
+    pub fn isNeutralised(&self) -> bool {
+        matches!(self.safetyCar, SafetyCar::Deployed | SafetyCar::Virtual)
+    }
+
+    fn event(&mut self, code: [u8; 4], details: EventDetails) {
+        self.events.push(Event { code, details });
+    }
+
+    pub fn step(&mut self, dt: f32) {
+        self.time += dt;
+        self.frame += 1;
+
+        match self.phase {
+            Phase::Grid if self.time >= 3.0 => {
+                self.phase = Phase::Lights {
+                    lit: 0,
+                    next: self.time,
+                };
+            }
+            Phase::Lights { lit, next } if self.time >= next => {
+                if lit < 5 {
+                    let hold = if lit == 4 {
+                        self.rng.range(0.2, 3.0)
+                    } else {
+                        1.0
+                    };
+                    self.phase = Phase::Lights {
+                        lit: lit + 1,
+                        next: next + hold,
+                    };
+                    self.event(
+                        *b"STLG",
+                        EventDetails {
+                            startLights: StartLights { numLights: lit + 1 },
+                        },
+                    );
+                } else {
+                    self.phase = Phase::Racing;
+                    self.lightsOut = self.time;
+                    self.event(
+                        *b"LGOT",
+                        EventDetails {
+                            lightsOut: LightsOut {},
+                        },
+                    );
+                    self.event(
+                        *b"DRSD",
+                        EventDetails {
+                            drsDisabled: DRSDisabled { reason: 3 },
+                        },
+                    );
+                }
+            }
+            _ => {}
+        }
+
+        if self.phase != Phase::Racing {
+            return;
+        }
+
+        self.script();
+        self.raceControl();
+        self.drive(dt);
+        self.classify();
+        self.flags();
+
+        if self.cars.iter().all(|car| !car.isRunning()) {
+            self.phase = Phase::Finished;
+        }
+    }
+
+    // Anything scripted for the lap the leader is on.
+    fn script(&mut self) {
+        while let Some(pos) = self.incidents.iter().position(|i| i.lap <= self.leaderLap) {
+            let incident = self.incidents.remove(pos);
+            self.apply(incident.action);
+        }
+    }
+
+    fn apply(&mut self, action: Action) {
+        let lap = self.leaderLap;
+
+        match action {
+            Action::SafetyCar(laps) | Action::VirtualSafetyCar(laps) if !self.isNeutralised() => {
+                let isFull = matches!(action, Action::SafetyCar(_));
+                self.safetyCar = if isFull {
+                    SafetyCar::Deployed
+                } else {
+                    SafetyCar::Virtual
+                };
+                self.safetyCarEnd = lap + laps;
+                self.isSafetyCarReturning = false;
+
+                if isFull {
+                    self.numSafetyCarPeriods += 1;
+                } else {
+                    self.numVirtualSafetyCarPeriods += 1;
+                }
+
+                println!("L{lap:<2} {} for {laps} laps", self.safetyCar);
+                let safetyCarType = self.safetyCar;
+                self.event(
+                    *b"SCAR",
+                    EventDetails {
+                        safetyCar: SafetyCarEvent {
+                            safetyCarType,
+                            eventType: 0,
+                        },
+                    },
+                );
+                self.disableDrs(1);
+            }
+            Action::SafetyCar(_) | Action::VirtualSafetyCar(_) => {
+                println!("L{lap:<2} {} already out, ignored", self.safetyCar);
+            }
+            Action::Pit(car, tyre) => {
+                let car = &mut self.cars[car as usize];
+                println!("L{lap:<2} {} told to box", car.name());
+                car.boxThisLap = true;
+                car.nextTyre = tyre;
+            }
+            Action::Penalty(car, seconds) => {
+                self.cars[car as usize].penalties += seconds;
+                self.cars[car as usize].penaltiesTime += seconds;
+                self.penalty(
+                    car,
+                    PenaltyType::TimePenalty,
+                    InfringementType::CornerCuttingGainedTime,
+                    seconds,
+                );
+            }
+            Action::DriveThrough(car) => {
+                self.cars[car as usize].driveThroughs += 1;
+                self.penalty(
+                    car,
+                    PenaltyType::DriveThrough,
+                    InfringementType::PitLaneSpeeding,
+                    0,
+                );
+            }
+            Action::StopGo(car, seconds) => {
+                self.cars[car as usize].stopGoes.push(seconds);
+                self.penalty(
+                    car,
+                    PenaltyType::StopGo,
+                    InfringementType::CollisionFailedToHandBackPositionSingle,
+                    seconds,
+                );
+            }
+            Action::Rain(rain) => {
+                self.rain = rain;
+                println!("L{lap:<2} {} ({rain}% rain)", weather(rain));
+
+                if Tyre::wetFor(rain).is_some() {
+                    self.disableDrs(0);
+                }
+
+                // Everyone on the wrong rubber comes in at the end of the lap.
+                for car in self.cars.iter_mut().filter(|car| car.isRunning()) {
+                    if car.tyre.mismatch(rain) > 0.0 {
+                        car.boxThisLap = true;
+                    }
+                }
+            }
+            Action::Retire(car) => {
+                let length = self.trackLength;
+                let entrant = &mut self.cars[car as usize];
+                if !entrant.isRunning() {
+                    return;
+                }
+
+                println!("L{lap:<2} {} retires", entrant.name());
+                entrant.status = ResultStatus::Retired;
+                entrant.drs = false;
+
+                // Yellow where they stopped until the leader is round again.
+                let zone = ((entrant.lapDistance.max(0.0) / length) * ZONES as f32) as usize;
+                self.yellows.push((zone.min(ZONES - 1), lap + 1));
+                self.event(
+                    *b"RTMT",
+                    EventDetails {
+                        retirement: Retirement {
+                            vehicleIdx: car,
+                            reason: 8,
+                        },
+                    },
+                );
+            }
+        }
+    }
+
+    fn penalty(
+        &mut self,
+        car: u8,
+        penaltyType: PenaltyType,
+        infringementType: InfringementType,
+        time: u8,
+    ) {
+        let entrant = &mut self.cars[car as usize];
+        entrant.numPenalties += 1;
+        println!(
+            "L{:<2} {} {penaltyType} for {infringementType}",
+            entrant.lapNum,
+            entrant.name()
+        );
+
+        let penalty = Penalty {
+            penaltyType,
+            infringementType,
+            vehicleIdx: car,
+            otherVehicleIdx: 255,
+            time,
+            lapNum: entrant.lapNum,
+            placesGained: 0,
+        };
+        self.event(*b"PENA", EventDetails { penalty });
+    }
+
+    fn disableDrs(&mut self, reason: u8) {
+        if self.isDrsEnabled {
+            self.isDrsEnabled = false;
+            self.event(
+                *b"DRSD",
+                EventDetails {
+                    drsDisabled: DRSDisabled { reason },
+                },
+            );
+        }
+    }
+
+    // Bring the safety car in, and DRS back once it's allowed.
+    fn raceControl(&mut self) {
+        let safetyCarType = self.safetyCar;
+
+        if self.isNeutralised()
+            && !self.isSafetyCarReturning
+            && self.leaderLap + 1 >= self.safetyCarEnd
+        {
+            self.isSafetyCarReturning = true;
+            self.event(
+                *b"SCAR",
+                EventDetails {
+                    safetyCar: SafetyCarEvent {
+                        safetyCarType,
+                        eventType: 1,
+                    },
+                },
+            );
+        }
+
+        if self.isNeutralised() && self.leaderLap >= self.safetyCarEnd {
+            println!(
+                "L{:<2} {} in, racing resumes",
+                self.leaderLap, self.safetyCar
+            );
+            self.safetyCar = SafetyCar::Ready;
+            self.drsLap = self.leaderLap + 2;
+            self.event(
+                *b"SCAR",
+                EventDetails {
+                    safetyCar: SafetyCarEvent {
+                        safetyCarType,
+                        eventType: 3,
+                    },
+                },
+            );
+
+            for car in &mut self.cars {
+                car.safetyCarDelta = 0.0;
+            }
+        }
+
+        let isAllowed = self.leaderLap >= self.drsLap
+            && Tyre::wetFor(self.rain).is_none()
+            && !self.isNeutralised();
+        if isAllowed && !self.isDrsEnabled {
+            self.isDrsEnabled = true;
+            self.event(
+                *b"DRSE",
+                EventDetails {
+                    drsEnabled: DRSEnabled {},
+                },
+            );
+        }
+    }
+
+    fn drive(&mut self, dt: f32) {
+        let length = self.trackLength;
+        let isNeutralised = self.isNeutralised();
+
+        // Metres to the car in front, so the field can bunch up behind the safety car.
+        let mut behind = vec![f32::MAX; self.cars.len()];
+        for pair in self.order.windows(2) {
+            behind[pair[1]] = self.cars[pair[0]].totalDistance - self.cars[pair[1]].totalDistance;
+        }
+
+        for (idx, behind) in behind.into_iter().enumerate() {
+            if matches!(self.cars[idx].status, ResultStatus::Retired) {
+                self.cars[idx].speed = 0.0;
+                continue;
+            }
+
+            let target = self.targetSpeed(idx, behind);
+            let car = &mut self.cars[idx];
+            let before = car.lapDistance;
+
+            let previous = car.speed;
+            car.speed = if target > car.speed {
+                (car.speed + 11.0 * dt).min(target)
+            } else {
+                (car.speed - 40.0 * dt).max(target)
+            };
+            car.accel = (car.speed - previous) / dt;
+
+            let distance = car.speed * dt;
+            car.lapDistance += distance;
+            car.totalDistance += distance;
+            car.lapTime += dt;
+            car.mark(self.time);
+
+            if !car.isRunning() {
+                continue;
+            }
+
+            car.wear += distance / length * car.tyre.wear();
+            car.fuel = (car.fuel - distance / length * FUEL_PER_LAP).max(0.0);
+
+            // Harvest under braking, deploy on the way out of the corners.
+            if car.accel < -0.5 {
+                car.ers = (car.ers + 200_000.0 * dt).min(ERS_STORE);
+                car.ersHarvested += 200_000.0 * dt;
+            } else if car.accel > 0.5 && car.ers > 0.0 {
+                car.ers = (car.ers - 120_000.0 * dt).max(0.0);
+                car.ersDeployed += 120_000.0 * dt;
+            }
+
+            if isNeutralised {
+                let reference = length / (self.baseLap * 1.6);
+                car.safetyCarDelta += dt * (car.speed / reference - 1.0);
+            }
+
+            // Pit lane
+            if car.isInPitLane() {
+                car.pitLaneTime += dt;
+            }
+            match car.pit {
+                Pit::Stopped(left) => {
+                    car.pitStopTime += dt;
+                    car.pit = if left > dt {
+                        Pit::Stopped(left - dt)
+                    } else {
+                        Pit::Leaving
+                    };
+                }
+                Pit::Leaving if car.lapDistance >= PIT_EXIT && car.lapDistance < length / 2.0 => {
+                    car.pit = Pit::None;
+                }
+                Pit::None
+                    if car.boxThisLap
+                        && before < length - PIT_ENTRY
+                        && car.lapDistance >= length - PIT_ENTRY =>
+                {
+                    car.pit = Pit::Entering;
+                    car.pitLaneTime = 0.0;
+                    car.pitStopTime = 0.0;
+                    car.drs = false;
+                }
+                _ => {}
+            }
+
+            // Sectors
+            if car.sector == 0 && car.lapDistance >= length / 3.0 {
+                car.sector1MS = (car.lapTime * 1000.0) as u32;
+                car.sector = 1;
+            }
+            if car.sector == 1 && car.lapDistance >= length * 2.0 / 3.0 {
+                car.sector2MS = (car.lapTime * 1000.0) as u32 - car.sector1MS;
+                car.sector = 2;
+            }
+
+            // DRS, on the straights only
+            let fraction = car.lapDistance.max(0.0) / length;
+            car.drs = car.drsAllowed && (TAU * CORNERS * fraction).sin() > 0.5;
+
+            let trap = length * SPEED_TRAP;
+            if before < trap && car.lapDistance >= trap {
+                self.speedTrap(idx);
+            }
+
+            if self.cars[idx].lapDistance >= length {
+                self.crossLine(idx);
+            }
+        }
+    }
+
+    // Where this car wants to be going, in m/s.
+    fn targetSpeed(&self, idx: usize, behind: f32) -> f32 {
+        let car = &self.cars[idx];
+        let length = self.trackLength;
+
+        match car.pit {
+            Pit::Entering | Pit::Leaving => return PIT_LIMIT / 3.6,
+            Pit::Stopped(_) => return 0.0,
+            Pit::None => {}
+        }
+
+        let lap = car.pace
+            + car.variation
+            + car.tyre.delta(self.rain)
+            + car.wear * 0.04
+            + car.fuel * 0.03;
+
+        // Time round a lap of b(1 + A sin) is L / (b sqrt(1 - A²)), so this averages out to `lap`.
+        let base = length / (lap * (1.0 - SHAPE * SHAPE).sqrt());
+        let fraction = car.lapDistance.max(0.0) / length;
+        let speed = base * (1.0 + SHAPE * (TAU * CORNERS * fraction).sin());
+
+        match car.status {
+            // Cool down lap, then park up half way round.
+            ResultStatus::Finished if car.lapDistance < length / 2.0 => speed * 0.5,
+            ResultStatus::Finished => 0.0,
+            _ => match self.safetyCar {
+                SafetyCar::Deployed if behind > 30.0 => speed.min(base * 0.75),
+                SafetyCar::Deployed => speed.min(base * 0.6),
+                SafetyCar::Virtual => speed.min(base * 0.65),
+                _ => speed,
+            },
+        }
+    }
+
+    fn speedTrap(&mut self, idx: usize) {
+        let car = &mut self.cars[idx];
+        let kph = car.speed * 3.6;
+
+        if kph <= car.speedTrap {
+            return;
+        }
+
+        car.speedTrap = kph;
+        car.speedTrapLap = car.lapNum;
+
+        let isOverall = kph > self.fastestTrap.1;
+        if isOverall {
+            self.fastestTrap = (idx as u8, kph);
+        }
+
+        let speedTrap = SpeedTrap {
+            vehicleIdx: idx as u8,
+            speed: kph,
+            isOverallFastestInSession: isOverall as u8,
+            isDriverFastestInSession: 1,
+            fastestVehicleIdxInSession: self.fastestTrap.0,
+            fastestSpeedInSession: self.fastestTrap.1,
+        };
+        self.event(*b"SPTP", EventDetails { speedTrap });
+    }
+
+    fn crossLine(&mut self, idx: usize) {
+        let length = self.trackLength;
+        let isNeutralised = self.isNeutralised();
+        let car = &mut self.cars[idx];
+
+        car.lapDistance -= length;
+        let over = car.lapDistance / car.speed.max(1.0);
+        let lapMS = ((car.lapTime - over) * 1000.0) as u32;
+        let sector3MS = lapMS.saturating_sub(car.sector1MS + car.sector2MS);
+
+        car.laps.push(LapHistory {
+            lapTimeInMS: lapMS,
+            sector1TimeMSPart: (car.sector1MS % 60_000) as u16,
+            sector1TimeMinutesPart: (car.sector1MS / 60_000) as u8,
+            sector2TimeMSPart: (car.sector2MS % 60_000) as u16,
+            sector2TimeMinutesPart: (car.sector2MS / 60_000) as u8,
+            sector3TimeMSPart: (sector3MS % 60_000) as u16,
+            sector3TimeMinutesPart: (sector3MS / 60_000) as u8,
+            lapValidBitFlags: Valid::All,
+        });
+        car.lastLapMS = lapMS;
+        car.lapTime = over;
+        car.sector = 0;
+        car.tyreAge += 1;
+        car.ersDeployed = 0.0;
+        car.ersHarvested = 0.0;
+        car.variation = self.rng.range(-0.4, 0.4);
+
+        if lapMS < self.fastestLapMS {
+            self.fastestLapMS = lapMS;
+            let fastestLap = FastestLap {
+                vehicleIdx: idx as u8,
+                lapTime: lapMS as f32 / 1000.0,
+            };
+            self.event(*b"FTLP", EventDetails { fastestLap });
+        }
+
+        let car = &mut self.cars[idx];
+
+        // Chequered flag for the leader, and everyone after them as they come round.
+        if car.lapNum >= self.totalLaps || self.isChequered {
+            car.status = ResultStatus::Finished;
+            car.finishTime = self.time - over;
+            car.drs = false;
+            car.drsAllowed = false;
+
+            if !self.isChequered {
+                self.isChequered = true;
+                println!("L{:<2} Chequered flag, {} wins", car.lapNum, car.name());
+                self.event(
+                    *b"CHQF",
+                    EventDetails {
+                        chequeredFlag: ChequeredFlag {},
+                    },
+                );
+                self.event(
+                    *b"RCWN",
+                    EventDetails {
+                        raceWinner: RaceWinner {
+                            vehicleIdx: idx as u8,
+                        },
+                    },
+                );
+            }
+            return;
+        }
+
+        car.lapNum += 1;
+
+        if car.pit == Pit::Entering {
+            self.pitBox(idx);
+        }
+
+        // Strategy for the lap just started.
+        let lapsLeft = self.totalLaps - self.cars[idx].lapNum;
+        let car = &mut self.cars[idx];
+        let isWorn = car.wear > 65.0 && lapsLeft >= 3;
+        let isCheapStop = isNeutralised && car.wear > 35.0 && lapsLeft >= 5;
+        let owesPenalty = car.driveThroughs > 0 || !car.stopGoes.is_empty();
+
+        if car.pit == Pit::None
+            && (isWorn || isCheapStop || owesPenalty || car.tyre.mismatch(self.rain) > 0.0)
+        {
+            car.boxThisLap = true;
+        }
+    }
+
+    // At the box, which sits on the line.
+    fn pitBox(&mut self, idx: usize) {
+        let rain = self.rain;
+        let lapsLeft = self.totalLaps - self.cars[idx].lapNum + 1;
+        let stop = PIT_STOP + self.rng.range(0.0, 0.8);
+        let car = &mut self.cars[idx];
+        car.boxThisLap = false;
+
+        if car.driveThroughs > 0 {
+            car.driveThroughs -= 1;
+            car.pit = Pit::Leaving;
+            let served = DriveThroughPenaltyServed {
+                vehicleIdx: idx as u8,
+            };
+            self.event(
+                *b"DTSV",
+                EventDetails {
+                    driveThroughPenaltyServed: served,
+                },
+            );
+            return;
+        }
+
+        if !car.stopGoes.is_empty() {
+            let stopTime = car.stopGoes.remove(0) as f32;
+            car.pit = Pit::Stopped(stopTime);
+            let served = StopGoPenaltyServed {
+                vehicleIdx: idx as u8,
+                stopTime,
+            };
+            self.event(
+                *b"SGSV",
+                EventDetails {
+                    stopGoPenaltyServed: served,
+                },
+            );
+            return;
+        }
+
+        // Wet rubber if it's wet, otherwise something to get to the end on.
+        let tyre = car.nextTyre.take().unwrap_or(match Tyre::wetFor(rain) {
+            Some(wet) => wet,
+            None if lapsLeft <= 12 => Tyre::Soft,
+            None if car.tyre == Tyre::Hard => Tyre::Medium,
+            None => Tyre::Hard,
+        });
+
+        // Any time penalties are served before the crew touch the car.
+        car.pit = Pit::Stopped(stop + car.penalties as f32);
+        car.penalties = 0;
+        car.stints.push((car.lapNum - 1, car.tyre));
+        car.tyre = tyre;
+        car.tyreAge = 0;
+        car.wear = 0.0;
+        car.numPitStops += 1;
+    }
+
+    // Running order, gaps and DRS.
+    fn classify(&mut self) {
+        let length = self.trackLength;
+        let cars = &self.cars;
+
+        self.order.sort_by(|a, b| {
+            let (a, b) = (cars[*a].standing(length), cars[*b].standing(length));
+            a.0.cmp(&b.0)
+                .then(a.1.total_cmp(&b.1))
+                .then(a.2.total_cmp(&b.2))
+        });
+
+        let leader = self.order[0];
+        self.leaderLap = self.cars[leader].lapNum;
+
+        for (pos, idx) in self.order.clone().into_iter().enumerate() {
+            self.cars[idx].position = pos as u8 + 1;
+
+            if !self.cars[idx].isRunning() {
+                continue;
+            }
+
+            if pos == 0 {
+                self.cars[idx].gapAhead = 0.0;
+                self.cars[idx].gapLeader = 0.0;
+                self.cars[idx].drsAllowed = false;
+                continue;
+            }
+
+            let ahead = self.order[pos - 1];
+            let gapAhead = self.cars[idx].gapTo(&self.cars[ahead]);
+            let gapLeader = self.cars[idx].gapTo(&self.cars[leader]);
+
+            let car = &mut self.cars[idx];
+            car.gapAhead = gapAhead;
+            car.gapLeader = gapLeader;
+            car.drsAllowed =
+                self.isDrsEnabled && gapAhead > 0.0 && gapAhead < 1.0 && !car.isInPitLane();
+        }
+    }
+
+    // Marshal zones and what each driver is being shown.
+    fn flags(&mut self) {
+        let leaderLap = self.leaderLap;
+        self.yellows.retain(|(_, until)| *until > leaderLap);
+
+        let flag = if self.isNeutralised() {
+            ZoneFlag::Yellow
+        } else {
+            ZoneFlag::Green
+        };
+        self.zones = [flag; ZONES];
+        for (zone, _) in &self.yellows {
+            self.zones[*zone] = ZoneFlag::Yellow;
+        }
+
+        let length = self.trackLength;
+        for car in &mut self.cars {
+            let zone = ((car.lapDistance.max(0.0) / length) * ZONES as f32) as usize;
+            car.flag = match car.status {
+                ResultStatus::Active => self.zones[zone.min(ZONES - 1)],
+                _ => ZoneFlag::None,
+            };
+        }
+    }
+
+    // Chance of rain at a leader lap, from what is scripted to come.
+    fn rainAt(&self, lap: f32) -> u8 {
+        self.incidents
+            .iter()
+            .filter_map(|i| match i.action {
+                Action::Rain(rain) if i.lap as f32 <= lap => Some((i.lap, rain)),
+                _ => None,
+            })
+            .max_by_key(|(lap, _)| *lap)
+            .map_or(self.rain, |(_, rain)| rain)
+    }
+
+    pub fn forecast(&self) -> Vec<WeatherForecast> {
+        let mut previous: Option<(i8, i8)> = None;
+
+        FORECAST
+            .iter()
+            .map(|offset| {
+                let lap = self.leaderLap as f32 + *offset as f32 * 60.0 / self.baseLap;
+                let rain = self.rainAt(lap);
+                let track = 34 - (rain / 5) as i8;
+                let air = 24 - (rain / 12) as i8;
+                let change = |now: i8, then: Option<i8>| match then {
+                    Some(then) if now > then => Temperature::Up,
+                    Some(then) if now < then => Temperature::Down,
+                    _ => Temperature::None,
+                };
+
+                let sample = WeatherForecast {
+                    sessionType: Session::Race,
+                    timeOffset: *offset,
+                    weather: weather(rain),
+                    trackTemperature: track,
+                    trackChange: change(track, previous.map(|p| p.0)),
+                    airTemperature: air,
+                    airChange: change(air, previous.map(|p| p.1)),
+                    rainPercentage: rain,
+                };
+                previous = Some((track, air));
+                sample
+            })
+            .collect()
+    }
+
+    pub fn points(position: u8) -> u8 {
+        POINTS.get(position as usize - 1).copied().unwrap_or(0)
+    }
+}
@@ -0,0 +1,81 @@
+use crate::race::Tyre;
+use std::fs;
+use std::str::FromStr;
+
+// Something for the simulator to make happen once the leader starts `lap`.
+#[derive(Debug, Clone, Copy)]
+pub struct Incident {
+    pub lap: u8,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Action {
+    SafetyCar(u8),         // sc <laps>
+    VirtualSafetyCar(u8),  // vsc <laps>
+    Pit(u8, Option<Tyre>), // pit <car> [soft|medium|hard|inter|wet]
+    Penalty(u8, u8),       // penalty <car> <seconds>
+    DriveThrough(u8),      // drivethrough <car>
+    StopGo(u8, u8),        // stopgo <car> <seconds>
+    Rain(u8),              // rain <percent>, 0 dries it out again
+    Retire(u8),            // retire <car>
+}
+
+impl Incident {
+    // The car this is about, if it is about one.
+    pub fn car(&self) -> Option<u8> {
+        match self.action {
+            Action::Pit(car, _)
+            | Action::Penalty(car, _)
+            | Action::DriveThrough(car)
+            | Action::StopGo(car, _)
+            | Action::Retire(car) => Some(car),
+            _ => None,
+        }
+    }
+}
+
+// "<lap> <kind> [args]", e.g. "12 sc 3" or "20 penalty 4 5".
+impl FromStr for Incident {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let number = |i: usize, what: &str| -> Result<u8, String> {
+            let word = words.get(i).ok_or(format!("missing {what} in '{line}'"))?;
+            word.parse()
+                .map_err(|_| format!("bad {what} '{word}' in '{line}'"))
+        };
+
+        let lap = number(0, "lap")?;
+        let action = match words.get(1).copied() {
+            Some("sc") => Action::SafetyCar(number(2, "laps")?.max(1)),
+            Some("vsc") => Action::VirtualSafetyCar(number(2, "laps")?.max(1)),
+            Some("pit") => Action::Pit(
+                number(2, "car")?,
+                words.get(3).map(|w| w.parse()).transpose()?,
+            ),
+            Some("penalty") => Action::Penalty(number(2, "car")?, number(3, "seconds")?),
+            Some("drivethrough") => Action::DriveThrough(number(2, "car")?),
+            Some("stopgo") => Action::StopGo(number(2, "car")?, number(3, "seconds")?),
+            Some("rain") => Action::Rain(number(2, "percent")?.min(100)),
+            Some("retire") => Action::Retire(number(2, "car")?),
+            Some(other) => return Err(format!("unknown incident '{other}' in '{line}'")),
+            None => return Err(format!("missing incident in '{line}'")),
+        };
+
+        Ok(Self { lap, action })
+    }
+}
+
+// One incident per line, anything after a # is a comment.
+pub fn load(path: &str) -> Result<Vec<Incident>, String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?;
+
+    text.lines()
+        .enumerate()
+        .map(|(n, line)| (n + 1, line.split('#').next().unwrap_or("").trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(n, line)| line.parse().map_err(|err| format!("{path}:{n}: {err}")))
+        .collect()
+}
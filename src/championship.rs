@@ -0,0 +1,560 @@
+use crate::export;
+use crate::results::Results;
+use colored::*;
+use pitwall::packet::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+static HEADER: &str = "Season,Weekend,Session UID,Session,Sprint,Track,Position,Driver,Team,Status,Points,Fastest Lap";
+
+// Points for each finishing position, or whatever the game awarded.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Scale {
+    Game,            // FinalClassification.points
+    Table(Vec<u16>), // P1, P2, ...
+}
+
+// "game", "f1", "sprint" or a list such as "10,6,4,3,2,1".
+impl FromStr for Scale {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "game" => Ok(Scale::Game),
+            "f1" => Ok(Scale::Table(vec![25, 18, 15, 12, 10, 8, 6, 4, 2, 1])),
+            "sprint" => Ok(Scale::Table(vec![8, 7, 6, 5, 4, 3, 2, 1])),
+            _ => value
+                .split(',')
+                .map(|points| points.trim().parse())
+                .collect::<Result<Vec<u16>, _>>()
+                .map(Scale::Table)
+                .map_err(|_| format!("bad points '{value}'")),
+        }
+    }
+}
+
+// How a league scores its season.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rules {
+    pub race: Scale,       // --points
+    pub sprint: Scale,     // --sprint-points
+    pub fastestLap: u16,   // --fastest-lap-point, for a top ten finisher in a full race
+    pub dropRounds: usize, // --drop-rounds, each driver's worst rounds don't count
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            race: Scale::Game,
+            sprint: Scale::Game,
+            fastestLap: 0,
+            dropRounds: 0,
+        }
+    }
+}
+
+// One car's result in one race, a row of the database.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Entry {
+    pub season: u32,          // PacketSession.seasonLinkIdentifier
+    pub weekend: u32,         // PacketSession.weekendLinkIdentifier, a round of the championship
+    pub sessionUID: u64,      // PacketHeader.sessionUID
+    pub session: Session,     // PacketSession.sessionType
+    pub isSprint: bool,       // Not the last race of the weekend
+    pub trackId: TrackId,     // PacketSession.trackId
+    pub position: u8,         // FinalClassification.position
+    pub driver: String,       // PacketParticipants.participants.name
+    pub team: String,         // PacketParticipants.participants.teamId
+    pub status: ResultStatus, // FinalClassification.resultStatus
+    pub points: u8,           // FinalClassification.points
+    pub isFastestLap: bool,   // Best lap of the race
+}
+
+impl Entry {
+    fn score(&self, rules: &Rules) -> u32 {
+        let scale = if self.isSprint {
+            &rules.sprint
+        } else {
+            &rules.race
+        };
+        let isClassified = matches!(self.status, ResultStatus::Finished);
+        // Position 0 is a blank or unclassified row, it scores nothing.
+        let place = (self.position as usize).checked_sub(1);
+
+        let mut points = match scale {
+            Scale::Game => self.points as u32,
+            Scale::Table(table) if isClassified => place
+                .and_then(|place| table.get(place))
+                .copied()
+                .unwrap_or(0) as u32,
+            Scale::Table(_) => 0,
+        };
+        if self.isFastestLap
+            && !self.isSprint
+            && isClassified
+            && place.is_some_and(|place| place < 10)
+        {
+            points += rules.fastestLap as u32;
+        }
+
+        points
+    }
+
+    fn row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.season,
+            self.weekend,
+            self.sessionUID,
+            self.session as u8,
+            self.isSprint as u8,
+            self.trackId.id,
+            self.position,
+            export::field(&self.driver),
+            export::field(&self.team),
+            self.status as u8,
+            self.points,
+            self.isFastestLap as u8,
+        )
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let fields = split(line);
+        if fields.len() != 12 {
+            return None;
+        }
+        let number = |i: usize| fields[i].parse::<u64>().ok();
+
+        Some(Self {
+            season: number(0)? as u32,
+            weekend: number(1)? as u32,
+            sessionUID: number(2)?,
+            session: Session::from_u8(&(number(3)? as u8)),
+            isSprint: number(4)? == 1,
+            trackId: TrackId::from_u8_to_i8(&(fields[5].parse::<i8>().ok()? as u8)),
+            position: number(6)? as u8,
+            driver: fields[7].clone(),
+            team: fields[8].clone(),
+            status: ResultStatus::from_u8(number(9)? as u8),
+            points: number(10)? as u8,
+            isFastestLap: number(11)? == 1,
+        })
+    }
+}
+
+// Every race result seen, kept in a CSV file so it lasts between runs.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Database {
+    pub path: PathBuf,
+    pub entries: Vec<Entry>,
+}
+
+impl Database {
+    // A database that doesn't exist yet is an empty one.
+    pub fn load(path: &str) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+
+        Ok(Self {
+            path: PathBuf::from(path),
+            entries: text.lines().skip(1).filter_map(Entry::parse).collect(),
+        })
+    }
+
+    // Add a race to the database, once, however many times the classification is sent.
+    pub fn record(&mut self, entries: Vec<Entry>) -> io::Result<usize> {
+        let Some(first) = entries.first() else {
+            return Ok(0);
+        };
+        if self
+            .entries
+            .iter()
+            .any(|entry| entry.sessionUID == first.sessionUID)
+        {
+            return Ok(0);
+        }
+
+        let isNew = !self.path.exists();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        if isNew {
+            writeln!(file, "{HEADER}")?;
+        }
+        for entry in &entries {
+            writeln!(file, "{}", entry.row())?;
+        }
+
+        let count = entries.len();
+        self.entries.extend(entries);
+        Ok(count)
+    }
+
+    pub fn standings(&self, season: u32, rules: &Rules) -> Standings {
+        let entries: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.season == season)
+            .collect();
+
+        // Rounds in the order they were raced.
+        let mut rounds: Vec<u32> = vec![];
+        for entry in &entries {
+            if !rounds.contains(&entry.weekend) {
+                rounds.push(entry.weekend);
+            }
+        }
+
+        let mut drivers: Vec<Standing> = vec![];
+        let mut constructors: Vec<Standing> = vec![];
+        for entry in &entries {
+            let round = rounds
+                .iter()
+                .position(|weekend| *weekend == entry.weekend)
+                .unwrap_or(0);
+            let points = entry.score(rules);
+            let isWin = entry.position == 1 && !entry.isSprint;
+
+            Standing::find(&mut drivers, &entry.driver, rounds.len()).add(
+                &entry.team,
+                round,
+                points,
+                isWin,
+            );
+            Standing::find(&mut constructors, &entry.team, rounds.len()).add(
+                &entry.team,
+                round,
+                points,
+                isWin,
+            );
+        }
+
+        // Drop rounds are for drivers, the constructors keep everything.
+        for driver in &mut drivers {
+            driver.drop(rules.dropRounds);
+        }
+
+        for table in [&mut drivers, &mut constructors] {
+            table.sort_by(|a, b| {
+                b.points
+                    .cmp(&a.points)
+                    .then(b.wins.cmp(&a.wins))
+                    .then(a.name.cmp(&b.name))
+            });
+        }
+
+        Standings {
+            season,
+            rounds: rounds.len(),
+            drivers,
+            constructors,
+        }
+    }
+}
+
+// What goes in the database for one race.
+pub fn entries(
+    season: u32,
+    weekend: u32,
+    sessionUID: u64,
+    isSprint: bool,
+    results: &Results,
+) -> Vec<Entry> {
+    let fastest = results
+        .rows
+        .iter()
+        .filter(|row| row.bestLapInMS > 0)
+        .min_by_key(|row| row.bestLapInMS)
+        .map(|row| row.position);
+
+    results
+        .rows
+        .iter()
+        .map(|row| Entry {
+            season,
+            weekend,
+            sessionUID,
+            session: results.session,
+            isSprint,
+            trackId: results.trackId,
+            position: row.position,
+            driver: row.driver.clone(),
+            team: row.team.clone(),
+            status: row.status,
+            points: row.points,
+            isFastestLap: fastest == Some(row.position),
+        })
+        .collect()
+}
+
+// A driver or constructor in the table.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Standing {
+    pub name: String,
+    pub team: String, // Latest team, for drivers
+    pub points: u32,  // Counted points, after drop rounds
+    pub dropped: u32, // Points from the rounds dropped
+    pub wins: u8,
+    pub rounds: Vec<u32>, // Points per round
+}
+
+impl Standing {
+    fn find<'a>(table: &'a mut Vec<Standing>, name: &str, rounds: usize) -> &'a mut Standing {
+        let idx = match table.iter().position(|standing| standing.name == name) {
+            Some(idx) => idx,
+            None => {
+                table.push(Standing {
+                    name: name.to_string(),
+                    rounds: vec![0; rounds],
+                    ..Standing::default()
+                });
+                table.len() - 1
+            }
+        };
+
+        &mut table[idx]
+    }
+
+    fn add(&mut self, team: &str, round: usize, points: u32, isWin: bool) {
+        self.team = team.to_string();
+        self.rounds[round] += points;
+        self.points += points;
+        self.wins += isWin as u8;
+    }
+
+    // Only the worst `count` rounds of a season that has had more than that many.
+    fn drop(&mut self, count: usize) {
+        if count == 0 || self.rounds.len() <= count {
+            return;
+        }
+
+        let mut rounds = self.rounds.clone();
+        rounds.sort();
+        self.dropped = rounds[..count].iter().sum();
+        self.points -= self.dropped;
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Standings {
+    pub season: u32,
+    pub rounds: usize,
+    pub drivers: Vec<Standing>,
+    pub constructors: Vec<Standing>,
+}
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}",
+            format!(
+                "Championship, season {:x} after {} round{}",
+                self.season,
+                self.rounds,
+                if self.rounds == 1 { "" } else { "s" }
+            )
+            .bold()
+        )?;
+        writeln!(
+            f,
+            "{:>3} {:<22} {:<15} {:>4} {:>4} {:5} {:>3} {:<15} {:>4}",
+            "Pos", "Driver", "Team", "Pts", "Wins", "", "Pos", "Constructor", "Pts"
+        )?;
+
+        for i in 0..self.drivers.len().max(self.constructors.len()) {
+            match self.drivers.get(i) {
+                Some(driver) => {
+                    let dropped = if driver.dropped > 0 {
+                        format!("({})", driver.dropped)
+                    } else {
+                        String::new()
+                    };
+                    write!(
+                        f,
+                        "{:>3} {:<22.22} {:<15.15} {:>4} {:>4} {:<5}",
+                        i + 1,
+                        driver.name,
+                        driver.team,
+                        driver.points,
+                        driver.wins,
+                        dropped,
+                    )?;
+                }
+                None => write!(f, "{:58}", "")?,
+            }
+            if let Some(team) = self.constructors.get(i) {
+                write!(f, " {:>3} {:<15.15} {:>4}", i + 1, team.name, team.points)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+// A CSV line, with quoted fields as export::field writes them.
+fn split(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut isQuoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if isQuoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            }
+            '"' => isQuoted = !isQuoted,
+            ',' if !isQuoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(weekend: u32, position: u8, driver: &str, team: &str) -> Entry {
+        Entry {
+            season: 1,
+            weekend,
+            sessionUID: weekend as u64,
+            position,
+            driver: driver.to_string(),
+            team: team.to_string(),
+            status: ResultStatus::Finished,
+            ..Entry::default()
+        }
+    }
+
+    fn rules() -> Rules {
+        Rules {
+            race: "f1".parse().unwrap(),
+            sprint: "sprint".parse().unwrap(),
+            fastestLap: 1,
+            dropRounds: 0,
+        }
+    }
+
+    #[test]
+    fn score_table() {
+        let rules = rules();
+        assert_eq!(entry(1, 1, "A", "X").score(&rules), 25);
+        assert_eq!(entry(1, 10, "A", "X").score(&rules), 1);
+        assert_eq!(entry(1, 11, "A", "X").score(&rules), 0);
+
+        // Blank and unclassified rows score nothing, rather than panic.
+        assert_eq!(entry(1, 0, "A", "X").score(&rules), 0);
+        let retired = Entry {
+            status: ResultStatus::Retired,
+            ..entry(1, 1, "A", "X")
+        };
+        assert_eq!(retired.score(&rules), 0);
+    }
+
+    #[test]
+    fn score_game() {
+        let rules = Rules::default();
+        let game = Entry {
+            points: 18,
+            ..entry(1, 2, "A", "X")
+        };
+        assert_eq!(game.score(&rules), 18);
+    }
+
+    #[test]
+    fn score_sprint() {
+        let rules = rules();
+        let sprint = |position| Entry {
+            isSprint: true,
+            ..entry(1, position, "A", "X")
+        };
+        assert_eq!(sprint(1).score(&rules), 8);
+        assert_eq!(sprint(8).score(&rules), 1);
+        assert_eq!(sprint(9).score(&rules), 0);
+    }
+
+    #[test]
+    fn score_fastest_lap() {
+        let rules = rules();
+        let fastest = |position, isSprint| Entry {
+            isFastestLap: true,
+            isSprint,
+            ..entry(1, position, "A", "X")
+        };
+        assert_eq!(fastest(1, false).score(&rules), 26);
+        assert_eq!(fastest(10, false).score(&rules), 2);
+        assert_eq!(fastest(11, false).score(&rules), 0);
+        assert_eq!(fastest(0, false).score(&rules), 0);
+        assert_eq!(fastest(1, true).score(&rules), 8);
+    }
+
+    #[test]
+    fn standings_drop_rounds() {
+        let database = Database {
+            entries: vec![
+                entry(1, 1, "A", "X"),
+                entry(1, 2, "B", "X"),
+                entry(2, 10, "A", "X"),
+                entry(2, 1, "B", "X"),
+                entry(3, 2, "A", "X"),
+                entry(3, 3, "B", "X"),
+            ],
+            ..Database::default()
+        };
+        let rules = Rules {
+            dropRounds: 1,
+            ..rules()
+        };
+
+        let standings = database.standings(1, &rules);
+        assert_eq!(standings.rounds, 3);
+
+        // A drops the 1 from round 2, B the 15 from round 3.
+        let a = &standings.drivers[0];
+        assert_eq!((a.name.as_str(), a.points, a.dropped), ("A", 43, 1));
+        assert_eq!(a.rounds, vec![25, 1, 18]);
+        let b = &standings.drivers[1];
+        assert_eq!((b.name.as_str(), b.points, b.dropped), ("B", 43, 15));
+        assert_eq!((a.wins, b.wins), (1, 1));
+
+        // The constructors keep every round.
+        assert_eq!(standings.constructors[0].points, 102);
+    }
+
+    #[test]
+    fn standings_sprint_win() {
+        let sprint = Entry {
+            isSprint: true,
+            ..entry(1, 1, "A", "X")
+        };
+        let database = Database {
+            entries: vec![sprint, entry(1, 2, "A", "X")],
+            ..Database::default()
+        };
+
+        let standings = database.standings(1, &rules());
+        assert_eq!(standings.rounds, 1);
+        assert_eq!(standings.drivers[0].points, 26);
+        assert_eq!(standings.drivers[0].wins, 0);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::championship::Rules;
+use crate::metrics::Endpoint;
+use std::env;
+use std::process;
+
+// Command line options, anything not given keeps its default.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub rainThreshold: u8,          // --rain-threshold, percentage that raises the rain alert
+    pub json: bool,                 // --json, print each packet as a line of JSON instead of the dashboard
+    pub exportDir: String,          // --export-dir, where the lap export is written
+    pub championship: String,       // --championship, the results database
+    pub database: String,           // --database, the SQLite session database
+    pub rules: Rules,               // --points, --sprint-points, --fastest-lap-point, --drop-rounds
+    pub influx: Option<Endpoint>,   // --influx, where to send InfluxDB line protocol
+    pub prometheus: Option<String>, // --prometheus, address to serve /metrics on
+    pub web: Option<String>,        // --web, address to serve the overlays on
+    pub mqtt: Option<String>,       // --mqtt, broker to publish live values to
+    pub mqttPrefix: String,         // --mqtt-prefix, first level of every topic
+    pub mqttRate: f32,              // --mqtt-rate, car and session updates a second
+    pub fps: u32,                   // --fps, most dashboard frames drawn a second
+    pub layout: Option<String>,     // --layout, file of timing tower presets
+    pub tower: Option<String>,      // --tower, the preset shown first
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            rainThreshold: 50,
+            json: false,
+            exportDir: ".".to_string(),
+            championship: "championship.csv".to_string(),
+            database: "pitwall.db".to_string(),
+            rules: Rules::default(),
+            influx: None,
+            prometheus: None,
+            web: None,
+            mqtt: None,
+            mqttPrefix: "pitwall".to_string(),
+            mqttRate: 10.0,
+            fps: 20,
+            layout: None,
+            tower: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn from_args() -> Self {
+        let mut config = Self::default();
+        let mut args = env::args().skip(1);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--rain-threshold" => {
+                    config.rainThreshold = Self::value(&arg, args.next());
+                    if config.rainThreshold > 100 {
+                        Self::usage("--rain-threshold is a percentage, 0 to 100");
+                    }
+                }
+                "--json" => config.json = true,
+                "--export-dir" => config.exportDir = Self::value(&arg, args.next()),
+                "--championship" => config.championship = Self::value(&arg, args.next()),
+                "--database" => config.database = Self::value(&arg, args.next()),
+                "--points" => config.rules.race = Self::value(&arg, args.next()),
+                "--sprint-points" => config.rules.sprint = Self::value(&arg, args.next()),
+                "--fastest-lap-point" => config.rules.fastestLap = Self::value(&arg, args.next()),
+                "--drop-rounds" => config.rules.dropRounds = Self::value(&arg, args.next()),
+                "--influx" => config.influx = Some(Self::value(&arg, args.next())),
+                "--prometheus" => config.prometheus = Some(Self::value(&arg, args.next())),
+                "--web" => config.web = Some(Self::value(&arg, args.next())),
+                "--mqtt" => config.mqtt = Some(Self::value(&arg, args.next())),
+                "--mqtt-prefix" => config.mqttPrefix = Self::value(&arg, args.next()),
+                "--mqtt-rate" => config.mqttRate = Self::value(&arg, args.next()),
+                "--fps" => config.fps = Self::value(&arg, args.next()),
+                "--layout" => config.layout = Some(Self::value(&arg, args.next())),
+                "--tower" => config.tower = Some(Self::value(&arg, args.next())),
+                _ => Self::usage(&format!("Unknown argument {arg}")),
+            }
+        }
+
+        config
+    }
+
+    fn value<T: std::str::FromStr>(arg: &str, value: Option<String>) -> T {
+        match value.map(|v| v.parse()) {
+            Some(Ok(v)) => v,
+            _ => Self::usage(&format!("Missing or invalid value for {arg}")),
+        }
+    }
+
+    fn usage(error: &str) -> ! {
+        eprintln!("{error}");
+        eprintln!();
+        eprintln!("Usage: pitwall [options]");
+        eprintln!("       pitwall query <report> [options], see pitwall query --help");
+        eprintln!("  --rain-threshold <0-100>  Alert when the forecast rain chance reaches this (default 50)");
+        eprintln!("  --json                    Headless, print one JSON object per packet to stdout");
+        eprintln!("  --export-dir <dir>        Where the lap CSV goes at session end or on Enter (default .)");
+        eprintln!("  --fps <n>                 Most dashboard redraws a second, it only redraws on changes (default 20)");
+        eprintln!("  --layout <file>           Timing tower presets, [name] then columns such as pos driver:12 | last s1");
+        eprintln!("  --tower <preset>          Tower preset shown first, race, strategy, engineer, stewards, projection or one of --layout's");
+        eprintln!("  --championship <file>     Race results database for the standings (default championship.csv)");
+        eprintln!("  --database <file>         SQLite database every session is stored in (default pitwall.db)");
+        eprintln!("  --points <scale>          game, f1, or a list such as 25,18,15 (default game)");
+        eprintln!("  --sprint-points <scale>   game, sprint, or a list such as 8,7,6 (default game)");
+        eprintln!("  --fastest-lap-point <n>   Bonus for the fastest lap in a top ten finish (default 0)");
+        eprintln!("  --drop-rounds <n>         Each driver's worst rounds don't count (default 0)");
+        eprintln!("  --influx <endpoint>       Send live telemetry as InfluxDB line protocol, udp://host:port or tcp://host:port");
+        eprintln!("  --prometheus <addr>       Serve Prometheus gauges on http://<addr>/metrics, e.g. 0.0.0.0:9100");
+        eprintln!("  --web <addr>              Serve the overlays on http://<addr>/tower and /lower-third, fed by /ws,");
+        eprintln!("                            and JSON at /session, /cars, /cars/<idx>/laps, /bests, /events and /weather");
+        eprintln!("  --mqtt <host:port>        Publish live values to an MQTT broker, e.g. pitwall/car/3/gear");
+        eprintln!("  --mqtt-prefix <topic>     First level of every MQTT topic (default pitwall)");
+        eprintln!("  --mqtt-rate <hz>          How often car and session topics are updated (default 10)");
+        process::exit(2);
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// One car's completed lap, a row of the lap export and of the session database.
+#[derive(Debug, Default, Clone)]
+pub struct LapRow {
+    pub carIdx: usize,       // Index into the packet arrays
+    pub driver: String,      // PacketParticipants.participants.name
+    pub team: String,        // PacketParticipants.participants.teamId
+    pub lap: u8,             // PacketLap.laps.currentLapNum, of the lap just finished
+    pub lapTime: u32,        // PacketSessionHistory.lapHistoryData.lapTimeInMS, PacketLap until it arrives
+    pub sector1: u32,        // PacketSessionHistory.lapHistoryData, 0 until it arrives
+    pub sector2: u32,
+    pub sector3: u32,
+    pub isValid: bool,       // PacketSessionHistory.lapHistoryData.lapValidBitFlags, PacketLap until it arrives
+    pub tyre: String,        // PacketCarStatus.carStatusData.visualTyre
+    pub tyreAge: u8,         // PacketCarStatus.carStatusData.tyresAgeLaps
+    pub isPit: bool,         // Entered or left the pit lane on this lap
+    pub isNeutralised: bool, // Run under the SC / VSC
+    pub position: u8,        // PacketLap.laps.carPosition, crossing the line
+    pub gapToLeader: u32,    // PacketLap.laps.deltaToRaceLeader, crossing the line
+}
+
+// Where a session's file goes, e.g. laps-Race-1a2b3c4d.csv in --export-dir.
+pub fn path(dir: &str, kind: &str, session: &str, sessionUID: u64, extension: &str) -> PathBuf {
+    Path::new(dir).join(format!("{kind}-{session}-{sessionUID:x}.{extension}"))
+}
+
+// One row per car per lap, in lap then position order.
+pub fn laps(path: &Path, rows: &[LapRow]) -> io::Result<()> {
+    let mut csv = String::from(
+        "Driver,Team,Lap,Lap Time,S1,S2,S3,Valid,Tyre,Tyre Age,Pit,Position,Gap to Leader\n",
+    );
+
+    let mut rows: Vec<&LapRow> = rows.iter().collect();
+    rows.sort_by_key(|row| (row.lap, row.position));
+
+    for row in rows {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            field(&row.driver),
+            field(&row.team),
+            row.lap,
+            seconds(row.lapTime),
+            seconds(row.sector1),
+            seconds(row.sector2),
+            seconds(row.sector3),
+            if row.isValid { "Yes" } else { "No" },
+            field(&row.tyre),
+            row.tyreAge,
+            if row.isPit { "Yes" } else { "No" },
+            row.position,
+            gap(row.gapToLeader),
+        );
+    }
+
+    fs::write(path, csv)
+}
+
+// Seconds to the millisecond, so a spreadsheet reads it as a number. Blank when unknown.
+pub fn seconds(inMS: u32) -> String {
+    if inMS == 0 {
+        return String::new();
+    }
+
+    gap(inMS)
+}
+
+// The leader is 0.000 behind, not unknown.
+fn gap(inMS: u32) -> String {
+    format!("{}.{:03}", inMS / 1000, inMS % 1000)
+}
+
+// Quoted when it would otherwise break the row, e.g. a lobby name with a comma.
+pub fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,2 @@
+// Wire format of the F1 25 UDP telemetry, shared by pitwall and pitwall-sim.
+pub mod packet;
@@ -0,0 +1,275 @@
+use crate::stewards::Outstanding;
+use crate::weather::Forecast;
+use crate::{
+    Best, Car, Drs, Ers, LapRecord, Page, Period, PitStop, RaceControl, Time, Times, Tyres,
+};
+use pitwall::packet::{Object, ToJson};
+
+//
+// The dashboard's state as JSON, for the overlays and anything else that isn't a terminal.
+// Field names follow the structs, times stay in milliseconds.
+//
+
+impl ToJson for Time {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("inMS", &self.inMS)
+            .field("byId", &self.byId)
+            .field("onLap", &self.onLap)
+            .field("isSet", &self.isSet)
+            .field("isOB", &self.isOB)
+            .field("isPB", &self.isPB)
+            .end();
+    }
+}
+
+impl ToJson for Times {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("sector1", &self.sector1)
+            .field("sector2", &self.sector2)
+            .field("sector3", &self.sector3)
+            .field("lastLap", &self.lastLap)
+            .field("current", &self.current)
+            .field("interval", &self.interval)
+            .field("leader", &self.leader)
+            .end();
+    }
+}
+
+impl ToJson for Best {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("sector1", &self.sector1)
+            .field("sector2", &self.sector2)
+            .field("sector3", &self.sector3)
+            .field("lapTime", &self.lapTime)
+            .field("possible", &self.possible)
+            .end();
+    }
+}
+
+impl ToJson for Tyres {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("actual", &self.actual)
+            .field("visual", &self.visual)
+            .field("age", &self.age)
+            .end();
+    }
+}
+
+impl ToJson for Drs {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("isOpen", &self.isOpen)
+            .field("isAllowed", &self.isAllowed)
+            .end();
+    }
+}
+
+impl ToJson for Ers {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("storeEnergy", &self.storeEnergy)
+            .field("deployMode", &self.deployMode)
+            .end();
+    }
+}
+
+impl ToJson for Outstanding {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("timePenalty", &self.timePenalty)
+            .field("warnings", &self.warnings)
+            .field("cornerCutting", &self.cornerCutting)
+            .field("driveThroughs", &self.driveThroughs)
+            .field("stopGoes", &self.stopGoes)
+            .field("serveAtStop", &self.serveAtStop)
+            .end();
+    }
+}
+
+impl ToJson for RaceControl {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("safetyCar", &self.safetyCar)
+            .field("periodsSC", &self.periodsSC)
+            .field("periodsVSC", &self.periodsVSC)
+            .field("periodsRed", &self.periodsRed)
+            .field("isNeutralised", &self.isNeutralised())
+            .end();
+    }
+}
+
+impl ToJson for LapRecord {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("lapNum", &self.lapNum)
+            .field("inMS", &self.inMS)
+            .field("sector1InMS", &self.sector1InMS)
+            .field("sector2InMS", &self.sector2InMS)
+            .field("sector3InMS", &self.sector3InMS)
+            .field("isValid", &self.isValid)
+            .field("tyre", &self.tyre)
+            .field("tyreAge", &self.tyreAge)
+            .field("isNeutralised", &self.isNeutralised)
+            .field("isPit", &self.isPit)
+            .field("position", &self.position)
+            .field("leaderInMS", &self.leaderInMS)
+            .end();
+    }
+}
+
+impl ToJson for PitStop {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("lap", &self.lap)
+            .field("laneInMS", &self.laneInMS)
+            .field("stopInMS", &self.stopInMS)
+            .field("tyreIn", &self.tyreIn)
+            .field("ageIn", &self.ageIn)
+            .field("tyreOut", &self.tyreOut)
+            .field("positionIn", &self.positionIn)
+            .field("positionOut", &self.positionOut)
+            .end();
+    }
+}
+
+// Everything the timing tower shows for a car, but not its laps.
+impl ToJson for Car {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("name", &self.driver.name)
+            .field("number", &self.driver.number)
+            .field("nationality", &self.driver.nationality)
+            .field("isAI", &self.driver.isAI)
+            .field("flag", &self.driver.underFlag)
+            .field("team", &self.team.id.to_string())
+            .field("position", &self.spotRace)
+            .field("grid", &self.spotGrid)
+            .field("lapNum", &self.lapNum)
+            .field("sector", &self.sector)
+            .field("status", &self.carStatus)
+            .field("times", &self.time)
+            .field("pace", &self.pace(3))
+            .field("safetyCarDelta", &self.safetyCarDelta)
+            .field("tyres", &self.tyres)
+            .field("drs", &self.Drs)
+            .field("ers", &self.Ers)
+            .field("penalties", &self.penalties)
+            .field("pitCount", &self.pitCount)
+            .field("isPitting", &self.pitLane.is_some())
+            .end();
+    }
+}
+
+impl ToJson for Forecast {
+    fn json(&self, out: &mut String) {
+        Object::new(out)
+            .field("weather", &self.weather)
+            .field("trackTemperature", &self.trackTemperature)
+            .field("airTemperature", &self.airTemperature)
+            .field("accuracy", &self.accuracy)
+            .field("samples", &self.samples)
+            .end();
+    }
+}
+
+impl Car {
+    // Best valid lap and sectors of this car, as `Best` keeps them for the field.
+    pub fn personalBest(&self, idx: usize) -> Best {
+        let mut best = Best::default();
+        for lap in self.laps.iter().filter(|lap| lap.isValid) {
+            best.isBest(Period::Sector1, lap.sector1InMS, idx, lap.lapNum);
+            best.isBest(Period::Sector2, lap.sector2InMS, idx, lap.lapNum);
+            best.isBest(Period::Sector3, lap.sector3InMS, idx, lap.lapNum);
+            best.isBest(Period::LapTime, lap.inMS, idx, lap.lapNum);
+        }
+        best
+    }
+}
+
+// Bests of a single car, named so a poller doesn't have to look the index up.
+struct PersonalBest<'a>(usize, &'a Car);
+
+impl ToJson for PersonalBest<'_> {
+    fn json(&self, out: &mut String) {
+        let PersonalBest(idx, car) = *self;
+        Object::new(out)
+            .field("carIdx", &idx)
+            .field("name", &car.driver.name)
+            .field("best", &car.personalBest(idx))
+            .end();
+    }
+}
+
+impl Page {
+    // The cars taking part, by index.
+    pub fn activeCars(&self) -> &[Car] {
+        &self.car[..=(self.playerCarIndex as usize).min(self.car.len() - 1)]
+    }
+
+    // Car indices in race order.
+    pub fn order(&self) -> Vec<usize> {
+        self.positions
+            .iter()
+            .copied()
+            .filter(|idx| *idx < self.activeCars().len())
+            .collect()
+    }
+
+    pub fn sessionJson(&self) -> String {
+        let mut out = String::new();
+        Object::new(&mut out)
+            .field("sessionUID", &self.sessionUID.to_string())
+            .field("session", &self.session)
+            .field("track", &self.trackId)
+            .field("lapLeader", &self.lap.leader)
+            .field("totalLaps", &self.lap.total)
+            .field("timeLeft", &{ self.sessionTimeLeft.time })
+            .field("duration", &{ self.sessionDuration.time })
+            .field("raceControl", &self.raceControl)
+            .field("zones", &self.track.zones)
+            .field("weather", &self.forecast.weather)
+            .field("trackTemperature", &self.forecast.trackTemperature)
+            .field("airTemperature", &self.forecast.airTemperature)
+            .field("playerCarIndex", &self.playerCarIndex)
+            .field("order", &self.order())
+            .field("best", &self.ob)
+            .end();
+        out
+    }
+
+    pub fn carsJson(&self) -> Vec<String> {
+        self.activeCars().iter().map(|car| car.to_json()).collect()
+    }
+
+    // Each car's completed laps, by car index.
+    pub fn lapsJson(&self) -> Vec<String> {
+        self.activeCars()
+            .iter()
+            .map(|car| car.laps.to_json())
+            .collect()
+    }
+
+    // The overall bests, and each car's own in race order.
+    pub fn bestsJson(&self) -> String {
+        let cars: Vec<PersonalBest> = self
+            .order()
+            .into_iter()
+            .map(|idx| PersonalBest(idx, &self.car[idx]))
+            .collect();
+
+        let mut out = String::new();
+        Object::new(&mut out)
+            .field("overall", &self.ob)
+            .field("cars", cars.as_slice())
+            .end();
+        out
+    }
+
+    pub fn weatherJson(&self) -> String {
+        self.forecast.to_json()
+    }
+}
@@ -1,13 +1,50 @@
 #![allow(non_snake_case)]
 
 use colored::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::env;
 use std::fmt;
+use std::fs;
+use std::io;
 use std::net::UdpSocket;
-
-static ESC: char = 27 as char;
-
-mod packet;
-use packet::*;
+use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::TryRecvError;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+mod championship;
+mod config;
+mod export;
+mod live;
+mod metrics;
+mod mqtt;
+mod projection;
+mod query;
+mod results;
+mod speedtrap;
+mod stewards;
+mod strategy;
+mod storage;
+mod tower;
+mod tui;
+mod weather;
+mod web;
+use championship::{Database, Rules, Standings};
+use config::Config;
+use metrics::{Influx, Metrics};
+use mqtt::Mqtt;
+use results::Results;
+use pitwall::packet::*;
+use speedtrap::Trap;
+use stewards::{Ledger, Outstanding};
+use strategy::PitWindow;
+use storage::Store;
+use tower::Layout;
+use tui::Action;
+use weather::Forecast;
+use web::{Hub, Snapshot};
 
 #[allow(dead_code)]
 enum Packet {
@@ -26,13 +63,34 @@ enum Packet {
     Unknown,
 }
 
+impl Packet {
+    fn json(&self) -> Option<String> {
+        match self {
+            Packet::Motion(p) => Some(p.to_json()),
+            Packet::Session(p) => Some(p.to_json()),
+            Packet::Lap(p) => Some(p.to_json()),
+            Packet::Event(p) => Some(p.to_json()),
+            Packet::Participants(p) => Some(p.to_json()),
+            Packet::CarSetups(p) => Some(p.to_json()),
+            Packet::CarTelemetry(p) => Some(p.to_json()),
+            Packet::CarStatus(p) => Some(p.to_json()),
+            Packet::FinalClassification(p) => Some(p.to_json()),
+            Packet::LobbyInfo(p) => Some(p.to_json()),
+            Packet::CarDamage(p) => Some(p.to_json()),
+            Packet::SessionHistory(p) => Some(p.to_json()),
+            Packet::Unknown => None,
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Driver {
     // From PacketParticipants.participants
-    pub id: u8,                   // driverId
+    pub id: DriverId,             // driverId
     pub idNetwork: u8,            // networkId
     pub number: u8,               // raceNumber
-    pub nationality: u8,          // nationality
+    pub nationality: Nationality, // nationality
     pub isAI: bool,               // aiControlled
     pub isTelemetryEnabled: bool, // yourTelemetry
     pub name: String,             // name
@@ -42,52 +100,51 @@ struct Driver {
 }
 
 impl Driver {
-    pub fn getDriver(&self) -> String {
-        format!(
-            "{:>15} ({:2})",
-            match self.underFlag {
-                ZoneFlag::Green => {
-                    if self.isAI {
-                        self.name.white().on_green()
-                    } else {
-                        self.name.yellow().on_green()
-                    }
+    // The name, coloured for the flag the driver is under.
+    pub fn flagged(&self) -> ColoredString {
+        match self.underFlag {
+            ZoneFlag::Green => {
+                if self.isAI {
+                    self.name.white().on_green()
+                } else {
+                    self.name.yellow().on_green()
                 }
-                ZoneFlag::Blue => {
-                    if self.isAI {
-                        self.name.white().on_blue()
-                    } else {
-                        self.name.yellow().on_blue()
-                    }
+            }
+            ZoneFlag::Blue => {
+                if self.isAI {
+                    self.name.white().on_blue()
+                } else {
+                    self.name.yellow().on_blue()
                 }
-                ZoneFlag::Yellow => {
-                    if self.isAI {
-                        self.name.white().on_yellow()
-                    } else {
-                        self.name.black().on_yellow()
-                    }
+            }
+            ZoneFlag::Yellow => {
+                if self.isAI {
+                    self.name.white().on_yellow()
+                } else {
+                    self.name.black().on_yellow()
                 }
-                _ => {
-                    if self.isAI {
-                        self.name.white()
-                    } else {
-                        self.name.yellow()
-                    }
+            }
+            _ => {
+                if self.isAI {
+                    self.name.white()
+                } else {
+                    self.name.yellow()
                 }
-            },
-            self.number
-        )
+            }
+        }
     }
 }
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Team {
     // From PacketParticipants.participants
-    pub id: u8,         // teamId
+    pub id: TeamId,     // teamId
     pub isCustom: bool, // myTeam
 }
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Drs {
     // PacketCarTelemetry.carTelemetryData
     pub isOpen: bool, // drs
@@ -108,6 +165,7 @@ impl fmt::Display for Drs {
 }
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Assists {
     // PacketCarStatus.carStatusData
     pub TC: TC,      // tractionControl
@@ -115,6 +173,7 @@ struct Assists {
 }
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Tyres {
     // PacketCarStatus.carStatusData
     pub actual: ActualCompound, // actualTyre
@@ -151,6 +210,7 @@ impl fmt::Display for Tyres {
 }
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Telemetry {
     // PacketCarTelemetry.carTelemetryData
     pub speed: Kph,      // speed
@@ -160,6 +220,7 @@ struct Telemetry {
 }
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Times {
     // PacketLap.laps
     pub sector1: Time, // sector1Time
@@ -245,6 +306,7 @@ impl Times {
 }
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Car {
     pub driver: Driver,
     pub team: Team,
@@ -262,9 +324,187 @@ struct Car {
     pub pitCount: u8,        // numPitStops
     pub carStatus: CarState, // driverStatus
     pub sector: u8,          // sector
+    pub lapDistance: f32,    // lapDistance
+    pub safetyCarDelta: f32, // safetyCarDelta
+    pub speedTrap: Trap,
+    pub resultStatus: ResultStatus,
+    pub penalties: Outstanding,
+
+    // Completed laps, and what happened on the lap in progress.
+    pub laps: Vec<LapRecord>,
+    pub isNeutralised: bool, // Some of this lap was run under the SC / VSC
+    pub isPitting: bool,     // Some of this lap was spent in the pit lane
+    pub isInvalid: bool,     // Some of this lap broke track limits
+
+    // Trips down the pit lane, and the one in progress.
+    pub pitStops: Vec<PitStop>,
+    pub pitLane: Option<PitStop>,
+}
+
+impl Car {
+    // Retired, or out of the race some other way.
+    pub fn isOut(&self) -> bool {
+        matches!(
+            self.resultStatus,
+            ResultStatus::DidNotFinish | ResultStatus::Disqualified | ResultStatus::Retired
+        )
+    }
+
+    // Laps run at racing speed, so no SC / VSC and no trips down the pit lane.
+    fn greenLaps(&self) -> impl DoubleEndedIterator<Item = &LapRecord> {
+        self.laps
+            .iter()
+            .filter(|lap| lap.inMS > 0 && !lap.isNeutralised && !lap.isPit)
+    }
+
+    // Average of the last `count` green laps.
+    pub fn pace(&self, count: usize) -> Option<u32> {
+        let laps: Vec<u32> = self.greenLaps().rev().take(count).map(|lap| lap.inMS).collect();
+
+        if laps.is_empty() {
+            return None;
+        }
+
+        Some(laps.iter().sum::<u32>() / laps.len() as u32)
+    }
+
+    // Lap time lost per lap of tyre age over the current stint, in milliseconds.
+    // Only the laps since the last tyre change, earlier sets ran on more fuel.
+    pub fn degradation(&self) -> Option<f32> {
+        let fitted = self
+            .pitStops
+            .iter()
+            .rfind(|stop| !stop.isDriveThrough())
+            .map_or(0, |stop| stop.lap);
+        let stint: Vec<(f32, f32)> = self
+            .greenLaps()
+            .filter(|lap| lap.lapNum > fitted && lap.tyre as u8 == self.tyres.visual as u8)
+            .map(|lap| (lap.tyreAge as f32, lap.inMS as f32))
+            .collect();
+
+        if stint.len() < 3 {
+            return None;
+        }
+
+        // Least squares slope of lap time against tyre age.
+        let n = stint.len() as f32;
+        let meanAge = stint.iter().map(|(age, _)| age).sum::<f32>() / n;
+        let meanTime = stint.iter().map(|(_, time)| time).sum::<f32>() / n;
+        let covariance: f32 = stint.iter().map(|(age, time)| (age - meanAge) * (time - meanTime)).sum();
+        let variance: f32 = stint.iter().map(|(age, _)| (age - meanAge).powi(2)).sum();
+
+        if variance == 0.0 {
+            return None;
+        }
+
+        Some(covariance / variance)
+    }
+
+    // Time a stop cost, stationary time included: its laps against the car's green pace.
+    // Only once the out lap is done, and never for a stop made under the SC / VSC
+    // or a drive through.
+    pub fn netLoss(&self, stop: &PitStop) -> Option<u32> {
+        if stop.isDriveThrough() {
+            return None;
+        }
+        let outLap = stop.lap.saturating_add(1);
+        if !self.laps.iter().any(|lap| lap.lapNum == outLap) {
+            return None;
+        }
+
+        let laps: Vec<&LapRecord> = self
+            .laps
+            .iter()
+            .filter(|lap| lap.isPit && (lap.lapNum == stop.lap || lap.lapNum == outLap))
+            .collect();
+        if laps.is_empty() || laps.iter().any(|lap| lap.isNeutralised || lap.inMS == 0) {
+            return None;
+        }
+
+        let pace = self.pace(5)? as i64;
+        let lost: i64 = laps.iter().map(|lap| lap.inMS as i64 - pace).sum();
+        (lost > 0).then_some(lost as u32)
+    }
+
+    pub fn getPace(&self) -> String {
+        match self.pace(3) {
+            Some(inMS) => format!("{}", Time { inMS, ..Time::default() }),
+            None => format!("{:>8}", ""),
+        }
+    }
+
+    // Under the SC / VSC the gap to the car ahead means little, the delta to the SC does.
+    pub fn getInterval(&self, isNeutralised: bool) -> String {
+        if isNeutralised {
+            format!("{:>8}", format!("{:+.3}", self.safetyCarDelta).yellow())
+        } else {
+            format!("{}", self.time.interval)
+        }
+    }
+
+    pub fn getDegradation(&self) -> String {
+        match self.degradation() {
+            Some(ms) => format!("{:>+6.3}", ms / 1000.0),
+            None => format!("{:>6}", ""),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct LapRecord {
+    pub lapNum: u8,           // PacketLap.laps.currentLapNum, of the lap just finished
+    pub inMS: u32,            // PacketLap.laps.lastLapTimeInMS
+    pub tyre: VisualCompound, // PacketCarStatus.carStatusData.visualTyre
+    pub tyreAge: u8,          // PacketCarStatus.carStatusData.tyresAgeLaps
+    pub isNeutralised: bool,  // Run under the SC / VSC
+    pub isPit: bool,          // Entered or left the pit lane
+    pub isValid: bool,        // Track limits kept, PacketLap.laps.currentLapInvalid
+    pub position: u8,         // PacketLap.laps.carPosition, crossing the line
+    pub leaderInMS: u32,      // PacketLap.laps.deltaToRaceLeader, crossing the line
+
+    // PacketSessionHistory.lapHistoryData, once it has been sent for this lap.
+    pub sector1InMS: u32,
+    pub sector2InMS: u32,
+    pub sector3InMS: u32,
+}
+
+// A trip down the pit lane, from PacketLap.laps.
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct PitStop {
+    pub lap: u8,                 // currentLapNum, on the way in
+    pub laneInMS: u16,           // pitLaneTimeInLaneInMS, when the lane timer stopped
+    pub stopInMS: u16,           // pitStopTimerInMS, stationary in the box
+    pub tyreIn: VisualCompound,  // PacketCarStatus.carStatusData.visualTyre, on the way in
+    pub ageIn: u8,               // PacketCarStatus.carStatusData.tyresAgeLaps, on the way in
+    pub tyreOut: VisualCompound, // PacketCarStatus.carStatusData.visualTyre, on the way out
+    pub positionIn: u8,          // carPosition, on the way in
+    pub positionOut: u8,         // carPosition, when the lane timer stopped
+}
+
+impl PitStop {
+    // Places lost in the pit lane, negative if some were made.
+    pub fn positionsLost(&self) -> i16 {
+        self.positionOut as i16 - self.positionIn as i16
+    }
+
+    // Through the lane without stopping, so a drive through penalty rather than a stop.
+    pub fn isDriveThrough(&self) -> bool {
+        self.stopInMS == 0
+    }
+}
+
+// Where a car would come out if it pitted now.
+#[derive(Debug, Clone, Copy)]
+struct Rejoin {
+    pub position: usize,
+    pub ahead: Option<(usize, u32)>,  // The car it would be behind, and by how much
+    pub behind: Option<(usize, u32)>, // The car it would be ahead of, and by how much
 }
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Ers {
     pub storeEnergy: f32,           // ERS energy store in Joules
     pub deployMode: ErsDeployMode,  // u8
@@ -296,12 +536,132 @@ impl fmt::Display for Ers {
 }
 
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct SessionLap {
     pub leader: u8, // MAX of PacketLap.laps.currentLapNum
     pub total: u8,  // PacketSession.totalLaps
 }
 
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct RaceControl {
+    // PacketSession
+    pub safetyCar: SafetyCar, // safetyCarStatus
+    pub periodsSC: u8,        // numSafetyCarPeriods
+    pub periodsVSC: u8,       // numVirtualSafetyCarPeriods
+    pub periodsRed: u8,       // numRedFlagPeriods
+}
+
+impl RaceControl {
+    pub fn isNeutralised(&self) -> bool {
+        matches!(self.safetyCar, SafetyCar::Deployed | SafetyCar::Virtual)
+    }
+}
+
+impl fmt::Display for RaceControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let banner = format!(" {} ", self.safetyCar.to_string().to_uppercase());
+
+        match self.safetyCar {
+            SafetyCar::Deployed | SafetyCar::Virtual => write!(f, "{}", banner.black().on_yellow().bold())?,
+            SafetyCar::FormationLap => write!(f, "{}", banner.black().on_white())?,
+            _ => write!(f, "{}", banner.green())?,
+        }
+
+        write!(
+            f,
+            " SC {} VSC {} RF {}",
+            self.periodsSC, self.periodsVSC, self.periodsRed
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Track {
+    // PacketSession
+    pub length: u16,             // trackLength
+    pub zones: Vec<MarshalZone>, // marshalZones, numMarshalZones long
+}
+
+impl Track {
+    // Index of the marshal zone covering this fraction of the lap.
+    fn zoneAt(&self, fraction: f32) -> Option<usize> {
+        // Before the first zone starts we are still in the last zone of the lap.
+        self.zones
+            .iter()
+            .rposition(|zone| zone.zoneStart <= fraction)
+            .or_else(|| self.zones.len().checked_sub(1))
+    }
+
+    // Closest yellow or red zone ahead of the car, and how many metres until we reach it.
+    fn flagAhead(&self, lapDistance: f32) -> Option<(usize, ZoneFlag, f32)> {
+        if self.length == 0 || lapDistance < 0.0 {
+            return None;
+        }
+
+        let length = self.length as f32;
+        let fraction = (lapDistance / length).rem_euclid(1.0);
+        let current = self.zoneAt(fraction);
+
+        self.zones
+            .iter()
+            .enumerate()
+            .filter(|(_, zone)| matches!(zone.zoneFlag, ZoneFlag::Yellow | ZoneFlag::Red))
+            .map(|(idx, zone)| {
+                let metres = if Some(idx) == current {
+                    0.0
+                } else {
+                    (zone.zoneStart - fraction).rem_euclid(1.0) * length
+                };
+                (idx, zone.zoneFlag, metres)
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+    }
+
+    pub fn getAlert(&self, lapDistance: f32) -> Option<String> {
+        let (idx, flag, metres) = self.flagAhead(lapDistance)?;
+
+        let alert = match (flag, metres as u32) {
+            (ZoneFlag::Red, _) => format!(" RED FLAG - Zone {} ", idx + 1),
+            (_, 0) => format!(" YELLOW FLAG - In Zone {} ", idx + 1),
+            (_, m) => format!(" YELLOW FLAG - Zone {} in {}m ", idx + 1, m),
+        };
+
+        Some(match flag {
+            ZoneFlag::Red => format!("{}", alert.white().on_red().bold()),
+            _ => format!("{}", alert.black().on_yellow().bold()),
+        })
+    }
+
+    pub fn getZones(&self, lapDistance: f32) -> String {
+        let player = if self.length > 0 && lapDistance >= 0.0 {
+            self.zoneAt((lapDistance / self.length as f32).rem_euclid(1.0))
+        } else {
+            None
+        };
+
+        self.zones
+            .iter()
+            .enumerate()
+            .map(|(idx, zone)| {
+                let block = if Some(idx) == player { "<>" } else { "  " };
+
+                match zone.zoneFlag {
+                    ZoneFlag::Green => block.black().on_green(),
+                    ZoneFlag::Blue => block.white().on_blue(),
+                    ZoneFlag::Yellow => block.black().on_yellow(),
+                    ZoneFlag::Red => block.white().on_red(),
+                    _ => block.white().on_bright_black(),
+                }
+                .to_string()
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Time {
     pub inMS: u32,   // MIN of PacketLap.laps.{lastLapTimeInMS}
     pub byId: u8,    // Driver Index Number
@@ -340,6 +700,7 @@ impl fmt::Display for Time {
 }
 
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Best {
     pub sector1: Time,
     pub sector2: Time,
@@ -429,31 +790,369 @@ impl Best {
 }
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 struct Page {
     participants: u8,   // PacketParticipants.numActiveCars
     playerCarIndex: u8, // Always the last item, and so gives you the bounds of the array.
     positions: [usize; 23],
     session: Session, // PacketSession.sessionType
+    trackId: TrackId, // PacketSession.trackId
     ob: Best,
     car: [Car; 23],
     lap: SessionLap,
+    track: Track,
+    raceControl: RaceControl,
+    forecast: Forecast,
+    stewards: Ledger,
     sessionTimeLeft: SessionTime,
     sessionDuration: SessionTime,
+    sessionUID: u64,    // PacketHeader.sessionUID
+    season: u32,        // PacketSession.seasonLinkIdentifier
+    weekend: u32,       // PacketSession.weekendLinkIdentifier
+    weekendStructure: Vec<Session>, // PacketSession.weekendStructure
+    notices: Vec<String>, // Outcome of each export
+    isStoreFailing: bool, // The last database write failed, and has been said so
+    results: Option<Results>, // PacketFinalClassification, shown in place of the tower once it arrives
+    standings: Option<Standings>, // Shown with the results, until the next session starts
+    events: Vec<String>, // PacketEvent as JSON, for the web API, except button presses
+    log: Vec<String>,    // PacketEvent as a line of text, for the events view
+    trackPitLoss: Option<u32>, // Net pit loss at this track in earlier sessions, from the database
+    pitWindow: PitWindow,      // PacketSession, for the player
+}
+
+impl Page {
+    // Every completed lap of every car, for the lap export.
+    fn lapRows(&self) -> Vec<export::LapRow> {
+        self.car[..=(self.playerCarIndex as usize).min(self.car.len() - 1)]
+            .iter()
+            .enumerate()
+            .flat_map(|(carIdx, car)| {
+                car.laps.iter().map(move |lap| export::LapRow {
+                    carIdx,
+                    driver: car.driver.name.clone(),
+                    team: car.team.id.to_string(),
+                    lap: lap.lapNum,
+                    lapTime: lap.inMS,
+                    sector1: lap.sector1InMS,
+                    sector2: lap.sector2InMS,
+                    sector3: lap.sector3InMS,
+                    isValid: lap.isValid,
+                    tyre: format!("{:?}", lap.tyre),
+                    tyreAge: lap.tyreAge,
+                    isPit: lap.isPit,
+                    isNeutralised: lap.isNeutralised,
+                    position: lap.position,
+                    gapToLeader: lap.leaderInMS,
+                })
+            })
+            .collect()
+    }
+
+    // Save the results screen as Markdown, HTML and CSV.
+    fn saveResults(&mut self, dir: &str) {
+        let Some(results) = &self.results else {
+            return;
+        };
+        let session = format!("{:?}", self.session);
+        let files = [
+            ("md", results.markdown()),
+            ("html", results.html()),
+            ("csv", results.csv()),
+        ];
+
+        let mut saved = vec![];
+        for (extension, contents) in files {
+            let path = export::path(dir, "results", &session, self.sessionUID, extension);
+            match fs::write(&path, contents) {
+                Ok(()) => saved.push(path.display().to_string()),
+                Err(err) => {
+                    self.notices.push(format!("Couldn't write {}: {err}", path.display()));
+                    return;
+                }
+            }
+        }
+        self.notices.push(format!("Results saved to {}", saved.join(", ")));
+    }
+
+    // Add a race to the championship, and show the season's standings after any classified session.
+    fn recordStandings(&mut self, championship: &mut Database, rules: &Rules) {
+        let Some(results) = &self.results else {
+            return;
+        };
+
+        let isRace = |session: &Session| matches!(session, Session::Race | Session::Race2 | Session::Race3);
+        if isRace(&self.session) {
+            // A sprint weekend has another race after this one.
+            let isSprint = self
+                .weekendStructure
+                .iter()
+                .skip_while(|session| **session as u8 != self.session as u8)
+                .skip(1)
+                .any(isRace);
+
+            let entries = championship::entries(self.season, self.weekend, self.sessionUID, isSprint, results);
+            if let Err(err) = championship.record(entries) {
+                self.notices.push(format!("Couldn't record the race in {}: {err}", championship.path.display()));
+            }
+        }
+
+        self.standings = Some(championship.standings(self.season, rules));
+    }
+
+    // Laps, stints, pit stops and penalties so far, replacing what was stored for them before.
+    fn store(&mut self, store: &mut Store) {
+        let result = (|| {
+            for row in self.lapRows() {
+                store.lap(self.sessionUID, &row)?;
+            }
+            for row in self.results.iter().flat_map(|results| &results.rows) {
+                store.stints(self.sessionUID, row.carIdx, &row.stints)?;
+            }
+            for (carIdx, car) in self.car.iter().enumerate() {
+                for stop in &car.pitStops {
+                    store.pitStop(self.sessionUID, carIdx, stop.lap, stop.laneInMS, stop.stopInMS)?;
+                }
+            }
+            for decision in &self.stewards.decisions {
+                store.penalty(self.sessionUID, decision)?;
+            }
+            store.commit()
+        })();
+        self.committed(result.map(|_| true));
+    }
+
+    // Say on the dashboard when the database couldn't be written, once until a commit gets through.
+    fn stored(&mut self, result: rusqlite::Result<()>) {
+        if let Err(err) = result {
+            if !self.isStoreFailing {
+                self.notices.push(format!("Couldn't write to the database: {err}"));
+            }
+            self.isStoreFailing = true;
+        }
+    }
+
+    // The outcome of a write that may have committed the batch.
+    fn committed(&mut self, result: rusqlite::Result<bool>) {
+        match result {
+            Ok(true) => self.isStoreFailing = false,
+            Ok(false) => {}
+            Err(err) => self.stored(Err(err)),
+        }
+    }
+
+    // The last few export notices, oldest first.
+    fn latestNotices(&self) -> &[String] {
+        &self.notices[self.notices.len().saturating_sub(3)..]
+    }
+
+    // Write the lap export for this session, and say how it went on the dashboard.
+    fn exportLaps(&mut self, dir: &str) {
+        let path = export::path(dir, "laps", &format!("{:?}", self.session), self.sessionUID, "csv");
+        let rows = self.lapRows();
+
+        self.notices.push(match export::laps(&path, &rows) {
+            Ok(()) => format!("{} laps written to {}", rows.len(), path.display()),
+            Err(err) => format!("Couldn't write {}: {err}", path.display()),
+        });
+    }
+
+    // Running order once the time penalties handed out so far are added on.
+    fn provisional(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = self
+            .positions
+            .iter()
+            .copied()
+            .filter(|idx| *idx <= self.playerCarIndex as usize)
+            .collect();
+
+        // Stable, so cars on equal time keep their track order.
+        order.sort_by_key(|idx| {
+            let car = &self.car[*idx];
+            car.time.leader.inMS + car.penalties.timePenalty as u32 * 1000
+        });
+
+        order
+    }
+
+    // What a stop costs at this track, and how many of this session's stops that's from.
+    // Earlier sessions here stand in, as zero stops, until a stop this session is done.
+    fn pitLoss(&self) -> Option<(u32, usize)> {
+        let losses: Vec<u32> = self
+            .activeCars()
+            .iter()
+            .flat_map(|car| car.pitStops.iter().filter_map(|stop| car.netLoss(stop)))
+            .collect();
+
+        if losses.is_empty() {
+            return self.trackPitLoss.map(|ms| (ms, 0));
+        }
+        Some((losses.iter().sum::<u32>() / losses.len() as u32, losses.len()))
+    }
+
+    // Where `idx` would rejoin if it pitted now, going by the gaps to the leader.
+    fn rejoin(&self, idx: usize) -> Option<Rejoin> {
+        let (loss, _) = self.pitLoss()?;
+        let after = self.car[idx].time.leader.inMS + loss;
+
+        let mut rejoin = Rejoin {
+            position: 1,
+            ahead: None,
+            behind: None,
+        };
+        for other in self.order().into_iter().filter(|other| *other != idx) {
+            let gap = self.car[other].time.leader.inMS;
+            if gap <= after {
+                rejoin.position += 1;
+                if rejoin.ahead.is_none_or(|(_, by)| after - gap < by) {
+                    rejoin.ahead = Some((other, after - gap));
+                }
+            } else if rejoin.behind.is_none_or(|(_, by)| gap - after < by) {
+                rejoin.behind = Some((other, gap - after));
+            }
+        }
+        Some(rejoin)
+    }
+
+    // The player's car, None when spectating.
+    fn player(&self) -> Option<usize> {
+        Some(self.playerCarIndex as usize).filter(|idx| *idx < self.car.len())
+    }
+
+    // The other car in the player's team, if it's running.
+    fn teammate(&self) -> Option<usize> {
+        let player = self.player()?;
+        self.order()
+            .into_iter()
+            .find(|idx| *idx != player && self.car[*idx].team.id == self.car[player].team.id)
+    }
+
+    // Every car through the speed trap, fastest first.
+    fn speedTraps(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = self
+            .order()
+            .into_iter()
+            .filter(|idx| self.car[*idx].speedTrap.isSet())
+            .collect();
+        order.sort_by(|a, b| self.car[*b].speedTrap.speed.total_cmp(&self.car[*a].speedTrap.speed));
+        order
+    }
 }
 
 fn main() {
+    // pitwall query <report>, reports from the session database rather than the dashboard.
+    if env::args().nth(1).as_deref() == Some("query") {
+        process::exit(query::run(env::args().skip(2)));
+    }
+
+    let config = Config::from_args();
+
     let socket = UdpSocket::bind("0.0.0.0:20777").expect("Couldn't bind to address.");
-    println!("UDP Port Bound");
+    // Keep stdout clean for the JSON.
+    if config.json {
+        eprintln!("UDP Port Bound");
+    } else {
+        println!("UDP Port Bound");
+    }
 
-    let mut page = Page {
+    // Written by the loop below as packets arrive, read by the dashboard as it draws.
+    let state = Arc::new(Mutex::new(Page {
         positions: [usize::MAX; 23],
         ..Page::default()
-    };
+    }));
+    let changed = Arc::new(AtomicBool::new(true));
+
+    // The session database and the championship are only for the dashboard, not --json.
+    let mut store = (!config.json).then(|| {
+        Store::open(&config.database).unwrap_or_else(|err| {
+            eprintln!("Couldn't open {}: {err}", config.database);
+            process::exit(1);
+        })
+    });
+
+    // Live gauges for Grafana, pushed to InfluxDB and scraped by Prometheus.
+    let metrics = Arc::new(Mutex::new(Metrics::default()));
+    let mut influx = config.influx.clone().map(|endpoint| {
+        Influx::new(endpoint).unwrap_or_else(|err| {
+            eprintln!("Couldn't open a socket for InfluxDB: {err}");
+            process::exit(1);
+        })
+    });
+    if let Some(addr) = &config.prometheus {
+        metrics::serve(addr, Arc::clone(&metrics)).unwrap_or_else(|err| {
+            eprintln!("Couldn't serve Prometheus metrics on {addr}: {err}");
+            process::exit(1);
+        });
+    }
+
+    // Overlays in the browser, fed from the page ten times a second.
+    let hub = Arc::new(Mutex::new(Hub::default()));
+    let mut publishedAt = Instant::now();
+    if let Some(addr) = &config.web {
+        web::serve(addr, Arc::clone(&hub)).unwrap_or_else(|err| {
+            eprintln!("Couldn't serve the overlays on {addr}: {err}");
+            process::exit(1);
+        });
+    }
+
+    // Rig accessories on the LAN, by way of an MQTT broker.
+    let mut mqtt = config
+        .mqtt
+        .as_ref()
+        .map(|addr| Mqtt::new(addr, &config.mqttPrefix, config.mqttRate));
+
+    let mut championship = (!config.json).then(|| {
+        Database::load(&config.championship).unwrap_or_else(|err| {
+            eprintln!("Couldn't read {}: {err}", config.championship);
+            process::exit(1);
+        })
+    });
+
+    // The full screen dashboard on its own thread, unless it's headless.
+    let actions = (!config.json).then(|| {
+        let layout = Layout::load(config.layout.as_deref(), config.tower.as_deref())
+            .unwrap_or_else(|err| {
+                eprintln!("Couldn't load the tower layout: {err}");
+                process::exit(1);
+            });
+        tui::spawn(
+            config.fps,
+            config.rainThreshold,
+            layout,
+            Arc::clone(&state),
+            Arc::clone(&metrics),
+            Arc::clone(&changed),
+        )
+        .unwrap_or_else(|err| {
+            eprintln!("Couldn't set up the terminal: {err}");
+            process::exit(1);
+        })
+    });
+    // Wake up now and then without packets, so key presses still get an answer.
+    socket
+        .set_read_timeout(Some(Duration::from_millis(50)))
+        .expect("Couldn't set the socket timeout.");
 
     let mut buffer = [0; 1500];
     loop {
-        let (size, _) = socket.recv_from(&mut buffer).unwrap();
+        if let Some(actions) = &actions {
+            match actions.try_recv() {
+                Ok(Action::Export) => {
+                    let mut page = state.lock().unwrap();
+                    page.exportLaps(&config.exportDir);
+                    if let Some(store) = &mut store {
+                        page.store(store);
+                    }
+                }
+                Ok(Action::Quit) | Err(TryRecvError::Disconnected) => break,
+                Ok(Action::Nothing) | Err(TryRecvError::Empty) => {}
+            }
+        }
 
+        let size = match socket.recv_from(&mut buffer) {
+            Ok((size, _)) => size,
+            Err(err) if matches!(err.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => continue,
+            Err(err) => panic!("Couldn't receive a packet: {err}"),
+        };
         let header = Header::unpack(&buffer);
 
         let packet = match header.packetId {
@@ -523,7 +1222,7 @@ fn main() {
             },
             PacketId::Poisoned => {
                 dbg!(header);
-                println!(
+                eprintln!(
                     "{}, of {size}, & of ID {:#?}",
                     "Unknown PacketId".red(),
                     header.packetId
@@ -532,12 +1231,35 @@ fn main() {
             }
         };
 
+        // Headless, one JSON object per line instead of the dashboard.
+        if config.json {
+            if let Some(json) = packet.json() {
+                println!("{json}");
+            }
+            continue;
+        }
+
+        // Borrowed out of the guard, so the fields can be borrowed apart.
+        let mut guard = state.lock().unwrap();
+        let page = &mut *guard;
+        changed.store(true, Ordering::Relaxed);
+
         match packet {
             Packet::Event(e) => {
-                match e.eventType {
+                if let Some(store) = &mut store {
+                    let result = store.event(&e);
+                    page.stored(result);
+                }
+
+                match e.eventType() {
                     EventType::SessionStarted => {
+                        // Whatever is left of the last session goes in the database first.
+                        if let Some(store) = &mut store {
+                            page.store(store);
+                        }
+
                         // We have a new sessions, so let's reset everything back to defualt.
-                        page = Page {
+                        *page = Page {
                             positions: [usize::MAX; 23],
                             ..Page::default()
                         };
@@ -545,17 +1267,88 @@ fn main() {
                             car.time = Times::default();
                         }
                     },
+                    EventType::SessionEnded => {
+                        page.exportLaps(&config.exportDir);
+                        if let Some(store) = &mut store {
+                            page.store(store);
+                        }
+                    },
+                    EventType::Penalty => {
+                        let penalty = unsafe { e.eventDetails.penalty };
+                        page.stewards.record(e.header.sessionTime, &penalty);
+                    },
+                    EventType::DriveThroughPenaltyServed => {
+                        let served = unsafe { e.eventDetails.driveThroughPenaltyServed };
+                        page.stewards.servedDriveThrough(served.vehicleIdx);
+                    },
+                    EventType::StopGoPenaltyServed => {
+                        let served = unsafe { e.eventDetails.stopGoPenaltyServed };
+                        page.stewards.servedStopGo(served.vehicleIdx);
+                    },
+                    EventType::SpeedTrap => {
+                        let trap = unsafe { e.eventDetails.speedTrap };
+                        if let Some(car) = page.car.get_mut(trap.vehicleIdx as usize) {
+                            let lap = car.lapNum;
+                            car.speedTrap.event(&trap, lap);
+                        }
+                    },
                     _ => {
                         // Not handling these.
                     }
                 }
+
+                if !matches!(e.eventType(), EventType::Buttons) {
+                    page.events.push(e.to_json());
+                }
+                if let Some(line) = tui::describe(&e, page) {
+                    page.log.push(line);
+                }
+                if let Some(mqtt) = &mut mqtt {
+                    mqtt.event(&e);
+                }
             }
             Packet::Session(s) => {
+                // What a stop has cost here before, until this session has stops of its own.
+                if let Some(store) = store.as_mut().filter(|_| s.header.sessionUID != page.sessionUID) {
+                    let loss = store.pitLoss(&format!("{:?}", s.trackId), s.header.sessionUID);
+                    page.trackPitLoss = loss.as_ref().ok().copied().flatten();
+                    page.stored(loss.map(|_| ()));
+                }
                 page.playerCarIndex = s.header.playerCarIndex;
+                page.sessionUID = s.header.sessionUID;
+                page.season = s.seasonLinkIdentifier;
+                page.weekend = s.weekendLinkIdentifier;
+                let numSessions = (s.numSessionsInWeekend as usize).min(s.weekendStructure.len());
+                page.weekendStructure = s.weekendStructure[..numSessions].to_vec();
+                if let Some(store) = &mut store {
+                    let result = store.session(&s);
+                    page.stored(result);
+                }
                 page.lap.total = s.totalLaps;
                 page.session = s.sessionType;
+                page.trackId = s.trackId;
                 page.sessionDuration = s.sessionDuration;
                 page.sessionTimeLeft = s.sessionTimeLeft;
+                page.pitWindow = PitWindow {
+                    idealLap: s.pitStopWindowIdealLap,
+                    latestLap: s.pitStopWindowLatestLap,
+                    rejoinPosition: s.pitStopRejoinPosition,
+                };
+
+                // Safety Car, VSC & Red Flags
+                page.raceControl.safetyCar = s.safetyCarStatus;
+                page.raceControl.periodsSC = s.numSafetyCarPeriods;
+                page.raceControl.periodsVSC = s.numVirtualSafetyCarPeriods;
+                page.raceControl.periodsRed = s.numRedFlagPeriods;
+
+                // Marshal Zones
+                let zones = s.marshalZones;
+                let numZones = (s.numMarshalZones as usize).min(zones.len());
+                page.track.length = s.trackLength;
+                page.track.zones = zones[..numZones].to_vec();
+
+                // Weather
+                page.forecast.update(&s);
             }
             Packet::Participants(p) => {
                 page.participants = p.numActiveCars;
@@ -574,6 +1367,11 @@ fn main() {
                     page.car[idx].driver.isTelemetryEnabled =
                         p.participants[idx].yourTelemetry == 1;
                 }
+                if let Some(store) = &mut store {
+                    let result = store.participants(&p);
+                    page.stored(result);
+                }
+                metrics.lock().unwrap().participants(&p);
             }
             Packet::CarTelemetry(t) => {
                 for i in 0..=page.playerCarIndex {
@@ -585,8 +1383,25 @@ fn main() {
                     page.car[idx].telemetry.rpm = t.carTelemetry[idx].engineRPM;
                     page.car[idx].telemetry.leds = t.carTelemetry[idx].revLightsBitValue;
                 }
+                if let Some(store) = &mut store {
+                    let result = store.telemetry(&t, page.playerCarIndex as usize + 1);
+                    page.committed(result);
+                }
+
+                let lines = {
+                    let mut metrics = metrics.lock().unwrap();
+                    metrics.telemetry(&t);
+                    influx.as_ref().map(|_| metrics.influx())
+                };
+                if let (Some(influx), Some(lines)) = (&mut influx, lines) {
+                    if let Err(err) = influx.send(lines) {
+                        page.notices.push(format!("Couldn't send to InfluxDB: {err}"));
+                    }
+                }
             }
             Packet::CarStatus(s) => {
+                metrics.lock().unwrap().status(&s);
+
                 for i in 0..=page.playerCarIndex {
                     let idx = i as usize;
 
@@ -602,6 +1417,43 @@ fn main() {
                     page.car[idx].Ers = Ers::build(s.carStatus[idx].ersStoreEnergy, s.carStatus[idx].ersDeployMode);
                 }
             }
+            Packet::FinalClassification(c) => {
+                let names = |idx: usize| {
+                    let car = &page.car[idx % page.car.len()];
+                    (car.driver.name.clone(), car.team.id.to_string())
+                };
+                page.results = Some(Results::new(page.session, page.trackId, &c, names));
+                page.saveResults(&config.exportDir);
+                if let Some(championship) = &mut championship {
+                    page.recordStandings(championship, &config.rules);
+                }
+                if let Some(store) = &mut store {
+                    page.store(store);
+                }
+            }
+            Packet::SessionHistory(h) => {
+                // The game's own lap and sector times, for the laps we have already logged.
+                let Some(car) = page.car.get_mut(h.carIdx as usize) else {
+                    continue;
+                };
+                let history = h.lapHistory;
+                let numLaps = (h.numLaps as usize).min(history.len());
+
+                for lap in &mut car.laps {
+                    let Some(idx) = (lap.lapNum as usize).checked_sub(1).filter(|idx| *idx < numLaps) else {
+                        continue;
+                    };
+                    let played = &history[idx];
+
+                    if played.lapTimeInMS > 0 {
+                        lap.inMS = played.lapTimeInMS;
+                        lap.isValid = played.lapValidBitFlags.isLapValid();
+                    }
+                    lap.sector1InMS = played.sector1TimeInMS();
+                    lap.sector2InMS = played.sector2TimeInMS();
+                    lap.sector3InMS = played.sector3TimeInMS();
+                }
+            }
             Packet::Lap(l) => {
                 for (idx, car) in l.cars.iter().enumerate()
                 {
@@ -614,6 +1466,31 @@ fn main() {
 
                     // Update car positions.
                     page.positions[pos] = idx;
+                    pcs.lapDistance = car.lapDistance;
+                    pcs.safetyCarDelta = car.safetyCarDelta;
+                    pcs.penalties.update(car);
+
+                    // Remember if any of this lap was run under the SC / VSC or through the pits.
+                    pcs.isNeutralised |= page.raceControl.isNeutralised();
+                    pcs.isPitting |= !matches!(car.pitStatus, PitStatus::None);
+                    pcs.isInvalid |= car.currentLapInvalid == 1;
+
+                    // Time the trip down the pit lane, it's over once the lane timer stops.
+                    if car.pitLaneTimerActive == 1 {
+                        let stop = pcs.pitLane.get_or_insert(PitStop {
+                            lap: car.currentLapNum,
+                            tyreIn: pcs.tyres.visual,
+                            ageIn: pcs.tyres.age,
+                            positionIn: car.racePosition,
+                            ..PitStop::default()
+                        });
+                        stop.laneInMS = car.pitLaneTimeInLaneInMS;
+                        stop.stopInMS = stop.stopInMS.max(car.pitStopTimerInMS);
+                    } else if let Some(mut stop) = pcs.pitLane.take() {
+                        stop.tyreOut = pcs.tyres.visual;
+                        stop.positionOut = car.racePosition;
+                        pcs.pitStops.push(stop);
+                    }
 
                     // Ignore Formation & First Lap
                     if car.lapDistance < 0.0 {
@@ -661,6 +1538,23 @@ fn main() {
                                     pcs.time.sector3.isPB = false;
                                 }
 
+                                // Log the lap we just finished.
+                                pcs.laps.push(LapRecord {
+                                    lapNum: pcs.lapNum,
+                                    inMS: car.lastLapTimeInMS,
+                                    tyre: pcs.tyres.visual,
+                                    tyreAge: pcs.tyres.age,
+                                    isNeutralised: pcs.isNeutralised,
+                                    isPit: pcs.isPitting,
+                                    isValid: !pcs.isInvalid,
+                                    position: car.racePosition,
+                                    leaderInMS: pcs.time.leader.inMS,
+                                    ..LapRecord::default()
+                                });
+                                pcs.isNeutralised = page.raceControl.isNeutralised();
+                                pcs.isPitting = !matches!(car.pitStatus, PitStatus::None);
+                                pcs.isInvalid = car.currentLapInvalid == 1;
+
                                 // And check the overall lap time.
                                 pcs.time.lastLap.inMS = car.lastLapTimeInMS;
                                 if page.ob.isBest(
@@ -781,94 +1675,41 @@ fn main() {
                     pcs.pitCount = car.numPitStops;
                     pcs.carStatus = car.driverStatus;
                     pcs.sector = car.sector;
+                    pcs.resultStatus = car.resultStatus;
+                    pcs.speedTrap.lapData(car.speedTrapFastestSpeed, car.speedTrapFastestLap);
                 }
             }
+            Packet::CarDamage(d) => {
+                metrics.lock().unwrap().damage(&d);
+                continue;
+            }
             _ => {
                 continue;
             }
         }
 
-        // Clear Screen & Corsor @ Top Left
-        print!("{ESC}c");
-
-        println!(
-            "{session:>5} {lapLeader:02} {lapTotal:02} [{timeLeft} of {duration}]",
-            session   = page.session,
-            lapLeader = page.lap.leader,
-            lapTotal  = page.lap.total,
-            timeLeft  = page.sessionTimeLeft,
-            duration  = page.sessionDuration,
-        );
-
-        // Header
-        println!(
-                "{pos:2} {driver:>15} (##) {timeLastLap:>8} | {interval:>8} | {leader:>8} | {timeSector1:>8} {timeSector2:>8} {timeSector3:>8} | {timeCurrent:>8} | {lap:>3} {sector:^1} {tyre:>4} | {gear:>1} {DRS:^3} {ERS:^3} {speed:>3} | {state:^5}",
-                pos         = "P",
-                driver      = "Driver",
-                timeLastLap = "Last",
-                interval    = "Interval",
-                leader      = "Leader",
-                timeSector1 = "S1",
-                timeSector2 = "S2",
-                timeSector3 = "S3",
-                timeCurrent = "Time",
-                lap         = "Lap",
-                sector      = "S",
-                tyre        = "Tyre",
-                gear        = "G",
-                DRS         = "DRS",
-                ERS         = "ERS",
-                speed       = "KPH",
-                state       = "State"
-            );
-
-        for (pos, idx) in page.positions.iter().enumerate() {
-            if *idx > page.playerCarIndex as usize {
-                // Skip empty slots.
-                continue;
+        if config.web.is_some() && publishedAt.elapsed() >= Duration::from_millis(100) {
+            publishedAt = Instant::now();
+            hub.lock().unwrap().publish(Snapshot {
+                session: page.sessionJson(),
+                cars: page.carsJson(),
+                laps: page.lapsJson(),
+                bests: page.bestsJson(),
+                events: page.events.clone(),
+                weather: page.weatherJson(),
+            });
+        }
+
+        if let Some(mqtt) = &mut mqtt {
+            mqtt.update(page, &metrics.lock().unwrap());
+            if let Err(err) = mqtt.flush() {
+                page.notices.push(format!("Couldn't publish to MQTT: {err}"));
             }
+        }
+    }
 
-            let car = &page.car[*idx];
-
-            println!(
-                "{pos:02} {driver} {timeLastLap:>8} | {interval:>8} | {leader:>8} | {timeSector1:>8} {timeSector2:>8} {timeSector3:>8} | {timeCurrent:>8} | {lap:>3} {sector:^1}  {tyre:>4} | {gear:>1} {DRS} {ERS} {speed:>3} | {state:^5}",
-                driver      = car.driver.getDriver(),
-                timeLastLap = car.time.lastLap,
-                interval    = car.time.interval,
-                leader      = car.time.leader,
-                timeSector1 = car.time.sector1,
-                timeSector2 = car.time.sector2,
-                timeSector3 = car.time.sector3,
-                timeCurrent = car.time.current,
-                lap         = car.lapNum,
-                sector      = car.sector,
-                tyre        = car.tyres,
-                gear        = car.telemetry.gear,
-                DRS         = car.Drs,
-                ERS         = car.Ers,
-                speed       = car.telemetry.speed.kph,
-                state       = car.carStatus
-            );
-        }
-
-        // Header
-        println!();
-
-        // Bests
-        println!(
-            "{pos:2} {driver:>15}      {bestLapTime:>8} | {interval:>8} | {leader:>8} | {bestSector1:>8} {bestSector2:>8} {bestSector3:>8} | {bestPossible:>8.3}",
-            pos         = "",
-            driver      = "Bests",
-            interval    = "",
-            leader      = "",
-            bestLapTime = page.ob.lapTime,
-            bestSector1 = page.ob.sector1,
-            bestSector2 = page.ob.sector2,
-            bestSector3 = page.ob.sector3,
-            bestPossible= page.ob.possible as f32 / 1000_f32,
-        );
-
-        // Footer
-        println!();
+    // Quitting, so whatever hasn't been stored yet goes in now.
+    if let Some(store) = &mut store {
+        state.lock().unwrap().store(store);
     }
 }
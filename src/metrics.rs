@@ -0,0 +1,483 @@
+use pitwall::packet::*;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs, UdpSocket};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// Wheel order of every four wheel array in the packets.
+pub static WHEELS: [&str; 4] = ["rl", "rr", "fl", "fr"];
+
+// Room for the lines in one datagram, under a typical MTU.
+static DATAGRAM: usize = 1400;
+
+// How long an endpoint that refused us is left alone.
+static RETRY: Duration = Duration::from_secs(5);
+
+// Batches waiting on a slow endpoint, beyond which they are given back to the caller.
+static QUEUE: usize = 4;
+
+// How long a write, or a Prometheus scrape's request, may take.
+static TIMEOUT: Duration = Duration::from_millis(250);
+
+// One value per car: name, help, and where it comes from.
+pub type Scalar = (&'static str, &'static str, fn(&Car) -> f32);
+// One value per wheel, in WHEELS order.
+pub type PerWheel = (&'static str, &'static str, fn(&Car) -> [f32; 4]);
+
+pub static SCALARS: [Scalar; 17] = [
+    (
+        "speed_kph",
+        "Speed",
+        |car| { car.telemetry.speed }.kph as f32,
+    ),
+    ("rpm", "Engine RPM", |car| { car.telemetry.engineRPM }
+        as f32),
+    ("gear", "Gear, 0 is neutral and -1 reverse", |car| {
+        car.telemetry.gear as i8 as f32
+    }),
+    ("throttle", "Throttle, 0 to 1", |car| car.telemetry.throttle),
+    ("brake", "Brake, 0 to 1", |car| car.telemetry.brake),
+    ("fuel_kg", "Fuel in the tank", |car| car.status.fuelInTank),
+    (
+        "fuel_laps",
+        "Fuel remaining in laps, as on the MFD",
+        |car| car.status.fuelRemainingLaps,
+    ),
+    ("ers_store_joules", "ERS energy store", |car| {
+        car.status.ersStoreEnergy
+    }),
+    (
+        "ers_deployed_joules",
+        "ERS energy deployed this lap",
+        |car| car.status.ersDeployedThisLap,
+    ),
+    (
+        "front_left_wing_damage_percent",
+        "Front left wing damage",
+        |car| car.damage.frontLeftWingDamage as f32,
+    ),
+    (
+        "front_right_wing_damage_percent",
+        "Front right wing damage",
+        |car| car.damage.frontRightWingDamage as f32,
+    ),
+    ("rear_wing_damage_percent", "Rear wing damage", |car| {
+        car.damage.rearWingDamage as f32
+    }),
+    ("floor_damage_percent", "Floor damage", |car| {
+        car.damage.floorDamage as f32
+    }),
+    ("diffuser_damage_percent", "Diffuser damage", |car| {
+        car.damage.diffuserDamage as f32
+    }),
+    ("sidepod_damage_percent", "Sidepod damage", |car| {
+        car.damage.sidepodDamage as f32
+    }),
+    ("gearbox_damage_percent", "Gearbox damage", |car| {
+        car.damage.gearBoxDamage as f32
+    }),
+    ("engine_damage_percent", "Engine damage", |car| {
+        car.damage.engineDamage as f32
+    }),
+];
+
+pub static PER_WHEEL: [PerWheel; 6] = [
+    ("tyre_surface_celsius", "Tyre surface temperature", |car| {
+        car.telemetry.tyresSurfaceTemperature.map(|t| t as f32)
+    }),
+    ("tyre_inner_celsius", "Tyre inner temperature", |car| {
+        car.telemetry.tyresInnerTemperature.map(|t| t as f32)
+    }),
+    ("brake_celsius", "Brake temperature", |car| {
+        { car.telemetry.brakesTemperature }.map(|t| t as f32)
+    }),
+    ("tyre_pressure_psi", "Tyre pressure", |car| {
+        car.telemetry.tyresPressure
+    }),
+    ("tyre_wear_percent", "Tyre wear", |car| {
+        let wear = car.damage.tyresWear;
+        [wear.RL, wear.RR, wear.FL, wear.FR]
+    }),
+    ("tyre_damage_percent", "Tyre damage", |car| {
+        car.damage.tyresDamage.map(|d| d as f32)
+    }),
+];
+
+// The latest of each car's telemetry, status and damage.
+#[derive(Debug, Default, Clone)]
+pub struct Car {
+    pub driver: String, // PacketParticipants.participants.name
+    pub team: String,   // PacketParticipants.participants.teamId
+    pub telemetry: CarTelemetry,
+    pub status: CarStatus,
+    pub damage: CarDamage,
+}
+
+// Live gauges for every car, for Grafana by way of InfluxDB or Prometheus.
+#[derive(Debug, Default, Clone)]
+pub struct Metrics {
+    pub numCars: usize, // PacketParticipants.numActiveCars, nothing is published before it arrives
+    pub cars: [Car; 22],
+}
+
+impl Metrics {
+    pub fn participants(&mut self, p: &PacketParticipants) {
+        let participants = p.participants;
+        self.numCars = (p.numActiveCars as usize).min(self.cars.len());
+
+        for (car, participant) in self.cars.iter_mut().zip(participants.iter()) {
+            car.driver = participant.name_to_string();
+            car.team = participant.teamId.to_string();
+        }
+    }
+
+    pub fn telemetry(&mut self, t: &PacketCarTelemetry) {
+        for (car, telemetry) in self.cars.iter_mut().zip(t.carTelemetry) {
+            car.telemetry = telemetry;
+        }
+    }
+
+    pub fn status(&mut self, s: &PacketCarStatus) {
+        for (car, status) in self.cars.iter_mut().zip(s.carStatus) {
+            car.status = status;
+        }
+    }
+
+    pub fn damage(&mut self, d: &PacketCarDamage) {
+        for (car, damage) in self.cars.iter_mut().zip(d.carDamageData) {
+            car.damage = damage;
+        }
+    }
+
+    pub fn active(&self) -> impl Iterator<Item = (usize, &Car)> {
+        self.cars[..self.numCars].iter().enumerate()
+    }
+
+    // One line per car, e.g. car,car=0,driver=Lando\ Norris,team=McLaren speed_kph=301,... 1700000000000000000
+    pub fn influx(&self) -> String {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let mut lines = String::new();
+
+        for (idx, car) in self.active() {
+            let _ = write!(
+                lines,
+                "car,car={idx},driver={},team={} ",
+                influxTag(&car.driver),
+                influxTag(&car.team)
+            );
+
+            let mut fields = vec![];
+            for (name, _, value) in &SCALARS {
+                fields.push(format!("{name}={}", value(car)));
+            }
+            for (name, _, values) in &PER_WHEEL {
+                for (wheel, value) in WHEELS.iter().zip(values(car)) {
+                    fields.push(format!("{name}_{wheel}={value}"));
+                }
+            }
+
+            let _ = writeln!(lines, "{} {timestamp}", fields.join(","));
+        }
+
+        lines
+    }
+
+    // The Prometheus text format, one gauge per metric with a sample per car.
+    pub fn prometheus(&self) -> String {
+        let mut text = String::new();
+        let labels = |idx: usize, car: &Car| {
+            format!(
+                "car=\"{idx}\",driver=\"{}\",team=\"{}\"",
+                promLabel(&car.driver),
+                promLabel(&car.team)
+            )
+        };
+
+        for (name, help, value) in &SCALARS {
+            let _ = writeln!(
+                text,
+                "# HELP pitwall_{name} {help}\n# TYPE pitwall_{name} gauge"
+            );
+            for (idx, car) in self.active() {
+                let _ = writeln!(
+                    text,
+                    "pitwall_{name}{{{}}} {}",
+                    labels(idx, car),
+                    value(car)
+                );
+            }
+        }
+        for (name, help, values) in &PER_WHEEL {
+            let _ = writeln!(
+                text,
+                "# HELP pitwall_{name} {help}\n# TYPE pitwall_{name} gauge"
+            );
+            for (idx, car) in self.active() {
+                for (wheel, value) in WHEELS.iter().zip(values(car)) {
+                    let _ = writeln!(
+                        text,
+                        "pitwall_{name}{{{},wheel=\"{wheel}\"}} {value}",
+                        labels(idx, car)
+                    );
+                }
+            }
+        }
+
+        text
+    }
+}
+
+// Spaces, commas and equals signs are escaped in tag values.
+fn influxTag(value: &str) -> String {
+    let mut tag = String::new();
+    for c in value.chars() {
+        if matches!(c, ' ' | ',' | '=' | '\\') {
+            tag.push('\\');
+        }
+        tag.push(c);
+    }
+    tag
+}
+
+// Backslashes, quotes and newlines are escaped in label values.
+fn promLabel(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// Where the line protocol goes, --influx udp://host:port or tcp://host:port.
+#[derive(Debug, Clone)]
+pub enum Endpoint {
+    Udp(String),
+    Tcp(String),
+}
+
+impl FromStr for Endpoint {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once("://") {
+            Some(("udp", addr)) => Ok(Endpoint::Udp(addr.to_string())),
+            Some(("tcp", addr)) => Ok(Endpoint::Tcp(addr.to_string())),
+            _ => Err(format!(
+                "expected udp://host:port or tcp://host:port, not '{value}'"
+            )),
+        }
+    }
+}
+
+// Sends from a thread of its own, so an endpoint that stalls never holds up the packets.
+// Shared by InfluxDB and MQTT.
+pub struct Outbox<T> {
+    queue: SyncSender<T>,
+    results: Receiver<io::Result<()>>, // How each batch went
+    isFailing: bool,                   // The last batch failed, and has been said so
+    retryAt: Option<Instant>,          // Set while the endpoint is refusing us
+}
+
+impl<T: Send + 'static> Outbox<T> {
+    pub fn spawn(mut send: impl FnMut(&T) -> io::Result<()> + Send + 'static) -> Self {
+        let (queue, batches) = mpsc::sync_channel::<T>(QUEUE);
+        let (done, results) = mpsc::channel();
+
+        thread::spawn(move || {
+            for batch in batches {
+                if done.send(send(&batch)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            queue,
+            results,
+            isFailing: false,
+            retryAt: None,
+        }
+    }
+
+    pub fn isRetrying(&self) -> bool {
+        self.retryAt.is_some_and(|at| Instant::now() < at)
+    }
+
+    // Hand `batch` to the thread, or back to the caller while the thread is still busy
+    // or the endpoint is being left alone.
+    pub fn push(&mut self, batch: T) -> Result<(), T> {
+        if self.isRetrying() {
+            return Err(batch);
+        }
+        self.queue.try_send(batch).map_err(|err| match err {
+            TrySendError::Full(batch) | TrySendError::Disconnected(batch) => batch,
+        })
+    }
+
+    // How the batches went since the last call, with `failed` called for each that didn't.
+    // Only the first failure is returned, until a batch gets through again.
+    pub fn results(&mut self, mut failed: impl FnMut()) -> io::Result<()> {
+        let mut failure = None;
+        for result in self.results.try_iter() {
+            match result {
+                Ok(()) => self.isFailing = false,
+                Err(err) => {
+                    failed();
+                    self.retryAt = Some(Instant::now() + RETRY);
+                    if !self.isFailing {
+                        failure = Some(err);
+                    }
+                    self.isFailing = true;
+                }
+            }
+        }
+        failure.map_or(Ok(()), Err)
+    }
+}
+
+// Sends line protocol to an InfluxDB (or Telegraf) listener.
+pub struct Influx {
+    outbox: Outbox<String>,
+}
+
+impl Influx {
+    pub fn new(endpoint: Endpoint) -> io::Result<Self> {
+        let mut sink = Sink::new(endpoint)?;
+        let outbox = Outbox::spawn(move |lines: &String| sink.send(lines));
+        Ok(Self { outbox })
+    }
+
+    // Queue `lines`, or drop them while the sender is behind, and hand back a failure
+    // the sender has had since the last call.
+    pub fn send(&mut self, lines: String) -> io::Result<()> {
+        let _ = self.outbox.push(lines);
+        self.outbox.results(|| {})
+    }
+}
+
+// The socket behind Influx.
+struct Sink {
+    endpoint: Endpoint,
+    udp: Option<UdpSocket>,
+    tcp: Option<TcpStream>,
+}
+
+impl Sink {
+    fn new(endpoint: Endpoint) -> io::Result<Self> {
+        let udp = match endpoint {
+            Endpoint::Udp(_) => Some(UdpSocket::bind("0.0.0.0:0")?),
+            Endpoint::Tcp(_) => None,
+        };
+
+        Ok(Self {
+            endpoint,
+            udp,
+            tcp: None,
+        })
+    }
+
+    // Connecting first if need be.
+    fn send(&mut self, lines: &str) -> io::Result<()> {
+        let result = match &self.endpoint {
+            Endpoint::Udp(addr) => match &self.udp {
+                Some(socket) => datagrams(lines)
+                    .iter()
+                    .try_for_each(|datagram| socket.send_to(datagram.as_bytes(), addr).map(|_| ())),
+                None => Ok(()),
+            },
+            Endpoint::Tcp(addr) => match &mut self.tcp {
+                Some(stream) => stream.write_all(lines.as_bytes()),
+                None => connect(addr).and_then(|mut stream| {
+                    stream.write_all(lines.as_bytes())?;
+                    self.tcp = Some(stream);
+                    Ok(())
+                }),
+            },
+        };
+
+        if result.is_err() {
+            self.tcp = None;
+        }
+        result
+    }
+}
+
+pub fn connect(addr: &str) -> io::Result<TcpStream> {
+    let addr = addr
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{addr} didn't resolve")))?;
+
+    let stream = TcpStream::connect_timeout(&addr, TIMEOUT)?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    Ok(stream)
+}
+
+// Whole lines, as many as fit in each datagram.
+fn datagrams(lines: &str) -> Vec<String> {
+    let mut datagrams = vec![String::new()];
+
+    for line in lines.lines() {
+        let current = datagrams.last_mut().unwrap();
+        if !current.is_empty() && current.len() + line.len() + 1 > DATAGRAM {
+            datagrams.push(String::new());
+        }
+        let current = datagrams.last_mut().unwrap();
+        current.push_str(line);
+        current.push('\n');
+    }
+
+    datagrams.retain(|datagram| !datagram.is_empty());
+    datagrams
+}
+
+// Serve GET /metrics, from whatever the dashboard has seen last, each scrape on its own thread.
+pub fn serve(addr: &str, metrics: Arc<Mutex<Metrics>>) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let metrics = Arc::clone(&metrics);
+            thread::spawn(move || {
+                let _ = respond(stream, &metrics);
+            });
+        }
+    });
+
+    Ok(())
+}
+
+fn respond(mut stream: TcpStream, metrics: &Mutex<Metrics>) -> io::Result<()> {
+    // A client that never finishes its request gives up the thread.
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+
+    // The headers tell us nothing we need.
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let (status, body) = match request.split_whitespace().nth(1) {
+        Some("/metrics") => (
+            "200 OK",
+            metrics.lock().map(|m| m.prometheus()).unwrap_or_default(),
+        ),
+        _ => ("404 Not Found", "Not found\n".to_string()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
@@ -0,0 +1,287 @@
+use crate::metrics::{self, Metrics, Outbox, PER_WHEEL, SCALARS, WHEELS};
+use crate::Page;
+use pitwall::packet::*;
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::process;
+use std::time::{Duration, Instant};
+
+// Live values as MQTT topics for devices on the LAN, e.g. pitwall/car/3/gear.
+// Only QoS 0 publishing, and only values that changed since they were last sent.
+pub struct Mqtt {
+    prefix: String,                // Topic prefix, --mqtt-prefix
+    interval: Duration,            // Between car and session updates, from --mqtt-rate
+    outbox: Outbox<Vec<u8>>,       // Batches for the Link
+    updatedAt: Option<Instant>,    // Last car and session update
+    sent: HashMap<String, String>, // Last payload of each topic, cleared when the connection drops
+    pending: Vec<u8>,              // PUBLISH packets waiting for the next flush
+}
+
+impl Mqtt {
+    pub fn new(addr: &str, prefix: &str, rate: f32) -> Self {
+        let prefix = prefix.trim_end_matches('/').to_string();
+        let outbox = Link::spawn(addr, &prefix);
+        Self {
+            prefix,
+            interval: Duration::from_secs_f32(1.0 / rate.max(0.1)),
+            outbox,
+            updatedAt: None,
+            sent: HashMap::new(),
+            pending: vec![],
+        }
+    }
+
+    // Queue `payload` on prefix/topic, unless that's what the topic already holds.
+    fn publish(&mut self, topic: &str, payload: String, retain: bool) {
+        let topic = format!("{}/{topic}", self.prefix);
+        if self.sent.get(&topic) == Some(&payload) {
+            return;
+        }
+        self.pending.extend(publish(&topic, &payload, retain));
+        self.sent.insert(topic, payload);
+    }
+
+    // Session metadata and every car, no more than --mqtt-rate times a second.
+    pub fn update(&mut self, page: &Page, metrics: &Metrics) {
+        if self.outbox.isRetrying()
+            || self
+                .updatedAt
+                .is_some_and(|at| at.elapsed() < self.interval)
+        {
+            return;
+        }
+        self.updatedAt = Some(Instant::now());
+
+        // Retained, so a device that connects mid session still knows where it is.
+        // Nothing until the first PacketSession, a retained default would outlive it.
+        if page.sessionUID != 0 {
+            let session = [
+                ("uid", page.sessionUID.to_string()),
+                ("type", format!("{:?}", page.session)),
+                ("track", format!("{:?}", page.trackId)),
+                ("lap", page.lap.leader.to_string()),
+                ("totallaps", page.lap.total.to_string()),
+                ("safetycar", format!("{:?}", page.raceControl.safetyCar)),
+                ("weather", format!("{:?}", page.forecast.weather)),
+                (
+                    "tracktemperature",
+                    page.forecast.trackTemperature.to_string(),
+                ),
+                ("airtemperature", page.forecast.airTemperature.to_string()),
+            ];
+            for (name, payload) in session {
+                self.publish(&format!("session/{name}"), payload, true);
+            }
+        }
+
+        for (idx, car) in metrics.active() {
+            self.publish(&format!("car/{idx}/driver"), car.driver.clone(), true);
+            self.publish(&format!("car/{idx}/team"), car.team.clone(), true);
+
+            let timing = &page.car[idx];
+            self.publish(
+                &format!("car/{idx}/position"),
+                timing.spotRace.to_string(),
+                false,
+            );
+            self.publish(&format!("car/{idx}/lap"), timing.lapNum.to_string(), false);
+            self.publish(
+                &format!("car/{idx}/tyre"),
+                format!("{:?}", timing.tyres.visual),
+                false,
+            );
+            self.publish(
+                &format!("car/{idx}/drs"),
+                timing.Drs.isOpen.to_string(),
+                false,
+            );
+            self.publish(
+                &format!("car/{idx}/pitting"),
+                timing.pitLane.is_some().to_string(),
+                false,
+            );
+
+            for (name, _, value) in &SCALARS {
+                self.publish(&format!("car/{idx}/{name}"), value(car).to_string(), false);
+            }
+            for (name, _, values) in &PER_WHEEL {
+                for (wheel, value) in WHEELS.iter().zip(values(car)) {
+                    self.publish(
+                        &format!("car/{idx}/{name}/{wheel}"),
+                        value.to_string(),
+                        false,
+                    );
+                }
+            }
+        }
+    }
+
+    // Each event as it happens, e.g. pitwall/events/penalty, with the event as JSON.
+    pub fn event(&mut self, e: &PacketEvent) {
+        if self.outbox.isRetrying() || matches!(e.eventType(), EventType::Buttons) {
+            return;
+        }
+        let code = format!("{:?}", e.eventType()).to_lowercase();
+        let topic = format!("{}/events/{code}", self.prefix);
+        self.pending.extend(publish(&topic, &e.to_json(), false));
+    }
+
+    // Hand what's queued to the sender, unless it's still busy with the last lot.
+    // Only the first failure is returned, until the broker takes our packets again.
+    pub fn flush(&mut self) -> io::Result<()> {
+        let result = self.outbox.results(|| {
+            // Everything goes again once we're back, retained topics included,
+            // and nothing queued from before goes ahead of it.
+            self.sent.clear();
+            self.pending.clear();
+        });
+
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            if let Err(pending) = self.outbox.push(pending) {
+                self.pending = pending;
+            }
+        }
+
+        result
+    }
+}
+
+// The connection to the broker, on a thread of its own so connecting never holds up the packets.
+struct Link {
+    addr: String,
+    status: String, // prefix/status, the will
+    stream: Option<TcpStream>,
+}
+
+impl Link {
+    fn spawn(addr: &str, prefix: &str) -> Outbox<Vec<u8>> {
+        let mut link = Link {
+            addr: addr.to_string(),
+            status: format!("{prefix}/status"),
+            stream: None,
+        };
+        Outbox::spawn(move |batch: &Vec<u8>| link.send(batch))
+    }
+
+    // Connecting first if need be.
+    fn send(&mut self, batch: &[u8]) -> io::Result<()> {
+        let result = match &mut self.stream {
+            Some(stream) => stream.write_all(batch),
+            None => self.connect().and_then(|mut stream| {
+                stream.write_all(batch)?;
+                self.stream = Some(stream);
+                Ok(())
+            }),
+        };
+        if result.is_err() {
+            self.stream = None;
+        }
+        result
+    }
+
+    // CONNECT, with prefix/status as the will, then wait for the CONNACK.
+    fn connect(&self) -> io::Result<TcpStream> {
+        let mut stream = metrics::connect(&self.addr)?;
+        stream.set_read_timeout(Some(Duration::from_secs(1)))?;
+
+        let mut body = vec![];
+        body.extend(string("MQTT"));
+        body.push(4); // MQTT 3.1.1
+        body.push(0x02 | 0x04 | 0x20); // Clean session, a will, and the will is retained
+        body.extend(0u16.to_be_bytes()); // No keep alive, we never read after the CONNACK
+        body.extend(string(&format!("pitwall-{}", process::id())));
+        body.extend(string(&self.status));
+        body.extend(string("offline"));
+        stream.write_all(&packet(0x10, &body))?;
+
+        let mut connack = [0; 4];
+        stream.read_exact(&mut connack)?;
+        if connack[0] != 0x20 || connack[3] != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                format!(
+                    "{} refused the connection, return code {}",
+                    self.addr, connack[3]
+                ),
+            ));
+        }
+
+        stream.write_all(&publish(&self.status, "online", true))?;
+        Ok(stream)
+    }
+}
+
+// A UTF-8 string, length first.
+fn string(value: &str) -> Vec<u8> {
+    let mut bytes = (value.len() as u16).to_be_bytes().to_vec();
+    bytes.extend(value.as_bytes());
+    bytes
+}
+
+// Fixed header, with the remaining length seven bits at a time, then the body.
+fn packet(kind: u8, body: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![kind];
+    let mut length = body.len();
+    loop {
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        bytes.push(byte);
+        if length == 0 {
+            break;
+        }
+    }
+    bytes.extend(body);
+    bytes
+}
+
+fn publish(topic: &str, payload: &str, retain: bool) -> Vec<u8> {
+    let mut body = string(topic);
+    body.extend(payload.as_bytes());
+    packet(0x30 | retain as u8, &body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_length_first() {
+        assert_eq!(string("MQTT"), b"\x00\x04MQTT");
+        assert_eq!(string(""), [0, 0]);
+        assert_eq!(string(&"x".repeat(300))[..2], [0x01, 0x2c]);
+    }
+
+    #[test]
+    fn packet_remaining_length() {
+        let length = |len: usize| {
+            let bytes = packet(0x30, &vec![0; len]);
+            assert_eq!(bytes[0], 0x30);
+            assert!(bytes.ends_with(&vec![0; len]));
+            bytes[1..bytes.len() - len].to_vec()
+        };
+
+        assert_eq!(length(0), [0x00]);
+        assert_eq!(length(127), [0x7f]);
+        assert_eq!(length(128), [0x80, 0x01]);
+        assert_eq!(length(16_383), [0xff, 0x7f]);
+        assert_eq!(length(16_384), [0x80, 0x80, 0x01]);
+        assert_eq!(length(2_097_152), [0x80, 0x80, 0x80, 0x01]);
+    }
+
+    #[test]
+    fn publish_bytes() {
+        assert_eq!(
+            publish("pitwall/car/3/gear", "7", false),
+            b"\x30\x15\x00\x12pitwall/car/3/gear7"
+        );
+        assert_eq!(
+            publish("pitwall/status", "online", true),
+            b"\x31\x16\x00\x0epitwall/statusonline"
+        );
+    }
+}
@@ -3,6 +3,16 @@
 use colored::Colorize;
 use std::mem::size_of;
 use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod appendix;
+mod json;
+mod pack;
+#[cfg(feature = "serde")]
+mod serialize;
+pub use appendix::*;
+pub use json::{Object, ToJson};
 
 // https://answers.ea.com/t5/General-Discussion/F1-22-UDP-Specification/td-p/11551274
 // https://forums.ea.com/blog/f1-games-game-info-hub-en/ea-sports%e2%84%a2-f1%c2%ae25-udp-specification/12187347
@@ -20,6 +30,7 @@ pub static MAX_NUM_TYRE_SETS: usize = 13 + 7; // 13 slick and 7 wet weather
 
 #[repr(C, packed)] // Size: 29 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Header
 {
     pub packetFormat: u16,              // 2025
@@ -70,6 +81,7 @@ impl Header
 /// The packets IDs are as follows
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PacketId {
     Motion = 0,                         // Contains all motion data for player’s car – only sent while player is in control
     Session = 1,                        // Data about the session – track, time left
@@ -119,10 +131,11 @@ impl PacketId {
 /// The motion packet gives physics data for all the cars being driven. There is additional data for the car being driven with the goal of being able to drive a motion platform setup.
 /// N.B. For the normalised vectors below, to convert to float values divide by 32767.0f – 16-bit signed values are used to pack the data and on the assumption that direction values are always between -1.0f and 1.0f.
 /// Frequency: Rate as specified in menus
-/// Size: 1464 bytes
+/// Size: 1349 bytes
 /// Version: 1
 #[repr(C, packed)] // Size: 60 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CarMotion
 {
     pub worldPosition: Vector,          // World space position
@@ -152,8 +165,9 @@ impl CarMotion
     }
 }
 
-#[repr(C, packed)] // Size: 16 Bytes
+#[repr(C, packed)] // Size: 12 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Vector
 {
     pub X: f32,
@@ -175,6 +189,7 @@ impl Vector
 
 #[repr(C, packed)] // Size: 6 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Direction
 {
     pub X: i16,
@@ -194,8 +209,9 @@ impl Direction
     }
 }
 
-#[repr(C, packed)] // Size: 16 Bytes
+#[repr(C, packed)] // Size: 12 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Forces
 {
     pub Lateral     : f32,
@@ -217,6 +233,7 @@ impl Forces
 
 #[repr(C, packed)] // Size: 16 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Wheels
 {
     pub RL: f32,
@@ -238,27 +255,18 @@ impl Wheels
     }
 }
 
-#[repr(C, packed)] // Size: 1464 Bytes
+#[repr(C, packed)] // Size: 1349 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketMotion
 {
-    pub header: Header,                 // 24 Bytes - Header
+    pub header: Header,                 // 29 Bytes - Header
 
     pub carMotion: [CarMotion; 22],     // 60 Bytes * 22 - Data for all cars on track
-
-    // Extra player car ONLY data
-    pub suspensionPosition: Wheels,     // Note: All wheel arrays have the following order:
-    pub suspensionVelocity: Wheels,     // RL, RR, FL, FR
-    pub suspensionAcceleration: Wheels, // RL, RR, FL, FR
-    pub wheelSpeed: Wheels,             // Speed of each wheel
-    pub wheelSlip: Wheels,              // Slip ratio for each wheel
-    pub localVelocity: Vector,          // Velocity in local space
-    pub angularVelocity: Vector,        // Angular velocity
-    pub angularAcceleration: Vector,    // Angular acceleration
-    pub frontWheelsAngle: f32,          // Current front wheels angle in radians
+                                        // The extra player car data now lives in the Motion Ex packet
 }
 
-// Size: 1464 Bytes
+// Size: 1349 Bytes
 impl PacketMotion
 {
     pub fn unpack(bytes: &[u8]) -> Self
@@ -267,17 +275,6 @@ impl PacketMotion
             header: Header::unpack(bytes),
 
             carMotion: Self::carMotion(&bytes[size_of::<Header>()..size_of::<Header>()+(size_of::<CarMotion>()*22)]),
-
-            // Extra player car ONLY data
-            suspensionPosition    : Wheels::unpack(&bytes[1349..1365]),
-            suspensionVelocity    : Wheels::unpack(&bytes[1365..1381]),
-            suspensionAcceleration: Wheels::unpack(&bytes[1381..1397]),
-            wheelSpeed            : Wheels::unpack(&bytes[1397..1413]),
-            wheelSlip             : Wheels::unpack(&bytes[1413..1429]),
-            localVelocity         : Vector::unpack(&bytes[1429..1441]),
-            angularVelocity       : Vector::unpack(&bytes[1441..1453]),
-            angularAcceleration   : Vector::unpack(&bytes[1453..1465]),
-            frontWheelsAngle      : f32::from_le_bytes([bytes[1465], bytes[1466], bytes[1467], bytes[1468]]),
         }
     }
 
@@ -303,11 +300,12 @@ impl PacketMotion
  * # Session Packet
  * The session packet includes details about the current session in progress.
  * Frequency: 2 per second
- * Size: 632 bytes
+ * Size: 753 bytes
  * Version: 1
  */
 #[repr(C, packed)] // Size: 5 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MarshalZone
 {
     pub zoneStart: f32,                 // Fraction (0..1) of way through the lap the marshal zone starts
@@ -327,6 +325,7 @@ impl MarshalZone
 
 #[repr(i8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ZoneFlag {
     Invalid = -1,
     None = 0,
@@ -357,6 +356,7 @@ impl ZoneFlag
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Session {
     Unknown = 0,
     Practice1 = 1,
@@ -425,6 +425,7 @@ impl fmt::Display for Session {
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Weather {
     Clear = 0,
     LightCloud = 1,
@@ -453,8 +454,24 @@ impl Weather
     }
 }
 
+impl fmt::Display for Weather {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self
+        {
+            Self::Clear      => write!(f, "Clear"),
+            Self::LightCloud => write!(f, "Light Cloud"),
+            Self::Overcast   => write!(f, "Overcast"),
+            Self::RainLight  => write!(f, "Light Rain"),
+            Self::RainHeavy  => write!(f, "Heavy Rain"),
+            Self::RainStorm  => write!(f, "Storm"),
+            _                => write!(f, "???"),
+        }
+    }
+}
+
 #[repr(i8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Temperature {
     Up = 0,
     Down = 1,
@@ -477,8 +494,21 @@ impl Temperature
     }
 }
 
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self
+        {
+            Self::Up   => write!(f, "↑"),
+            Self::Down => write!(f, "↓"),
+            Self::None => write!(f, "-"),
+            _          => write!(f, "?"),
+        }
+    }
+}
+
 #[repr(C, packed)] // Size: 8 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WeatherForecast
 {
     pub sessionType: Session,           // u8
@@ -510,6 +540,7 @@ impl WeatherForecast
 
 #[repr(C, packed)] // Size: 753 Bytes
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketSession
 {
     pub header: Header,                         // 29 Bytes - Header
@@ -520,7 +551,7 @@ pub struct PacketSession
     pub totalLaps: u8,                          // Total number of laps in this race
     pub trackLength: u16,                       // Track length in metres
     pub sessionType: Session,                   // u8
-    pub trackId: i8,                            // -1 for unknown, see appendix
+    pub trackId: TrackId,                       // i8 -1 for unknown, see appendix
     pub formula: Formula,                       // u8
     pub sessionTimeLeft: SessionTime,           // Time left in session in seconds
     pub sessionDuration: SessionTime,           // Session duration in seconds
@@ -534,6 +565,7 @@ pub struct PacketSession
     pub safetyCarStatus: SafetyCar,             // u8
     pub networkGame: NetworkGame,               // u8
     pub numWeatherForecasts: u8,                // Number of weather samples to follow
+    #[cfg_attr(feature = "serde", serde(with = "serialize::array"))]
     pub weatherForecast: [WeatherForecast; 64], // 512 Bytes - of weather forecast samples
     pub forecastAccuracy: Accuracy,             // u8
     pub aiDifficulty: u8,                       // AI Difficulty rating – 0-110
@@ -552,8 +584,8 @@ pub struct PacketSession
     pub DRSAssist: Assist,                      // u8
     pub dynamicRacingLine: u8,                  // 0 = off, 1 = corners only, 2 = full
     pub dynamicRacingLineType: u8,              // 0 = 2D, 1 = 3D
-    pub gameMode: u8,                           // Game mode id - see appendix
-    pub ruleSet: u8,                            // Ruleset - see appendix
+    pub gameMode: GameMode,                     // u8 Game mode id - see appendix
+    pub ruleSet: RuleSet,                       // u8 Ruleset - see appendix
     pub timeOfDay: u32,                         // Local time of day - minutes since midnight
     pub sessionLength: SessionLength,           // u8
     pub speedUnitsLeadPlayer: u8,               // 0 = MPH, 1 = KPH
@@ -588,7 +620,7 @@ pub struct PacketSession
     pub affectsLicenceLevelSolo: u8,            // 0 = Off, 1 = On
     pub affectsLicenceLevelMP: u8,              // 0 = Off, 1 = On
     pub numSessionsInWeekend: u8,               // Number of session in following array
-    pub weekendStructure: [u8; 12],             // List of session types to show weekend structure - see appendix for types
+    pub weekendStructure: [Session; 12],        // List of session types to show weekend structure - see appendix for types
     pub sector2LapDistanceStart: f32,           // Distance in m around track where sector 2 starts
     pub sector3LapDistanceStart: f32,           // Distance in m around track where sector 3 starts
 }
@@ -606,7 +638,7 @@ impl PacketSession
             totalLaps                       : bytes[32],
             trackLength                     : u16::from_le_bytes([bytes[33], bytes[34]]),
             sessionType                     : Session::from_u8(&bytes[35]),
-            trackId                         : bytes[36] as i8,
+            trackId                         : TrackId::from_u8_to_i8(&bytes[36]),
             formula                         : Formula::from_u8(&bytes[37]),
             sessionTimeLeft                 : SessionTime::unpack(&[bytes[38], bytes[39]]),
             sessionDuration                 : SessionTime::unpack(&[bytes[40], bytes[41]]),
@@ -638,8 +670,8 @@ impl PacketSession
             DRSAssist                       : Assist::from_u8(&bytes[691]),
             dynamicRacingLine               : bytes[692],
             dynamicRacingLineType           : bytes[693],
-            gameMode                        : bytes[694],
-            ruleSet                         : bytes[695],
+            gameMode                        : GameMode::from_u8(&bytes[694]),
+            ruleSet                         : RuleSet::from_u8(&bytes[695]),
             timeOfDay                       : u32::from_le_bytes([bytes[696], bytes[697], bytes[698], bytes[699]]),
             sessionLength                   : SessionLength::from_u8(&bytes[700]),
             speedUnitsLeadPlayer            : bytes[701],
@@ -674,7 +706,7 @@ impl PacketSession
             affectsLicenceLevelSolo         : bytes[730],
             affectsLicenceLevelMP           : bytes[731],
             numSessionsInWeekend            : bytes[732],
-            weekendStructure                : [bytes[733], bytes[734], bytes[735], bytes[736], bytes[737], bytes[738], bytes[739], bytes[740], bytes[741], bytes[742], bytes[743], bytes[744]],
+            weekendStructure                : std::array::from_fn(|i| Session::from_u8(&bytes[733 + i])),
             sector2LapDistanceStart         : f32::from_le_bytes([bytes[745], bytes[746], bytes[747], bytes[748]]),
             sector3LapDistanceStart         : f32::from_le_bytes([bytes[749], bytes[750], bytes[751], bytes[752]]),
         }
@@ -718,6 +750,7 @@ impl PacketSession
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SessionTime {
     pub time: u16,  // session in seconds
 }
@@ -742,6 +775,7 @@ impl fmt::Display for SessionTime {
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Formula {
     Modern = 0,
     Classic = 1,
@@ -776,6 +810,7 @@ impl Formula
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SLIPro {
     Inactive = 0,
     Active = 1,
@@ -798,6 +833,7 @@ impl SLIPro
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum NetworkGame {
     Offline = 0,
     Online = 1,
@@ -820,6 +856,7 @@ impl NetworkGame
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Accuracy {
     Perfect = 0,
     Approximate = 1,
@@ -841,6 +878,7 @@ impl Accuracy {
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Assist {
     Off = 0,
     On = 1,
@@ -863,6 +901,7 @@ impl Assist
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SafetyCar {
     Ready = 0,
     Deployed = 1,
@@ -887,8 +926,22 @@ impl SafetyCar
     }
 }
 
+impl fmt::Display for SafetyCar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self
+        {
+            Self::Ready        => write!(f, "Green"),
+            Self::Deployed     => write!(f, "Safety Car"),
+            Self::Virtual      => write!(f, "Virtual Safety Car"),
+            Self::FormationLap => write!(f, "Formation Lap"),
+            _                  => write!(f, "???"),
+        }
+    }
+}
+
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum SessionLength {
     None = 0,
     VeryShort = 2,
@@ -925,8 +978,9 @@ impl SessionLength
 /// Frequency: Rate as specified in menus
 /// Size: 1285 bytes
 /// Version: 1
-#[repr(C, packed)] // Size: 56 Bytes
+#[repr(C, packed)] // Size: 57 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Car
 {
     pub lastLapTimeInMS: u32,             // Last lap time in milliseconds
@@ -1008,6 +1062,7 @@ impl Car
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PitStatus {
     None = 0,
     Pitting = 1,
@@ -1032,6 +1087,7 @@ impl PitStatus
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum CarState {
     InGarage = 0,
     OnFlyingLap = 1,
@@ -1074,6 +1130,7 @@ impl fmt::Display for CarState
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ResultStatus {
     #[default]
     Invalid = 0,
@@ -1104,11 +1161,12 @@ impl ResultStatus {
     }
 }
 
-#[repr(C, packed)]
+#[repr(C, packed)] // Size: 1285 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketLap
 {
-    pub header: Header,                 // 24 Bytes - Header
+    pub header: Header,                 // 29 Bytes - Header
 
     pub cars: [Car; 22],                // Lap data for all cars on track
 
@@ -1128,8 +1186,8 @@ impl PacketLap
 
             cars: Self::lap(&bytes[sz_hdr .. sz_hdr + (sz_lap * 22)]),
 
-            timeTrialPBCarIdx: bytes[sz_hdr + (sz_lap * 22) + 1],
-            timeTrialRivalCarIdx: bytes[sz_hdr + (sz_lap * 22) + 2],
+            timeTrialPBCarIdx: bytes[sz_hdr + (sz_lap * 22)],
+            timeTrialRivalCarIdx: bytes[sz_hdr + (sz_lap * 22) + 1],
         }
     }
 
@@ -1154,11 +1212,11 @@ impl PacketLap
 /// # Event Packet
 /// This packet gives details of events that happen during the course of a session.
 /// Frequency: When the event occurs
-/// Size: 40 bytes
+/// Size: 45 bytes
 /// Version: 1
 /// The event details packet is different for each type of event.
 /// Make sure only the correct type is interpreted.
-#[repr(C, packed)]
+#[repr(C, packed)] // Size: 12 Bytes
 #[derive(Clone, Copy)]
 pub union EventDetails
 {
@@ -1179,11 +1237,16 @@ pub union EventDetails
     pub stopGoPenaltyServed: StopGoPenaltyServed,
     pub flashback: Flashback,
     pub buttons: Buttons,
+    pub redFlag: RedFlag,
+    pub overtake: Overtake,
+    pub safetyCar: SafetyCarEvent,
+    pub collision: Collision,
     pub unknownTag: [u8; 4]
 }
 
 #[repr(C, packed)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SessionStarted {}
 impl SessionStarted
 {
@@ -1195,6 +1258,7 @@ impl SessionStarted
 
 #[repr(C, packed)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SessionEnded {}
 impl SessionEnded
 {
@@ -1206,6 +1270,7 @@ impl SessionEnded
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FastestLap
 {
     pub vehicleIdx: u8,                 // Vehicle index of car achieving fastest lap
@@ -1225,9 +1290,12 @@ impl FastestLap
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Retirement
 {
     pub vehicleIdx: u8,                 // Vehicle index of car retiring
+    pub reason: u8,                     // 0 = invalid, 1 = retired, 2 = finished, 3 = terminal damage, 4 = inactive, 5 = not enough laps completed,
+                                        // 6 = black flagged, 7 = red flagged, 8 = mechanical failure, 9 = session skipped, 10 = session simulated
 }
 
 impl Retirement
@@ -1236,6 +1304,7 @@ impl Retirement
     {
         Self {
             vehicleIdx: bytes[0],
+            reason: bytes[1],
         }
     }
 }
@@ -1243,6 +1312,7 @@ impl Retirement
 
 #[repr(C, packed)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DRSEnabled {}
 impl DRSEnabled
 {
@@ -1253,18 +1323,26 @@ impl DRSEnabled
 }
 
 #[repr(C, packed)]
-#[derive(Clone, Copy)]
-pub struct DRSDisabled {}
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DRSDisabled
+{
+    pub reason: u8,                     // 0 = Wet track, 1 = Safety car deployed, 2 = Red flag, 3 = Min lap not reached
+}
+
 impl DRSDisabled
 {
-    pub fn unpack() -> Self
+    pub fn unpack(bytes: &[u8]) -> Self
     {
-        Self {}
+        Self {
+            reason: bytes[0],
+        }
     }
 }
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TeamMateInPits
 {
     pub vehicleIdx: u8,                 // Vehicle index of team mate
@@ -1282,6 +1360,7 @@ impl TeamMateInPits
 
 #[repr(C, packed)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ChequeredFlag {}
 impl ChequeredFlag
 {
@@ -1293,6 +1372,7 @@ impl ChequeredFlag
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RaceWinner
 {
     pub vehicleIdx: u8,                 // Vehicle index of the race winner
@@ -1310,10 +1390,11 @@ impl RaceWinner
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Penalty
 {
-    pub penaltyType: u8,                // Penalty type – see Appendices
-    pub infringementType: u8,           // Infringement type – see Appendices
+    pub penaltyType: PenaltyType,       // u8 Penalty type – see Appendices
+    pub infringementType: InfringementType, // u8 Infringement type – see Appendices
     pub vehicleIdx: u8,                 // Vehicle index of the car the penalty is applied to
     pub otherVehicleIdx: u8,            // Vehicle index of the other car involved
     pub time: u8,                       // Time gained, or time spent doing action in seconds
@@ -1326,8 +1407,8 @@ impl Penalty
     pub fn unpack(bytes: &[u8]) -> Self
     {
         Self {
-            penaltyType: bytes[0],
-            infringementType: bytes[1],
+            penaltyType: PenaltyType::from_u8(&bytes[0]),
+            infringementType: InfringementType::from_u8(&bytes[1]),
             vehicleIdx: bytes[2],
             otherVehicleIdx: bytes[3],
             time: bytes[4],
@@ -1339,6 +1420,7 @@ impl Penalty
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SpeedTrap
 {
     pub vehicleIdx: u8,                 // Vehicle index of the vehicle triggering speed trap
@@ -1366,6 +1448,7 @@ impl SpeedTrap
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StartLights
 {
     pub numLights: u8,                  // Number of lights showing
@@ -1383,6 +1466,7 @@ impl StartLights
 
 #[repr(C, packed)]
 #[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LightsOut {}
 impl LightsOut
 {
@@ -1394,6 +1478,7 @@ impl LightsOut
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DriveThroughPenaltyServed
 {
     pub vehicleIdx: u8,                 // Vehicle index of the vehicle serving drive through
@@ -1412,9 +1497,11 @@ impl DriveThroughPenaltyServed
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct StopGoPenaltyServed
 {
     pub vehicleIdx: u8,                 // Vehicle index of the vehicle serving stop go
+    pub stopTime: f32,                  // Time spent serving stop go in seconds
 }
 
 impl StopGoPenaltyServed
@@ -1423,12 +1510,14 @@ impl StopGoPenaltyServed
     {
         Self {
             vehicleIdx: bytes[0],
+            stopTime  : f32::from_le_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]),
         }
     }
 }
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Flashback
 {
     pub flashbackFrameIdentifier: u32,  // Frame identifier flashed back to
@@ -1448,6 +1537,7 @@ impl Flashback
 
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Buttons
 {
     pub buttonStatus: u32,              // Bit flags specifying which buttons are being pressed currently - see appendices
@@ -1463,10 +1553,85 @@ impl Buttons
     }
 }
 
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RedFlag {}
+impl RedFlag
+{
+    pub fn unpack() -> Self
+    {
+        Self {}
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Overtake
+{
+    pub overtakingVehicleIdx: u8,       // Vehicle index of the vehicle overtaking
+    pub beingOvertakenVehicleIdx: u8,   // Vehicle index of the vehicle being overtaken
+}
+
+impl Overtake
+{
+    pub fn unpack(bytes: &[u8]) -> Self
+    {
+        Self {
+            overtakingVehicleIdx    : bytes[0],
+            beingOvertakenVehicleIdx: bytes[1],
+        }
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SafetyCarEvent
+{
+    pub safetyCarType: SafetyCar,       // u8 - 0 = No Safety Car, 1 = Full Safety Car, 2 = Virtual Safety Car, 3 = Formation Lap Safety Car
+    pub eventType: u8,                  // 0 = Deployed, 1 = Returning, 2 = Returned, 3 = Resume Race
+}
+
+impl SafetyCarEvent
+{
+    pub fn unpack(bytes: &[u8]) -> Self
+    {
+        Self {
+            safetyCarType: SafetyCar::from_u8(&bytes[0]),
+            eventType    : bytes[1],
+        }
+    }
+}
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Collision
+{
+    pub vehicle1Idx: u8,                // Vehicle index of the first vehicle involved in the collision
+    pub vehicle2Idx: u8,                // Vehicle index of the second vehicle involved in the collision
+}
+
+impl Collision
+{
+    pub fn unpack(bytes: &[u8]) -> Self
+    {
+        Self {
+            vehicle1Idx: bytes[0],
+            vehicle2Idx: bytes[1],
+        }
+    }
+}
+
 #[repr(C, packed)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct EventTag
 {
+    #[cfg_attr(feature = "serde", serde(with = "serialize::name"))]
     pub tag: [u8; 4]
 }
 
@@ -1480,15 +1645,14 @@ impl EventTag
     }
 }
 
-#[repr(C, packed)] // Size: 24 + 8 + (Depends) Bytes
+#[repr(C, packed)] // Size: 45 Bytes
 #[derive(Clone, Copy)]
 pub struct PacketEvent
 {
-    pub header: Header,                 // 24 Bytes - Header
+    pub header: Header,                 // 29 Bytes - Header
 
     pub eventStringCode: EventTag,      // u8 * 4 - Event string code, see below
-    pub eventType: EventType,           // Tag for the Event Type.
-    pub eventDetails: EventDetails,     // Depends - Event details - should be interpreted differently for each type
+    pub eventDetails: EventDetails,     // 12 Bytes - Event details - should be interpreted differently for each type
 }
 
 impl fmt::Debug for PacketEvent
@@ -1506,32 +1670,12 @@ impl PacketEvent
 {
     pub fn unpack(bytes: &[u8]) -> Self
     {
-        let eventTag: EventTag = EventTag::unpack(&bytes[29..34]);
+        let eventTag: EventTag = EventTag::unpack(&bytes[29..33]);
 
         Self {
             header: Header::unpack(bytes),
 
             eventStringCode: eventTag,
-            eventType: match &eventTag.tag {
-                b"SSTA" => EventType::SessionStarted,
-                b"SEND" => EventType::SessionEnded,
-                b"FTLP" => EventType::FastestLap,
-                b"RTMT" => EventType::Retirement,
-                b"DRSE" => EventType::DRSEnabled,
-                b"DRSD" => EventType::DRSDisabled,
-                b"TMPT" => EventType::TeamMateInPits,
-                b"CHQF" => EventType::ChequeredFlag,
-                b"RCWN" => EventType::RaceWinner,
-                b"PENA" => EventType::Penalty,
-                b"SPTP" => EventType::SpeedTrap,
-                b"STLG" => EventType::StartLights,
-                b"LGOT" => EventType::LightsOut,
-                b"DTSV" => EventType::DriveThroughPenaltyServed,
-                b"SGSV" => EventType::StopGoPenaltyServed,
-                b"FLBK" => EventType::Flashback,
-                b"BUTN" => EventType::Buttons,
-                _ => EventType::Poisoned,
-            },
             eventDetails: match &eventTag.tag {
                 b"SSTA" => EventDetails {
                         // Sent when the session starts
@@ -1543,11 +1687,11 @@ impl PacketEvent
                     },
                 b"FTLP" => EventDetails {
                         // When a driver achieves the fastest lap
-                        fastestLap: FastestLap::unpack(&bytes[28..])
+                        fastestLap: FastestLap::unpack(&bytes[33..])
                     },
                 b"RTMT" => EventDetails {
                         // When a driver retires
-                        retirement: Retirement::unpack(&bytes[28..])
+                        retirement: Retirement::unpack(&bytes[33..])
                     },
                 b"DRSE" => EventDetails {
                         // Race control have enabled DRS
@@ -1555,11 +1699,11 @@ impl PacketEvent
                     },
                 b"DRSD" => EventDetails {
                         // Race control have disabled DRS
-                        drsDisabled: DRSDisabled::unpack(),
+                        drsDisabled: DRSDisabled::unpack(&bytes[33..])
                     },
                 b"TMPT" => EventDetails {
                         // Your team mate has entered the pits
-                        teamMateInPits: TeamMateInPits::unpack(&bytes[28..])
+                        teamMateInPits: TeamMateInPits::unpack(&bytes[33..])
                     },
                 b"CHQF" => EventDetails {
                         // The chequered flag has been waved
@@ -1567,19 +1711,19 @@ impl PacketEvent
                     },
                 b"RCWN" => EventDetails {
                         // The race winner is announced
-                        raceWinner: RaceWinner::unpack(&bytes[28..])
+                        raceWinner: RaceWinner::unpack(&bytes[33..])
                     },
                 b"PENA" => EventDetails {
                         // A penalty has been issued – details in event
-                        penalty: Penalty::unpack(&bytes[28..])
+                        penalty: Penalty::unpack(&bytes[33..])
                     },
                 b"SPTP" => EventDetails {
                         // Speed trap has been triggered by fastest speed
-                        speedTrap: SpeedTrap::unpack(&bytes[28..])
+                        speedTrap: SpeedTrap::unpack(&bytes[33..])
                     },
                 b"STLG" => EventDetails {
                         // Start lights – number shown
-                        startLights: StartLights::unpack(&bytes[28..])
+                        startLights: StartLights::unpack(&bytes[33..])
                     },
                 b"LGOT" => EventDetails {
                         // Lights out
@@ -1587,22 +1731,38 @@ impl PacketEvent
                     },
                 b"DTSV" => EventDetails {
                         // Drive through penalty served
-                        driveThroughPenaltyServed: DriveThroughPenaltyServed::unpack(&bytes[28..])
+                        driveThroughPenaltyServed: DriveThroughPenaltyServed::unpack(&bytes[33..])
                     },
                 b"SGSV" => EventDetails {
                         // Stop go penalty served
-                        stopGoPenaltyServed: StopGoPenaltyServed::unpack(&bytes[28..])
+                        stopGoPenaltyServed: StopGoPenaltyServed::unpack(&bytes[33..])
                     },
                 b"FLBK" => EventDetails {
                         // Flashback activated
-                        flashback: Flashback::unpack(&bytes[28..])
+                        flashback: Flashback::unpack(&bytes[33..])
                     },
                 b"BUTN" => EventDetails {
                         // Button status changed
-                        buttons: Buttons::unpack(&bytes[28..])
+                        buttons: Buttons::unpack(&bytes[33..])
+                    },
+                b"RDFL" => EventDetails {
+                        // Red flag shown
+                        redFlag: RedFlag::unpack(),
+                    },
+                b"OVTK" => EventDetails {
+                        // Overtake occurred
+                        overtake: Overtake::unpack(&bytes[33..])
+                    },
+                b"SCAR" => EventDetails {
+                        // Safety car event
+                        safetyCar: SafetyCarEvent::unpack(&bytes[33..])
+                    },
+                b"COLL" => EventDetails {
+                        // Collision between two vehicles has occurred
+                        collision: Collision::unpack(&bytes[33..])
                     },
                 _ => {
-                    println!("Unhandled Event: {:?}", eventTag);
+                    eprintln!("Unhandled Event: {:?}", eventTag);
                     EventDetails {
                         unknownTag: eventTag.tag
                     }
@@ -1610,9 +1770,39 @@ impl PacketEvent
             }
         }
     }
+
+    /// Which member of eventDetails is live, going by the event string code.
+    pub fn eventType(&self) -> EventType
+    {
+        match &self.eventStringCode.tag {
+            b"SSTA" => EventType::SessionStarted,
+            b"SEND" => EventType::SessionEnded,
+            b"FTLP" => EventType::FastestLap,
+            b"RTMT" => EventType::Retirement,
+            b"DRSE" => EventType::DRSEnabled,
+            b"DRSD" => EventType::DRSDisabled,
+            b"TMPT" => EventType::TeamMateInPits,
+            b"CHQF" => EventType::ChequeredFlag,
+            b"RCWN" => EventType::RaceWinner,
+            b"PENA" => EventType::Penalty,
+            b"SPTP" => EventType::SpeedTrap,
+            b"STLG" => EventType::StartLights,
+            b"LGOT" => EventType::LightsOut,
+            b"DTSV" => EventType::DriveThroughPenaltyServed,
+            b"SGSV" => EventType::StopGoPenaltyServed,
+            b"FLBK" => EventType::Flashback,
+            b"BUTN" => EventType::Buttons,
+            b"RDFL" => EventType::RedFlag,
+            b"OVTK" => EventType::Overtake,
+            b"SCAR" => EventType::SafetyCar,
+            b"COLL" => EventType::Collision,
+            _ => EventType::Poisoned,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[repr(u8)]
 pub enum EventType {
     SessionStarted,
@@ -1632,6 +1822,10 @@ pub enum EventType {
     StopGoPenaltyServed,
     Flashback,
     Buttons,
+    RedFlag,
+    Overtake,
+    SafetyCar,
+    Collision,
     #[default]
     Poisoned,
 }
@@ -1643,17 +1837,19 @@ pub enum EventType {
 /// Frequency: Every 5 seconds
 /// Size: 1284 bytes
 /// Version: 1
-#[repr(C, packed)] // Size: 56 Bytes
+#[repr(C, packed)] // Size: 57 Bytes
 #[derive(Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Participant
 {
     pub aiControlled: u8,                   // Whether the vehicle is AI (1) or Human (0) controlled
-    pub driverId: u8,                       // Driver id - see appendix, 255 if network human
+    pub driverId: DriverId,                 // u8 Driver id - see appendix, 255 if network human
     pub networkId: u8,                      // Network id – unique identifier for network players
-    pub teamId: u8,                         // Team id - see appendix
+    pub teamId: TeamId,                     // u8 Team id - see appendix
     pub myTeam: u8,                         // My team flag – 1 = My Team, 0 = otherwise
     pub raceNumber: u8,                     // Race number of the car
-    pub nationality: u8,                    // Nationality of the driver
+    pub nationality: Nationality,           // u8 Nationality of the driver
+    #[cfg_attr(feature = "serde", serde(with = "serialize::name"))]
     pub name: [u8; 32],                     // Name of participant in UTF-8 format – null terminated
                                             // Will be truncated with … (U+2026) if too long
     pub yourTelemetry: u8,                  // The player's UDP setting, 0 = restricted, 1 = public
@@ -1670,12 +1866,12 @@ impl Participant
     {
         Self {
             aiControlled: bytes[0],
-            driverId: bytes[1],
+            driverId: DriverId::from_u8(&bytes[1]),
             networkId: bytes[2],
-            teamId: bytes[3],
+            teamId: TeamId::from_u8(&bytes[3]),
             myTeam: bytes[4],
             raceNumber: bytes[5],
-            nationality: bytes[6],
+            nationality: Nationality::from_u8(&bytes[6]),
             name: match bytes[7..39].try_into()
                     {
                         Ok(str) => str,
@@ -1700,7 +1896,9 @@ impl Participant
 }
 
 /// RGB value of a colour
-#[derive(Clone, Copy, Default)]
+#[repr(C, packed)] // Size: 3 Bytes
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LiveryColour
 {
     pub r: u8,  // Red
@@ -1744,11 +1942,12 @@ impl fmt::Debug for Participant
     }
 }
 
-#[repr(C, packed)] // Size: 1257 Bytes
+#[repr(C, packed)] // Size: 1284 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketParticipants
 {
-    pub header: Header,                 // 24 Bytes - Header
+    pub header: Header,                 // 29 Bytes - Header
 
     pub numActiveCars: u8,              // Number of active cars in the data – should match number of cars on HUD
     pub participants: [Participant; 22],
@@ -1787,10 +1986,11 @@ impl PacketParticipants
 /// # Car Setups Packet
 /// This packet details the car setups for each vehicle in the session. Note that in multiplayer games, other player cars will appear as blank, you will only be able to see your car setup and AI cars.
 /// Frequency: 2 per second
-/// Size: 1102 bytes
+/// Size: 1133 bytes
 /// Version: 1
-#[repr(C, packed)] // Size: 49 Bytes
+#[repr(C, packed)] // Size: 50 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CarSetup
 {
     pub frontWing: u8,                  // Front wing aero
@@ -1809,6 +2009,7 @@ pub struct CarSetup
     pub rearSuspensionHeight: u8,       // Rear ride height
     pub brakePressure: u8,              // Brake pressure (percentage)
     pub brakeBias: u8,                  // Brake bias (percentage)
+    pub engineBraking: u8,              // Engine braking (percentage)
     pub tyrePressure: Wheels,           // 16 Bytes - Tyre pressures in PSI
     pub ballast: u8,                    // Ballast
     pub fuelLoad: f32,                  // Fuel load
@@ -1834,20 +2035,23 @@ impl CarSetup
             rearSuspensionHeight : bytes[25],
             brakePressure        : bytes[26],
             brakeBias            : bytes[27],
-            tyrePressure         : Wheels::unpack(&bytes[28..44]),
-            ballast              : bytes[44],
-            fuelLoad             : f32::from_le_bytes([bytes[45], bytes[46], bytes[47], bytes[48]]),
+            engineBraking        : bytes[28],
+            tyrePressure         : Wheels::unpack(&bytes[29..45]),
+            ballast              : bytes[45],
+            fuelLoad             : f32::from_le_bytes([bytes[46], bytes[47], bytes[48], bytes[49]]),
         }
     }
 }
 
-#[repr(C, packed)] // Size: 1102 Bytes
+#[repr(C, packed)] // Size: 1133 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketCarSetups
 {
-    pub header: Header,                 // 24 Bytes - Header
+    pub header: Header,                 // 29 Bytes - Header
 
     pub carSetups: [CarSetup; 22],
+    pub nextFrontWingValue: f32,        // Value of front wing after next pit stop - player only
 }
 
 impl PacketCarSetups
@@ -1858,7 +2062,8 @@ impl PacketCarSetups
         {
             header: Header::unpack(bytes),
 
-            carSetups: Self::carSetups(&bytes[size_of::<Header>()..])
+            carSetups: Self::carSetups(&bytes[size_of::<Header>()..]),
+            nextFrontWingValue: f32::from_le_bytes([bytes[1129], bytes[1130], bytes[1131], bytes[1132]]),
         }
     }
 
@@ -1886,6 +2091,7 @@ impl PacketCarSetups
 /// Version: 1
 #[repr(C)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Kph {
     pub kph: u16
 }
@@ -1917,6 +2123,7 @@ impl fmt::Display for Kph
 
 #[repr(i8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Gear
 {
     Reverse =-1,
@@ -1976,6 +2183,7 @@ impl fmt::Display for Gear
 
 #[repr(u16)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LEDs
 {
     #[default]
@@ -1999,6 +2207,7 @@ pub enum LEDs
 
 #[repr(C)] // Size: 2 Bytes
 #[derive(Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RevLights
 {
     pub LEDs: u16,
@@ -2072,6 +2281,7 @@ impl fmt::Debug for RevLights
 
 #[repr(C, packed)] // Size: 60 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CarTelemetry
 {
     pub speed: Kph,                         // Speed of car in kilometres per hour
@@ -2089,7 +2299,7 @@ pub struct CarTelemetry
     pub tyresInnerTemperature: [u8; 4],     // Tyres inner temperature (celsius)
     pub engineTemperature: u16,             // Engine temperature (celsius)
     pub tyresPressure: [f32; 4],            // Tyres pressure (PSI)
-    pub surfaceType: [u8; 4],               // Driving surface, see appendices
+    pub surfaceType: [Surface; 4],          // u8 Driving surface, see appendices
 }
 
 impl CarTelemetry
@@ -2119,16 +2329,22 @@ impl CarTelemetry
             tyresPressure          : [
                                      f32::from_le_bytes([bytes[40], bytes[41], bytes[42], bytes[43]]),
                                      f32::from_le_bytes([bytes[44], bytes[45], bytes[46], bytes[47]]),
-                                     f32::from_le_bytes([bytes[48], bytes[49], bytes[50], bytes[50]]),
+                                     f32::from_le_bytes([bytes[48], bytes[49], bytes[50], bytes[51]]),
                                      f32::from_le_bytes([bytes[52], bytes[53], bytes[54], bytes[55]]),
             ],
-            surfaceType            : [bytes[56], bytes[57], bytes[58], bytes[59]],
+            surfaceType            : [
+                                     Surface::from_u8(&bytes[56]),
+                                     Surface::from_u8(&bytes[57]),
+                                     Surface::from_u8(&bytes[58]),
+                                     Surface::from_u8(&bytes[59]),
+            ],
         }
     }
 }
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MFDPanel
 {
     Setup = 0,
@@ -2160,9 +2376,10 @@ impl MFDPanel
 
 #[repr(C, packed)] // Size: 1352 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketCarTelemetry
 {
-    pub header: Header,                     // 24 Bytes - Header
+    pub header: Header,                     // 29 Bytes - Header
 
     pub carTelemetry: [CarTelemetry; 22],   // 60 * 22 = 1320 Bytes
 
@@ -2180,9 +2397,9 @@ impl PacketCarTelemetry
 
             carTelemetry      : Self::carTelemetry(&bytes[29..1349]),
 
-            mfdFirstPlayer    : MFDPanel::from_u8(&bytes[1350]),
-            mfdSecondaryPlayer: MFDPanel::from_u8(&bytes[1351]),
-            suggestedGear     : Gear::from_u8_to_i8(&bytes[1352])
+            mfdFirstPlayer    : MFDPanel::from_u8(&bytes[1349]),
+            mfdSecondaryPlayer: MFDPanel::from_u8(&bytes[1350]),
+            suggestedGear     : Gear::from_u8_to_i8(&bytes[1351])
         }
     }
 
@@ -2206,10 +2423,11 @@ impl PacketCarTelemetry
 /// # Car Status Packet
 /// This packet details car statuses for all the cars in the race.
 /// Frequency: Rate as specified in menus
-/// Size: 1058 bytes
+/// Size: 1239 bytes
 /// Version: 1
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TC {
     Off = 0,
     Medium = 1,
@@ -2234,6 +2452,7 @@ impl TC
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum FuelMix {
     Lean = 0,
     Standard = 1,
@@ -2260,6 +2479,7 @@ impl FuelMix
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ActualCompound {
              C6 = 22,
              C0 = 21,
@@ -2334,6 +2554,7 @@ impl fmt::Display for ActualCompound {
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum VisualCompound {
          OldHard = 22,
        OldMedium = 21,
@@ -2397,6 +2618,7 @@ impl fmt::Display for VisualCompound {
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ErsDeployMode {
     None = 0,
     Medium = 1,
@@ -2421,8 +2643,9 @@ impl ErsDeployMode
     }
 }
 
-#[repr(C, packed)] // Size: 44
+#[repr(C, packed)] // Size: 55 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CarStatus
 {
     pub tractionControl: TC,            // u8
@@ -2487,11 +2710,12 @@ impl CarStatus
     }
 }
 
-#[repr(C, packed)] // Size: 1239
+#[repr(C, packed)] // Size: 1239 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketCarStatus
 {
-    pub header: Header,                 // 24 Bytes - Header
+    pub header: Header,                 // 29 Bytes - Header
 
     pub carStatus: [CarStatus; 22],
 }
@@ -2527,10 +2751,11 @@ impl PacketCarStatus
 /// # Final Classification Packet
 /// This packet details the final classification at the end of the race, and the data will match with the post race results screen. This is especially useful for multiplayer games where it is not always possible to send lap times on the final frame because of network delay.
 /// Frequency: Once at the end of a race
-/// Size: 1015 bytes
+/// Size: 1042 bytes
 /// Version: 1
-#[repr(C, packed)] // Size: 45 Bytes
+#[repr(C, packed)] // Size: 46 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FinalClassification
 {
     pub position: u8,               // Finishing position
@@ -2539,6 +2764,9 @@ pub struct FinalClassification
     pub points: u8,                 // Number of points scored
     pub numPitStops: u8,            // Number of pit stops made
     pub resultStatus: ResultStatus, // u8
+    pub resultReason: u8,           // Result reason - 0 = invalid, 1 = retired, 2 = finished, 3 = terminal damage, 4 = inactive,
+                                    // 5 = not enough laps completed, 6 = black flagged, 7 = red flagged, 8 = mechanical failure,
+                                    // 9 = session skipped, 10 = session simulated
     pub bestLapTimeInMS: u32,       // Best lap time of the session in milliseconds
     pub totalRaceTime: f64,         // Total race time in seconds without penalties
     pub penaltiesTime: u8,          // Total penalties accumulated in seconds
@@ -2560,14 +2788,15 @@ impl FinalClassification
                        points:                       bytes[ 3],
                   numPitStops:                       bytes[ 4],
                  resultStatus: ResultStatus::from_u8(bytes[ 5]),
-              bestLapTimeInMS:   u32::from_le_bytes([bytes[ 6], bytes[ 7], bytes[ 8], bytes[ 9]]),
-                totalRaceTime:   f64::from_le_bytes([bytes[10], bytes[11], bytes[12], bytes[13], bytes[14], bytes[15], bytes[16], bytes[17]]),
-                penaltiesTime:                       bytes[18],
-                 numPenalties:                       bytes[19],
-                numTyreStints:                       bytes[20],
-             tyreStintsActual:     Self::actualTyre(&bytes[21..29]),
-             tyreStintsVisual:     Self::visualTyre(&bytes[29..37]),
-            tyreStintsEndLaps:                      [bytes[37], bytes[38], bytes[39], bytes[40], bytes[41], bytes[42], bytes[43], bytes[44]],
+                 resultReason:                       bytes[ 6],
+              bestLapTimeInMS:   u32::from_le_bytes([bytes[ 7], bytes[ 8], bytes[ 9], bytes[10]]),
+                totalRaceTime:   f64::from_le_bytes([bytes[11], bytes[12], bytes[13], bytes[14], bytes[15], bytes[16], bytes[17], bytes[18]]),
+                penaltiesTime:                       bytes[19],
+                 numPenalties:                       bytes[20],
+                numTyreStints:                       bytes[21],
+             tyreStintsActual:     Self::actualTyre(&bytes[22..30]),
+             tyreStintsVisual:     Self::visualTyre(&bytes[30..38]),
+            tyreStintsEndLaps:                      [bytes[38], bytes[39], bytes[40], bytes[41], bytes[42], bytes[43], bytes[44], bytes[45]],
         }
     }
 
@@ -2595,11 +2824,12 @@ impl FinalClassification
     }
 }
 
-#[repr(C, packed)] // Size: 1014 Bytes
+#[repr(C, packed)] // Size: 1042 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketFinalClassification
 {
-    pub header: Header,             // 24 Bytes - Header
+    pub header: Header,             // 29 Bytes - Header
 
     pub numCars: u8,                // Number of cars in the final classification
     pub classificationData: [FinalClassification; 22],
@@ -2612,8 +2842,8 @@ impl PacketFinalClassification
         Self {
             header: Header::unpack(bytes),
 
-            numCars: bytes[25],
-            classificationData: Self::classificationData(&bytes[26..])
+            numCars: bytes[29],
+            classificationData: Self::classificationData(&bytes[30..])
         }
     }
 
@@ -2637,17 +2867,23 @@ impl PacketFinalClassification
 /// # Lobby Info Packet
 /// This packet details the players currently in a multiplayer lobby. It details each player’s selected car, any AI involved in the game and also the ready status of each of the participants.
 /// Frequency: Two every second when in the lobby
-/// Size: 1191 bytes
+/// Size: 954 bytes
 /// Version: 1
-#[repr(C, packed)] // Size: 53 Bytes
-#[derive(Clone, Copy)]
+#[repr(C, packed)] // Size: 42 Bytes
+#[derive(Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LobbyInfo
 {
     pub aiControlled: u8,       // Whether the vehicle is AI (1) or Human (0) controlled
-    pub teamId: u8,             // Team id - see appendix (255 if no team currently selected)
-    pub nationality: u8,        // Nationality of the driver
-    pub name: [u8; 48],         // Name of participant in UTF-8 format – null terminated Will be truncated with ... (U+2026) if too long
+    pub teamId: TeamId,         // u8 Team id - see appendix (255 if no team currently selected)
+    pub nationality: Nationality,// u8 Nationality of the driver
+    pub platform: u8,           // 1 = Steam, 3 = PlayStation, 4 = Xbox, 6 = Origin, 255 = unknown
+    #[cfg_attr(feature = "serde", serde(with = "serialize::name"))]
+    pub name: [u8; 32],         // Name of participant in UTF-8 format – null terminated Will be truncated with ... (U+2026) if too long
     pub carNumber: u8,          // Car number of the player
+    pub yourTelemetry: u8,      // The player's UDP setting, 0 = restricted, 1 = public
+    pub showOnlineNames: u8,    // The player's show online names setting, 0 = off, 1 = on
+    pub techLevel: u16,         // F1 World tech level
     pub readyStatus: ReadyStatus,//u8
 }
 
@@ -2657,18 +2893,22 @@ impl LobbyInfo
     {
         Self {
             aiControlled: bytes[0],
-            teamId      : bytes[1],
-            nationality : bytes[2],
-            name: match bytes[3..3+48].try_into()
+            teamId      : TeamId::from_u8(&bytes[1]),
+            nationality : Nationality::from_u8(&bytes[2]),
+            platform    : bytes[3],
+            name: match bytes[4..4+32].try_into()
                     {
                         Ok(str) => str,
                         Err(err) => {
                             dbg!(err);
-                            [0; 48]
+                            [0; 32]
                         }
                     },
-            carNumber   : bytes[51],
-            readyStatus : ReadyStatus::from_u8(&bytes[52]),
+            carNumber      : bytes[36],
+            yourTelemetry  : bytes[37],
+            showOnlineNames: bytes[38],
+            techLevel      : u16::from_le_bytes([bytes[39], bytes[40]]),
+            readyStatus    : ReadyStatus::from_u8(&bytes[41]),
         }
     }
 
@@ -2678,21 +2918,6 @@ impl LobbyInfo
     }
 }
 
-impl Default for LobbyInfo
-{
-    fn default() -> Self
-    {
-        Self {
-            aiControlled: 0,
-            teamId: 0,
-            nationality: 0,
-            name: [0; 48],
-            carNumber: 0,
-            readyStatus: ReadyStatus::default()
-        }
-    }
-}
-
 impl fmt::Debug for LobbyInfo
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -2709,6 +2934,7 @@ impl fmt::Debug for LobbyInfo
 
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ReadyStatus {
     NotReady = 0,
     Ready = 1,
@@ -2731,11 +2957,12 @@ impl ReadyStatus
     }
 }
 
-#[repr(C, packed)] // Size: 1191 Bytes
+#[repr(C, packed)] // Size: 954 Bytes
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketLobbyInfo
 {
-    pub header: Header,             // 24 Bytes - Header
+    pub header: Header,             // 29 Bytes - Header
 
     pub numPlayers: u8,             // Number of players in the lobby data
     pub lobbyPlayers: [LobbyInfo; 22],
@@ -2748,8 +2975,8 @@ impl PacketLobbyInfo
         Self {
             header: Header::unpack(bytes),
 
-            numPlayers: bytes[24],
-            lobbyPlayers: Self::lobbyInfo(&bytes[25..]),
+            numPlayers: bytes[29],
+            lobbyPlayers: Self::lobbyInfo(&bytes[30..]),
         }
     }
 
@@ -2773,15 +3000,17 @@ impl PacketLobbyInfo
 /// # Car Damage Packet
 /// This packet details car damage parameters for all the cars in the race.
 /// Frequency: 2 per second
-/// Size: 948 bytes
+/// Size: 1041 bytes
 /// Version: 1
-#[repr(C, packed)] // Size: 42 Bytes
+#[repr(C, packed)] // Size: 46 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CarDamage
 {
     pub tyresWear: Wheels,          // Tyre wear (percentage)
     pub tyresDamage: [u8; 4],       // Tyre damage (percentage)
     pub brakesDamage: [u8; 4],      // Brakes damage (percentage)
+    pub tyreBlisters: [u8; 4],      // Tyre blisters value (percentage)
     pub frontLeftWingDamage: u8,    // Front left wing damage (percentage)
     pub frontRightWingDamage: u8,   // Front right wing damage (percentage)
     pub rearWingDamage: u8,         // Rear wing damage (percentage)
@@ -2810,33 +3039,35 @@ impl CarDamage
                        tyresWear: Wheels::unpack(&bytes[0..16]),
                      tyresDamage: [bytes[16], bytes[17], bytes[18], bytes[19]],
                     brakesDamage: [bytes[20], bytes[21], bytes[22], bytes[23]],
-             frontLeftWingDamage: bytes[24],
-            frontRightWingDamage: bytes[25],
-                  rearWingDamage: bytes[26],
-                     floorDamage: bytes[27],
-                  diffuserDamage: bytes[28],
-                   sidepodDamage: bytes[29],
-                        drsFault: bytes[30],
-                        ersFault: bytes[31],
-                   gearBoxDamage: bytes[32],
-                    engineDamage: bytes[33],
-                  engineMGUHWear: bytes[34],
-                    engineESWear: bytes[35],
-                    engineCEWear: bytes[36],
-                   engineICEWear: bytes[37],
-                  engineMGUKWear: bytes[38],
-                    engineTCWear: bytes[39],
-                     engineBlown: bytes[40],
-                    engineSeized: bytes[41],
-        }
-    }
-}
-
-#[repr(C, packed)] // Size: 948 Bytes
-#[derive(Debug, Default, Clone, Copy)]
+                    tyreBlisters: [bytes[24], bytes[25], bytes[26], bytes[27]],
+             frontLeftWingDamage: bytes[28],
+            frontRightWingDamage: bytes[29],
+                  rearWingDamage: bytes[30],
+                     floorDamage: bytes[31],
+                  diffuserDamage: bytes[32],
+                   sidepodDamage: bytes[33],
+                        drsFault: bytes[34],
+                        ersFault: bytes[35],
+                   gearBoxDamage: bytes[36],
+                    engineDamage: bytes[37],
+                  engineMGUHWear: bytes[38],
+                    engineESWear: bytes[39],
+                    engineCEWear: bytes[40],
+                   engineICEWear: bytes[41],
+                  engineMGUKWear: bytes[42],
+                    engineTCWear: bytes[43],
+                     engineBlown: bytes[44],
+                    engineSeized: bytes[45],
+        }
+    }
+}
+
+#[repr(C, packed)] // Size: 1041 Bytes
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketCarDamage
 {
-    pub header: Header,             // 24 Bytes - Header
+    pub header: Header,             // 29 Bytes - Header
 
     pub carDamageData: [CarDamage; 22],
 }
@@ -2848,7 +3079,7 @@ impl PacketCarDamage
         Self {
             header: Header::unpack(bytes),
 
-            carDamageData: Self::carDamage(&bytes[23..])
+            carDamageData: Self::carDamage(&bytes[29..])
         }
     }
 
@@ -2873,10 +3104,11 @@ impl PacketCarDamage
 /// This packet contains lap times and tyre usage for the session. **This packet works slightly differently to other packets. To reduce CPU and bandwidth, each packet relates to a specific vehicle and is sent every 1/20 s, and the vehicle being sent is cycled through. Therefore in a 20 car race you should receive an update for each vehicle at least once per second.**
 /// Note that at the end of the race, after the final classification packet has been sent, a final bulk update of all the session histories for the vehicles in that session will be sent.
 /// Frequency: 20 per second but cycling through cars
-/// Size: 1155 bytes
+/// Size: 1460 bytes
 /// Version: 1
 #[repr(u8)]
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Valid
 {
     Lap         = 0b00000001,
@@ -2908,16 +3140,25 @@ impl Valid
             _          => Valid::Poisoned,
         }
     }
+
+    pub fn isLapValid(&self) -> bool
+    {
+        matches!(self, Valid::Lap | Valid::All)
+    }
 }
 
-#[repr(C, packed)] // Size: 11 Bytes
+#[repr(C, packed)] // Size: 14 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct LapHistory
 {
     pub lapTimeInMS: u32,               // u32 Lap time in milliseconds
-    pub sector1TimeInMS: u16,           // u16 Sector 1 time in milliseconds
-    pub sector2TimeInMS: u16,           // u16 Sector 2 time in milliseconds
-    pub sector3TimeInMS: u16,           // u16 Sector 3 time in milliseconds
+    pub sector1TimeMSPart: u16,         // u16 Sector 1 milliseconds part
+    pub sector1TimeMinutesPart: u8,     // u8 Sector 1 whole minute part
+    pub sector2TimeMSPart: u16,         // u16 Sector 2 time milliseconds part
+    pub sector2TimeMinutesPart: u8,     // u8 Sector 2 whole minute part
+    pub sector3TimeMSPart: u16,         // u16 Sector 3 time milliseconds part
+    pub sector3TimeMinutesPart: u8,     // u8 Sector 3 whole minute part
     pub lapValidBitFlags: Valid,        // u8 - 0x01 bit set-lap valid, 0x02 bit set-sector 1 valid 0x04 bit set-sector 2 valid, 0x08 bit set-sector 3 valid
 }
 
@@ -2926,17 +3167,36 @@ impl LapHistory
     pub fn unpack(bytes: &[u8]) -> Self
     {
         Self {
-            lapTimeInMS     : u32::from_le_bytes([bytes[ 0], bytes[ 1], bytes[ 2], bytes[ 3]]),
-            sector1TimeInMS : u16::from_le_bytes([bytes[ 4], bytes[ 5]]),
-            sector2TimeInMS : u16::from_le_bytes([bytes[ 6], bytes[ 8]]),
-            sector3TimeInMS : u16::from_le_bytes([bytes[ 8], bytes[10]]),
-            lapValidBitFlags: Valid::from_u8(&bytes[10])
+            lapTimeInMS           : u32::from_le_bytes([bytes[ 0], bytes[ 1], bytes[ 2], bytes[ 3]]),
+            sector1TimeMSPart     : u16::from_le_bytes([bytes[ 4], bytes[ 5]]),
+            sector1TimeMinutesPart: bytes[ 6],
+            sector2TimeMSPart     : u16::from_le_bytes([bytes[ 7], bytes[ 8]]),
+            sector2TimeMinutesPart: bytes[ 9],
+            sector3TimeMSPart     : u16::from_le_bytes([bytes[10], bytes[11]]),
+            sector3TimeMinutesPart: bytes[12],
+            lapValidBitFlags      : Valid::from_u8(&bytes[13])
         }
     }
+
+    pub fn sector1TimeInMS(&self) -> u32
+    {
+        self.sector1TimeMinutesPart as u32 * 60_000 + self.sector1TimeMSPart as u32
+    }
+
+    pub fn sector2TimeInMS(&self) -> u32
+    {
+        self.sector2TimeMinutesPart as u32 * 60_000 + self.sector2TimeMSPart as u32
+    }
+
+    pub fn sector3TimeInMS(&self) -> u32
+    {
+        self.sector3TimeMinutesPart as u32 * 60_000 + self.sector3TimeMSPart as u32
+    }
 }
 
 #[repr(C, packed)] // Size: 3 Bytes
 #[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TyreStintHistory
 {
     pub endLap: u8,             // Lap the tyre usage ends on (255 of current tyre)
@@ -2956,8 +3216,9 @@ impl TyreStintHistory
     }
 }
 
-#[repr(C, packed)] // Size: 1155 Bytes
+#[repr(C, packed)] // Size: 1460 Bytes
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PacketSessionHistory
 {
     pub header: Header,         // 29 Bytes - Header
@@ -2971,7 +3232,8 @@ pub struct PacketSessionHistory
     pub bestSector2LapNum: u8,  // Lap the best Sector 2 time was achieved on
     pub bestSector3LapNum: u8,  // Lap the best Sector 3 time was achieved on
 
-    pub lapHistory: [LapHistory; 100], // 11 Bytes * 100 - 100 laps of data max
+    #[cfg_attr(feature = "serde", serde(with = "serialize::array"))]
+    pub lapHistory: [LapHistory; 100], // 14 Bytes * 100 - 100 laps of data max
     pub tyreStintsHistory: [TyreStintHistory; 8], // 3 Bytes * 8
 }
 
@@ -2989,15 +3251,15 @@ impl PacketSessionHistory
             bestSector1LapNum: bytes[33],
             bestSector2LapNum: bytes[34],
             bestSector3LapNum: bytes[35],
-            lapHistory       : Self::lapHistory(&bytes[29+7..(29+7)+(11*100)]),
-            tyreStintsHistory: Self::tyreStintHistory(&bytes[(29+7)+(11*100)..(29+7)+(11*100)+(3*8)]),
+            lapHistory       : Self::lapHistory(&bytes[29+7..(29+7)+(14*100)]),
+            tyreStintsHistory: Self::tyreStintHistory(&bytes[(29+7)+(14*100)..(29+7)+(14*100)+(3*8)]),
         }
     }
 
     pub fn lapHistory(bytes: &[u8]) -> [LapHistory; 100]
     {
         let mut lh = [LapHistory::default(); 100];
-        let size = 11;
+        let size = size_of::<LapHistory>();
 
         for (i, lh) in lh.iter_mut().enumerate()
         {
@@ -3013,7 +3275,7 @@ impl PacketSessionHistory
     pub fn tyreStintHistory(bytes: &[u8]) -> [TyreStintHistory; 8]
     {
         let mut tsh = [TyreStintHistory::default(); 8];
-        let size = 3;
+        let size = size_of::<TyreStintHistory>();
 
         for (i, tsh) in tsh.iter_mut().enumerate()
         {
@@ -3034,19 +3296,20 @@ impl PacketSessionHistory
 //-----------------------------------------------------------------------------
 // Data about one tyre set
 //-----------------------------------------------------------------------------
-#[repr(C, packed)] // Size: 1155 Bytes
+#[repr(C, packed)] // Size: 10 Bytes
 #[derive(Debug, Default, Clone, Copy)]
-struct TyreSet
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TyreSet
 {
-    actualCompound: ActualCompound,                 // Actual tyre compound used
-    visualCompound: VisualCompound,                 // Visual tyre compound used
-    wear: u8,                                       // Tyre wear (percentage)
-    available: u8,                                  // Whether this set is currently available
-    recommendedSession: u8,                         // Recommended session for tyre set, see appendix
-    lifeSpan: u8,                                   // Laps left in this tyre set
-    usableLife: u8,                                 // Max number of laps recommended for this compound
-    lapDeltaTime: i16,                              // Lap delta time in milliseconds compared to fitted set
-    fitted: u8,                                     // Whether the set is fitted or not
+    pub actualCompound: ActualCompound,             // Actual tyre compound used
+    pub visualCompound: VisualCompound,             // Visual tyre compound used
+    pub wear: u8,                                   // Tyre wear (percentage)
+    pub available: u8,                              // Whether this set is currently available
+    pub recommendedSession: Session,                // u8 Recommended session for tyre set, see appendix
+    pub lifeSpan: u8,                               // Laps left in this tyre set
+    pub usableLife: u8,                             // Max number of laps recommended for this compound
+    pub lapDeltaTime: i16,                          // Lap delta time in milliseconds compared to fitted set
+    pub fitted: u8,                                 // Whether the set is fitted or not
 }
 
 impl TyreSet
@@ -3058,7 +3321,7 @@ impl TyreSet
             visualCompound: VisualCompound::from_u8(&bytes[1]),
             wear: bytes[2],
             available: bytes[3],
-            recommendedSession: bytes[4],
+            recommendedSession: Session::from_u8(&bytes[4]),
             lifeSpan: bytes[5],
             usableLife: bytes[6],
             lapDeltaTime: i16::from_le_bytes([bytes[7], bytes[8]]),
@@ -3067,7 +3330,10 @@ impl TyreSet
     }
 }
 
-struct TyreSetsData
+#[repr(C, packed)] // Size: 231 Bytes
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TyreSetsData
 {
     pub header: Header,                             // 29 Bytes - Header
 
@@ -3084,8 +3350,8 @@ impl TyreSetsData
         Self {
             header           : Header::unpack(bytes),
             carIdx           : bytes[29],
-            tyreSetData      : Self::tyre_set(&bytes[29 .. 29 + (size_of::<TyreSet>() * MAX_NUM_TYRE_SETS)]),
-            fittedIdx        : bytes[bytes.len() - 1]
+            tyreSetData      : Self::tyre_set(&bytes[30 .. 30 + (size_of::<TyreSet>() * MAX_NUM_TYRE_SETS)]),
+            fittedIdx        : bytes[30 + (size_of::<TyreSet>() * MAX_NUM_TYRE_SETS)]
         }
     }
 
@@ -3105,3 +3371,40 @@ impl TyreSetsData
         t
     }
 }
+
+//
+// # Sizes
+// Checked at compile time against the sizes given in the specification.
+//
+const _: () = assert!(size_of::<Header>() == 29);
+const _: () = assert!(size_of::<CarMotion>() == 60);
+const _: () = assert!(size_of::<PacketMotion>() == 1349);
+const _: () = assert!(size_of::<MarshalZone>() == 5);
+const _: () = assert!(size_of::<WeatherForecast>() == 8);
+const _: () = assert!(size_of::<PacketSession>() == 753);
+const _: () = assert!(size_of::<Car>() == 57);
+const _: () = assert!(size_of::<PacketLap>() == 1285);
+const _: () = assert!(size_of::<EventDetails>() == 12);
+const _: () = assert!(size_of::<PacketEvent>() == 45);
+const _: () = assert!(size_of::<Participant>() == 57);
+const _: () = assert!(size_of::<PacketParticipants>() == 1284);
+const _: () = assert!(size_of::<CarSetup>() == 50);
+const _: () = assert!(size_of::<PacketCarSetups>() == 1133);
+const _: () = assert!(size_of::<CarTelemetry>() == 60);
+const _: () = assert!(size_of::<PacketCarTelemetry>() == 1352);
+const _: () = assert!(size_of::<CarStatus>() == 55);
+const _: () = assert!(size_of::<PacketCarStatus>() == 1239);
+const _: () = assert!(size_of::<FinalClassification>() == 46);
+const _: () = assert!(size_of::<PacketFinalClassification>() == 1042);
+const _: () = assert!(size_of::<LobbyInfo>() == 42);
+const _: () = assert!(size_of::<PacketLobbyInfo>() == 954);
+const _: () = assert!(size_of::<CarDamage>() == 46);
+const _: () = assert!(size_of::<PacketCarDamage>() == 1041);
+const _: () = assert!(size_of::<LapHistory>() == 14);
+const _: () = assert!(size_of::<TyreStintHistory>() == 3);
+const _: () = assert!(size_of::<PacketSessionHistory>() == 1460);
+const _: () = assert!(size_of::<TyreSet>() == 10);
+const _: () = assert!(size_of::<TyreSetsData>() == 231);
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,556 @@
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+//
+// # Appendices
+// Lookups for the ids the packets refer to with "see appendix".
+//
+
+// An id as it came off the wire, with a constant and a name for each one the appendix
+// lists. Ids it doesn't list are kept as they are, so packets pack back to the same bytes.
+macro_rules! appendix {
+    (
+        $(#[$meta:meta])*
+        $name:ident: $repr:ty = $default:literal, $from:ident {
+            $($id:literal => $variant:ident $display:literal,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[repr(C)]
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        pub struct $name {
+            pub id: $repr
+        }
+
+        #[allow(non_upper_case_globals)]
+        impl $name
+        {
+            $( pub const $variant: Self = Self { id: $id }; )*
+
+            pub fn $from(byte: &u8) -> Self
+            {
+                Self { id: *byte as $repr }
+            }
+
+            pub fn name(&self) -> Option<&'static str>
+            {
+                match self.id
+                {
+                    $( $id => Some($display), )*
+                    _ => None,
+                }
+            }
+
+            fn variant(&self) -> Option<&'static str>
+            {
+                match self.id
+                {
+                    $( $id => Some(stringify!($variant)), )*
+                    _ => None,
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { id: $default }
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.variant()
+                {
+                    Some(variant) => write!(f, "{variant}"),
+                    None          => write!(f, "Unknown({})", self.id),
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.name().unwrap_or("???"))
+            }
+        }
+
+        // By constant name, or the id itself if the appendix doesn't list it.
+        #[cfg(feature = "serde")]
+        impl Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self.variant()
+                {
+                    Some(variant) => serializer.serialize_str(variant),
+                    None          => self.id.serialize(serializer),
+                }
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                #[derive(Deserialize)]
+                #[serde(untagged)]
+                enum Id {
+                    Name(String),
+                    Id($repr),
+                }
+
+                match Id::deserialize(deserializer)?
+                {
+                    Id::Id(id) => Ok(Self { id }),
+                    $( Id::Name(name) if name == stringify!($variant) => Ok(Self::$variant), )*
+                    Id::Name(name) => Err(serde::de::Error::custom(
+                        format!("unknown {} {name}", stringify!($name)),
+                    )),
+                }
+            }
+        }
+    };
+}
+
+appendix! {
+    /// # Team IDs
+    TeamId: u8 = 255, from_u8 {
+          0 => Mercedes              "Mercedes",
+          1 => Ferrari               "Ferrari",
+          2 => RedBullRacing         "Red Bull Racing",
+          3 => Williams              "Williams",
+          4 => AstonMartin           "Aston Martin",
+          5 => Alpine                "Alpine",
+          6 => RB                    "RB",
+          7 => Haas                  "Haas",
+          8 => McLaren               "McLaren",
+          9 => Sauber                "Sauber",
+         41 => F1Generic             "F1 Generic",
+        104 => F1CustomTeam          "F1 Custom Team",
+        129 => Konnersport           "Konnersport",
+        142 => APXGP24               "APXGP '24",
+        143 => ArtGP24               "Art GP '24",
+        144 => Campos24              "Campos '24",
+        145 => RodinMotorsport24     "Rodin Motorsport '24",
+        146 => AIXRacing24           "AIX Racing '24",
+        147 => DAMS24                "DAMS '24",
+        148 => Hitech24              "Hitech '24",
+        149 => MPMotorsport24        "MP Motorsport '24",
+        150 => Prema24               "Prema '24",
+        151 => Trident24             "Trident '24",
+        152 => VanAmersfoortRacing24 "Van Amersfoort Racing '24",
+        153 => Invicta24             "Invicta '24",
+        154 => APXGP25               "APXGP '25",
+        155 => Konnersport24         "Konnersport '24",
+        185 => Mercedes24            "Mercedes '24",
+        186 => Ferrari24             "Ferrari '24",
+        187 => RedBullRacing24       "Red Bull Racing '24",
+        188 => Williams24            "Williams '24",
+        189 => AstonMartin24         "Aston Martin '24",
+        190 => Alpine24              "Alpine '24",
+        191 => RB24                  "RB '24",
+        192 => Haas24                "Haas '24",
+        193 => McLaren24             "McLaren '24",
+        194 => Sauber24              "Sauber '24",
+    }
+}
+
+appendix! {
+    /// # Track IDs
+    TrackId: i8 = -1, from_u8_to_i8 {
+         0 => Melbourne          "Melbourne",
+         1 => PaulRicard         "Paul Ricard",
+         2 => Shanghai           "Shanghai",
+         3 => Sakhir             "Sakhir",
+         4 => Catalunya          "Catalunya",
+         5 => Monaco             "Monaco",
+         6 => Montreal           "Montreal",
+         7 => Silverstone        "Silverstone",
+         8 => Hockenheim         "Hockenheim",
+         9 => Hungaroring        "Hungaroring",
+        10 => Spa                "Spa",
+        11 => Monza              "Monza",
+        12 => Singapore          "Singapore",
+        13 => Suzuka             "Suzuka",
+        14 => AbuDhabi           "Abu Dhabi",
+        15 => Texas              "Texas",
+        16 => Brazil             "Brazil",
+        17 => Austria            "Austria",
+        18 => Sochi              "Sochi",
+        19 => Mexico             "Mexico",
+        20 => Baku               "Baku",
+        21 => SakhirShort        "Sakhir Short",
+        22 => SilverstoneShort   "Silverstone Short",
+        23 => TexasShort         "Texas Short",
+        24 => SuzukaShort        "Suzuka Short",
+        25 => Hanoi              "Hanoi",
+        26 => Zandvoort          "Zandvoort",
+        27 => Imola              "Imola",
+        28 => Portimao           "Portimão",
+        29 => Jeddah             "Jeddah",
+        30 => Miami              "Miami",
+        31 => LasVegas           "Las Vegas",
+        32 => Losail             "Losail",
+        39 => SilverstoneReverse "Silverstone (Reverse)",
+        40 => AustriaReverse     "Austria (Reverse)",
+        41 => ZandvoortReverse   "Zandvoort (Reverse)",
+    }
+}
+
+/// # Driver IDs
+/// 255 for network humans, who go by their participant name instead.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DriverId {
+    pub id: u8
+}
+
+impl DriverId
+{
+    pub fn from_u8(byte: &u8) -> Self
+    {
+        Self { id: *byte }
+    }
+
+    pub fn name(&self) -> Option<&'static str>
+    {
+        let name = match self.id
+        {
+              0 => "Carlos Sainz",
+              2 => "Daniel Ricciardo",
+              3 => "Fernando Alonso",
+              4 => "Felipe Massa",
+              7 => "Lewis Hamilton",
+              9 => "Max Verstappen",
+             10 => "Nico Hülkenberg",
+             11 => "Kevin Magnussen",
+             14 => "Sergio Pérez",
+             15 => "Valtteri Bottas",
+             17 => "Esteban Ocon",
+             19 => "Lance Stroll",
+             20 => "Arron Barnes",
+             21 => "Martin Giles",
+             22 => "Alex Murray",
+             23 => "Lucas Roth",
+             24 => "Igor Correia",
+             25 => "Sophie Levasseur",
+             26 => "Jonas Schiffer",
+             27 => "Alain Forest",
+             28 => "Jay Letourneau",
+             29 => "Esto Saari",
+             30 => "Yasar Atiyeh",
+             31 => "Callisto Calabresi",
+             32 => "Naota Izum",
+             33 => "Howard Clarke",
+             34 => "Lars Kaufmann",
+             35 => "Marie Laursen",
+             36 => "Flavio Nieves",
+             38 => "Klimek Michalski",
+             39 => "Santiago Moreno",
+             40 => "Benjamin Coppens",
+             41 => "Noah Visser",
+             50 => "George Russell",
+             54 => "Lando Norris",
+             58 => "Charles Leclerc",
+             59 => "Pierre Gasly",
+             62 => "Alexander Albon",
+             70 => "Rashid Nair",
+             71 => "Jack Tremblay",
+             77 => "Ayrton Senna",
+             80 => "Guanyu Zhou",
+             83 => "Juan Manuel Correa",
+             90 => "Michael Schumacher",
+             94 => "Yuki Tsunoda",
+            102 => "Aidan Jackson",
+            109 => "Jenson Button",
+            110 => "David Coulthard",
+            112 => "Oscar Piastri",
+            113 => "Liam Lawson",
+            116 => "Richard Verschoor",
+            123 => "Enzo Fittipaldi",
+            125 => "Mark Webber",
+            126 => "Jacques Villeneuve",
+            127 => "Callie Mayer",
+            132 => "Logan Sargeant",
+            136 => "Jack Doohan",
+            137 => "Amaury Cordeel",
+            138 => "Dennis Hauger",
+            145 => "Zane Maloney",
+            146 => "Victor Martins",
+            147 => "Oliver Bearman",
+            148 => "Jak Crawford",
+            149 => "Isack Hadjar",
+            152 => "Roman Stanek",
+            153 => "Kush Maini",
+            156 => "Brendon Leigh",
+            157 => "David Tonizza",
+            158 => "Jarno Opmeer",
+            159 => "Lucas Blakeley",
+            160 => "Paul Aron",
+            161 => "Gabriel Bortoleto",
+            162 => "Franco Colapinto",
+            163 => "Taylor Barnard",
+            164 => "Joshua Dürksen",
+            165 => "Andrea-Kimi Antonelli",
+            166 => "Ritomo Miyata",
+            167 => "Rafael Villagómez",
+            168 => "Zak O'Sullivan",
+            169 => "Pepe Marti",
+            170 => "Sonny Hayes",
+            171 => "Joshua Pearce",
+            172 => "Callum Voisin",
+            173 => "Matias Zagazeta",
+            174 => "Nikola Tsolov",
+            175 => "Tim Tramnitz",
+            185 => "Luca Cortez",
+              _ => return None,
+        };
+
+        Some(name)
+    }
+}
+
+impl fmt::Display for DriverId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.id, self.name())
+        {
+            (_, Some(name)) => write!(f, "{name}"),
+            (255, None)     => write!(f, "Human"),
+            (id, None)      => write!(f, "Driver {id}"),
+        }
+    }
+}
+
+/// # Nationality IDs
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Nationality {
+    pub id: u8
+}
+
+static NATIONALITIES: [&str; 91] = [
+    "Unknown", "American", "Argentinean", "Australian", "Austrian", "Azerbaijani", "Bahraini", "Belgian",
+    "Bolivian", "Brazilian", "British", "Bulgarian", "Cameroonian", "Canadian", "Chilean", "Chinese",
+    "Colombian", "Costa Rican", "Croatian", "Cypriot", "Czech", "Danish", "Dutch", "Ecuadorian",
+    "English", "Emirian", "Estonian", "Finnish", "French", "German", "Ghanaian", "Greek",
+    "Guatemalan", "Honduran", "Hong Konger", "Hungarian", "Icelander", "Indian", "Indonesian", "Irish",
+    "Israeli", "Italian", "Jamaican", "Japanese", "Jordanian", "Kuwaiti", "Latvian", "Lebanese",
+    "Lithuanian", "Luxembourger", "Malaysian", "Maltese", "Mexican", "Monegasque", "New Zealander", "Nicaraguan",
+    "Northern Irish", "Norwegian", "Omani", "Pakistani", "Panamanian", "Paraguayan", "Peruvian", "Polish",
+    "Portuguese", "Qatari", "Romanian", "Russian", "Salvadoran", "Saudi", "Scottish", "Serbian",
+    "Singaporean", "Slovakian", "Slovenian", "South Korean", "South African", "Spanish", "Swedish", "Swiss",
+    "Thai", "Turkish", "Uruguayan", "Ukrainian", "Venezuelan", "Barbadian", "Welsh", "Vietnamese",
+    "Algerian", "Bosnian", "Filipino",
+];
+
+impl Nationality
+{
+    pub fn from_u8(byte: &u8) -> Self
+    {
+        Self { id: *byte }
+    }
+
+    pub fn name(&self) -> &'static str
+    {
+        NATIONALITIES.get(self.id as usize).unwrap_or(&NATIONALITIES[0])
+    }
+}
+
+impl fmt::Display for Nationality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+appendix! {
+    /// # Game Mode IDs
+    GameMode: u8 = 255, from_u8 {
+          0 => EventMode                "Event Mode",
+          3 => GrandPrix                "Grand Prix",
+          4 => GrandPrix23              "Grand Prix '23",
+          5 => TimeTrial                "Time Trial",
+          6 => Splitscreen              "Splitscreen",
+          7 => OnlineCustom             "Online Custom",
+          8 => OnlineLeague             "Online League",
+         11 => CareerInvitational       "Career Invitational",
+         12 => ChampionshipInvitational "Championship Invitational",
+         13 => Championship             "Championship",
+         14 => OnlineChampionship       "Online Championship",
+         15 => OnlineWeeklyEvent        "Online Weekly Event",
+         17 => StoryMode                "Story Mode",
+         19 => Career22                 "Career '22",
+         20 => Career22Online           "Career '22 Online",
+         21 => Career23                 "Career '23",
+         22 => Career23Online           "Career '23 Online",
+         23 => DriverCareer24           "Driver Career '24",
+         24 => Career24Online           "Career '24 Online",
+         25 => MyTeamCareer24           "My Team Career '24",
+         26 => CuratedCareer24          "Curated Career '24",
+         27 => MyTeamCareer25           "My Team Career '25",
+         28 => DriverCareer25           "Driver Career '25",
+         29 => Career25Online           "Career '25 Online",
+         30 => ChallengeCareer25        "Challenge Career '25",
+         75 => StoryModeAPXGP           "Story Mode (APXGP)",
+        127 => Benchmark                "Benchmark",
+    }
+}
+
+appendix! {
+    /// # Ruleset IDs
+    RuleSet: u8 = 255, from_u8 {
+         0 => PracticeAndQualifying "Practice & Qualifying",
+         1 => Race                  "Race",
+         2 => TimeTrial             "Time Trial",
+         4 => TimeAttack            "Time Attack",
+         6 => CheckpointChallenge   "Checkpoint Challenge",
+         8 => Autocross             "Autocross",
+         9 => Drift                 "Drift",
+        10 => AverageSpeedZone      "Average Speed Zone",
+        11 => RivalDuel             "Rival Duel",
+        12 => Elimination           "Elimination",
+    }
+}
+
+appendix! {
+    /// # Surface Types
+    /// What each wheel is running on.
+    Surface: u8 = 255, from_u8 {
+         0 => Tarmac      "Tarmac",
+         1 => RumbleStrip "Rumble Strip",
+         2 => Concrete    "Concrete",
+         3 => Rock        "Rock",
+         4 => Gravel      "Gravel",
+         5 => Mud         "Mud",
+         6 => Sand        "Sand",
+         7 => Grass       "Grass",
+         8 => Water       "Water",
+         9 => Cobblestone "Cobblestone",
+        10 => Metal       "Metal",
+        11 => Ridged      "Ridged",
+    }
+}
+
+appendix! {
+    /// # Penalty Types
+    PenaltyType: u8 = 255, from_u8 {
+         0 => DriveThrough                               "Drive through",
+         1 => StopGo                                     "Stop go",
+         2 => GridPenalty                                "Grid penalty",
+         3 => PenaltyReminder                            "Penalty reminder",
+         4 => TimePenalty                                "Time penalty",
+         5 => Warning                                    "Warning",
+         6 => Disqualified                               "Disqualified",
+         7 => RemovedFromFormationLap                    "Removed from formation lap",
+         8 => ParkedTooLongTimer                         "Parked too long timer",
+         9 => TyreRegulations                            "Tyre regulations",
+        10 => ThisLapInvalidated                         "This lap invalidated",
+        11 => ThisAndNextLapInvalidated                  "This and next lap invalidated",
+        12 => ThisLapInvalidatedWithoutReason            "This lap invalidated without reason",
+        13 => ThisAndNextLapInvalidatedWithoutReason     "This and next lap invalidated without reason",
+        14 => ThisAndPreviousLapInvalidated              "This and previous lap invalidated",
+        15 => ThisAndPreviousLapInvalidatedWithoutReason "This and previous lap invalidated without reason",
+        16 => Retired                                    "Retired",
+        17 => BlackFlagTimer                             "Black flag timer",
+    }
+}
+
+appendix! {
+    /// # Infringement Types
+    InfringementType: u8 = 255, from_u8 {
+         0 => BlockingBySlowDriving                     "Blocking by slow driving",
+         1 => BlockingByWrongWayDriving                 "Blocking by wrong way driving",
+         2 => ReversingOffTheStartLine                  "Reversing off the start line",
+         3 => BigCollision                              "Big collision",
+         4 => SmallCollision                            "Small collision",
+         5 => CollisionFailedToHandBackPositionSingle   "Collision, failed to hand back position",
+         6 => CollisionFailedToHandBackPositionMultiple "Collision, failed to hand back positions",
+         7 => CornerCuttingGainedTime                   "Track limits, gained time",
+         8 => CornerCuttingOvertakeSingle               "Track limits, overtake",
+         9 => CornerCuttingOvertakeMultiple             "Track limits, overtakes",
+        10 => CrossedPitExitLane                        "Crossed pit exit lane",
+        11 => IgnoringBlueFlags                         "Ignoring blue flags",
+        12 => IgnoringYellowFlags                       "Ignoring yellow flags",
+        13 => IgnoringDriveThrough                      "Ignoring drive through",
+        14 => TooManyDriveThroughs                      "Too many drive throughs",
+        15 => DriveThroughReminderServeWithinNLaps      "Drive through reminder, serve within n laps",
+        16 => DriveThroughReminderServeThisLap          "Drive through reminder, serve this lap",
+        17 => PitLaneSpeeding                           "Pit lane speeding",
+        18 => ParkedForTooLong                          "Parked for too long",
+        19 => IgnoringTyreRegulations                   "Ignoring tyre regulations",
+        20 => TooManyPenalties                          "Too many penalties",
+        21 => MultipleWarnings                          "Multiple warnings",
+        22 => ApproachingDisqualification               "Approaching disqualification",
+        23 => TyreRegulationsSelectSingle               "Tyre regulations, select single",
+        24 => TyreRegulationsSelectMultiple             "Tyre regulations, select multiple",
+        25 => LapInvalidatedCornerCutting               "Track limits",
+        26 => LapInvalidatedRunningWide                 "Track limits, running wide",
+        27 => CornerCuttingRanWideGainedTimeMinor       "Track limits, ran wide, minor gain",
+        28 => CornerCuttingRanWideGainedTimeSignificant "Track limits, ran wide, significant gain",
+        29 => CornerCuttingRanWideGainedTimeExtreme     "Track limits, ran wide, extreme gain",
+        30 => LapInvalidatedWallRiding                  "Wall riding",
+        31 => LapInvalidatedFlashbackUsed               "Flashback used",
+        32 => LapInvalidatedResetToTrack                "Reset to track",
+        33 => BlockingThePitlane                        "Blocking the pit lane",
+        34 => JumpStart                                 "Jump start",
+        35 => SafetyCarToCarCollision                   "Safety car to car collision",
+        36 => SafetyCarIllegalOvertake                  "Safety car illegal overtake",
+        37 => SafetyCarExceedingAllowedPace             "Safety car exceeding allowed pace",
+        38 => VirtualSafetyCarExceedingAllowedPace      "Virtual safety car exceeding allowed pace",
+        39 => FormationLapBelowAllowedSpeed             "Formation lap below allowed speed",
+        40 => FormationLapParking                       "Formation lap parking",
+        41 => RetiredMechanicalFailure                  "Retired, mechanical failure",
+        42 => RetiredTerminallyDamaged                  "Retired, terminally damaged",
+        43 => SafetyCarFallingTooFarBack                "Safety car, falling too far back",
+        44 => BlackFlagTimer                            "Black flag timer",
+        45 => UnservedStopGoPenalty                     "Unserved stop go penalty",
+        46 => UnservedDriveThroughPenalty               "Unserved drive through penalty",
+        47 => EngineComponentChange                     "Engine component change",
+        48 => GearboxChange                             "Gearbox change",
+        49 => ParcFermeChange                           "Parc fermé change",
+        50 => LeagueGridPenalty                         "League grid penalty",
+        51 => RetryPenalty                              "Retry penalty",
+        52 => IllegalTimeGain                           "Illegal time gain",
+        53 => MandatoryPitstop                          "Mandatory pit stop",
+        54 => AttributeAssigned                         "Attribute assigned",
+    }
+}
+
+/// # Button Flags
+/// Bits of Buttons.buttonStatus.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Button
+{
+    CrossOrA        = 0x00000001,
+    TriangleOrY     = 0x00000002,
+    CircleOrB       = 0x00000004,
+    SquareOrX       = 0x00000008,
+    DPadLeft        = 0x00000010,
+    DPadRight       = 0x00000020,
+    DPadUp          = 0x00000040,
+    DPadDown        = 0x00000080,
+    OptionsOrMenu   = 0x00000100,
+    L1OrLB          = 0x00000200,
+    R1OrRB          = 0x00000400,
+    L2OrLT          = 0x00000800,
+    R2OrRT          = 0x00001000,
+    LeftStickClick  = 0x00002000,
+    RightStickClick = 0x00004000,
+    RightStickLeft  = 0x00008000,
+    RightStickRight = 0x00010000,
+    RightStickUp    = 0x00020000,
+    RightStickDown  = 0x00040000,
+    Special         = 0x00080000,
+    UDPAction1      = 0x00100000,
+    UDPAction2      = 0x00200000,
+    UDPAction3      = 0x00400000,
+    UDPAction4      = 0x00800000,
+    UDPAction5      = 0x01000000,
+    UDPAction6      = 0x02000000,
+    UDPAction7      = 0x04000000,
+    UDPAction8      = 0x08000000,
+    UDPAction9      = 0x10000000,
+    UDPAction10     = 0x20000000,
+    UDPAction11     = 0x40000000,
+    UDPAction12     = 0x80000000,
+}
@@ -0,0 +1,677 @@
+use super::*;
+use std::fmt::Write;
+
+//
+// # JSON
+// Each type writes itself as one JSON object, field names as in the structs and
+// enums by name rather than number, so a packet can go straight to jq or a log
+// shipper. Packed fields are copied out before they are written.
+//
+
+pub trait ToJson
+{
+    fn json(&self, out: &mut String);
+
+    fn to_json(&self) -> String
+    {
+        let mut out = String::new();
+        self.json(&mut out);
+        out
+    }
+}
+
+// Writes the fields of an object, in order, between the braces.
+pub struct Object<'a>
+{
+    out: &'a mut String,
+    first: bool,
+}
+
+impl<'a> Object<'a>
+{
+    pub fn new(out: &'a mut String) -> Self
+    {
+        out.push('{');
+        Self { out, first: true }
+    }
+
+    pub fn field<T: ToJson + ?Sized>(&mut self, name: &str, value: &T) -> &mut Self
+    {
+        if !self.first {
+            self.out.push(',');
+        }
+        self.first = false;
+
+        name.json(self.out);
+        self.out.push(':');
+        value.json(self.out);
+        self
+    }
+
+    pub fn end(&mut self)
+    {
+        self.out.push('}');
+    }
+}
+
+impl ToJson for str
+{
+    fn json(&self, out: &mut String)
+    {
+        out.push('"');
+        for c in self.chars() {
+            match c {
+                '"'  => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                '\n' => out.push_str("\\n"),
+                '\r' => out.push_str("\\r"),
+                '\t' => out.push_str("\\t"),
+                c if (c as u32) < 0x20 => { let _ = write!(out, "\\u{:04x}", c as u32); }
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+}
+
+impl ToJson for String
+{
+    fn json(&self, out: &mut String)
+    {
+        self.as_str().json(out);
+    }
+}
+
+impl<T: ToJson> ToJson for [T]
+{
+    fn json(&self, out: &mut String)
+    {
+        out.push('[');
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            value.json(out);
+        }
+        out.push(']');
+    }
+}
+
+impl<T: ToJson, const N: usize> ToJson for [T; N]
+{
+    fn json(&self, out: &mut String)
+    {
+        self.as_slice().json(out);
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T>
+{
+    fn json(&self, out: &mut String)
+    {
+        self.as_slice().json(out);
+    }
+}
+
+impl<T: ToJson> ToJson for Option<T>
+{
+    fn json(&self, out: &mut String)
+    {
+        match self {
+            Some(value) => value.json(out),
+            None => out.push_str("null"),
+        }
+    }
+}
+
+macro_rules! number {
+    ($($t:ty),*) => {$(
+        impl ToJson for $t
+        {
+            fn json(&self, out: &mut String)
+            {
+                let _ = write!(out, "{self}");
+            }
+        }
+    )*};
+}
+
+number!(u8, i8, u16, i16, u32, u64, usize);
+
+impl ToJson for bool
+{
+    fn json(&self, out: &mut String)
+    {
+        out.push_str(if *self { "true" } else { "false" });
+    }
+}
+
+macro_rules! float {
+    ($($t:ty),*) => {$(
+        impl ToJson for $t
+        {
+            // JSON has no NaN or infinity.
+            fn json(&self, out: &mut String)
+            {
+                if self.is_finite() {
+                    let _ = write!(out, "{self}");
+                } else {
+                    out.push_str("null");
+                }
+            }
+        }
+    )*};
+}
+
+float!(f32, f64);
+
+// Enums go out as their variant name.
+macro_rules! name {
+    ($($t:ty),*) => {$(
+        impl ToJson for $t
+        {
+            fn json(&self, out: &mut String)
+            {
+                format!("{self:?}").json(out);
+            }
+        }
+    )*};
+}
+
+name!(
+    PacketId, ZoneFlag, Session, Weather, Temperature, Formula, SLIPro, NetworkGame, Accuracy,
+    Assist, SafetyCar, SessionLength, PitStatus, CarState, ResultStatus, EventType, Gear, MFDPanel,
+    TC, FuelMix, ActualCompound, VisualCompound, ErsDeployMode, ReadyStatus, Valid, TeamId,
+    TrackId, GameMode, RuleSet, Surface, PenaltyType, InfringementType
+);
+
+macro_rules! object {
+    ($t:ty { $($field:ident),* $(,)? }) => {
+        impl ToJson for $t
+        {
+            fn json(&self, out: &mut String)
+            {
+                let mut object = Object::new(out);
+                $( object.field(stringify!($field), &{ self.$field }); )*
+                object.end();
+            }
+        }
+    };
+}
+
+// Names are nul padded and may be cut mid character, so never trust them as UTF-8.
+pub(super) fn name(bytes: &[u8]) -> String
+{
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+// Drivers and nationalities by name, "Driver 250" for drivers the appendix doesn't know.
+impl ToJson for DriverId
+{
+    fn json(&self, out: &mut String)
+    {
+        match self.name() {
+            Some(name) => name.json(out),
+            None => format!("Driver {}", self.id).json(out),
+        }
+    }
+}
+
+impl ToJson for Nationality
+{
+    fn json(&self, out: &mut String)
+    {
+        self.name().json(out);
+    }
+}
+
+//
+// Header
+//
+
+// sessionUID goes out as a string, jq and JavaScript would round a u64 to a double.
+impl ToJson for Header
+{
+    fn json(&self, out: &mut String)
+    {
+        Object::new(out)
+            .field("packetFormat", &{ self.packetFormat })
+            .field("gameYear", &{ self.gameYear })
+            .field("gameMajorVersion", &{ self.gameMajorVersion })
+            .field("gameMinorVersion", &{ self.gameMinorVersion })
+            .field("packetVersion", &{ self.packetVersion })
+            .field("packetId", &{ self.packetId })
+            .field("sessionUID", &{ self.sessionUID }.to_string())
+            .field("sessionTime", &{ self.sessionTime })
+            .field("frameIdentifier", &{ self.frameIdentifier })
+            .field("overallFrameIdentifier", &{ self.overallFrameIdentifier })
+            .field("playerCarIndex", &{ self.playerCarIndex })
+            .field("secondaryPlayerCarIndex", &{ self.secondaryPlayerCarIndex })
+            .end();
+    }
+}
+
+//
+// Motion
+//
+
+object!(Vector { X, Y, Z });
+object!(Direction { X, Y, Z });
+object!(Forces { Lateral, Longitudinal, Vertical });
+object!(Wheels { RL, RR, FL, FR });
+object!(CarMotion { worldPosition, worldVelocity, worldForward, worldRight, gForce, yaw, pitch, roll });
+object!(PacketMotion { header, carMotion });
+
+//
+// Session
+//
+
+object!(SessionTime { time });
+object!(MarshalZone { zoneStart, zoneFlag });
+object!(WeatherForecast {
+    sessionType, timeOffset, weather, trackTemperature, trackChange, airTemperature, airChange,
+    rainPercentage,
+});
+
+impl ToJson for PacketSession
+{
+    fn json(&self, out: &mut String)
+    {
+        // Only the zones and forecasts in use, the rest of the arrays is padding.
+        let zones = &self.marshalZones[..(self.numMarshalZones as usize).min(self.marshalZones.len())];
+        let forecasts = &self.weatherForecast[..(self.numWeatherForecasts as usize).min(self.weatherForecast.len())];
+        let weekend = &self.weekendStructure[..(self.numSessionsInWeekend as usize).min(self.weekendStructure.len())];
+
+        let mut object = Object::new(out);
+        object
+            .field("header", &{ self.header })
+            .field("weather", &{ self.weather })
+            .field("trackTemperature", &{ self.trackTemperature })
+            .field("airTemperature", &{ self.airTemperature })
+            .field("totalLaps", &{ self.totalLaps })
+            .field("trackLength", &{ self.trackLength })
+            .field("sessionType", &{ self.sessionType })
+            .field("trackId", &{ self.trackId })
+            .field("formula", &{ self.formula })
+            .field("sessionTimeLeft", &{ self.sessionTimeLeft })
+            .field("sessionDuration", &{ self.sessionDuration })
+            .field("pitSpeedLimit", &{ self.pitSpeedLimit })
+            .field("gamePaused", &{ self.gamePaused })
+            .field("isSpectating", &{ self.isSpectating })
+            .field("spectatorCarIndex", &{ self.spectatorCarIndex })
+            .field("sliProNativeSupport", &{ self.sliProNativeSupport })
+            .field("numMarshalZones", &{ self.numMarshalZones })
+            .field("marshalZones", zones)
+            .field("safetyCarStatus", &{ self.safetyCarStatus })
+            .field("networkGame", &{ self.networkGame })
+            .field("numWeatherForecasts", &{ self.numWeatherForecasts })
+            .field("weatherForecast", forecasts)
+            .field("forecastAccuracy", &{ self.forecastAccuracy })
+            .field("aiDifficulty", &{ self.aiDifficulty })
+            .field("seasonLinkIdentifier", &{ self.seasonLinkIdentifier })
+            .field("weekendLinkIdentifier", &{ self.weekendLinkIdentifier })
+            .field("sessionLinkIdentifier", &{ self.sessionLinkIdentifier })
+            .field("pitStopWindowIdealLap", &{ self.pitStopWindowIdealLap })
+            .field("pitStopWindowLatestLap", &{ self.pitStopWindowLatestLap })
+            .field("pitStopRejoinPosition", &{ self.pitStopRejoinPosition })
+            .field("steeringAssist", &{ self.steeringAssist })
+            .field("brakingAssist", &{ self.brakingAssist })
+            .field("gearboxAssist", &{ self.gearboxAssist })
+            .field("pitAssist", &{ self.pitAssist })
+            .field("pitReleaseAssist", &{ self.pitReleaseAssist })
+            .field("ERSAssist", &{ self.ERSAssist })
+            .field("DRSAssist", &{ self.DRSAssist })
+            .field("dynamicRacingLine", &{ self.dynamicRacingLine })
+            .field("dynamicRacingLineType", &{ self.dynamicRacingLineType })
+            .field("gameMode", &{ self.gameMode })
+            .field("ruleSet", &{ self.ruleSet })
+            .field("timeOfDay", &{ self.timeOfDay })
+            .field("sessionLength", &{ self.sessionLength })
+            .field("speedUnitsLeadPlayer", &{ self.speedUnitsLeadPlayer })
+            .field("temperatureUnitsLeadPlayer", &{ self.temperatureUnitsLeadPlayer })
+            .field("speedUnitsSecondaryPlayer", &{ self.speedUnitsSecondaryPlayer })
+            .field("temperatureUnitsSecondaryPlayer", &{ self.temperatureUnitsSecondaryPlayer })
+            .field("numSafetyCarPeriods", &{ self.numSafetyCarPeriods })
+            .field("numVirtualSafetyCarPeriods", &{ self.numVirtualSafetyCarPeriods })
+            .field("numRedFlagPeriods", &{ self.numRedFlagPeriods })
+            .field("equalCarPerformance", &{ self.equalCarPerformance })
+            .field("recoveryMode", &{ self.recoveryMode })
+            .field("flashbackLimit", &{ self.flashbackLimit })
+            .field("surfaceType", &{ self.surfaceType })
+            .field("lowFuelMode", &{ self.lowFuelMode })
+            .field("raceStarts", &{ self.raceStarts })
+            .field("tyreTemperature", &{ self.tyreTemperature })
+            .field("pitLaneTyreSim", &{ self.pitLaneTyreSim })
+            .field("carDamage", &{ self.carDamage })
+            .field("carDamageRate", &{ self.carDamageRate })
+            .field("collisions", &{ self.collisions })
+            .field("collisionsOffForFirstLapOnly", &{ self.collisionsOffForFirstLapOnly })
+            .field("mpUnsafePitRelease", &{ self.mpUnsafePitRelease })
+            .field("mpOffForGriefing", &{ self.mpOffForGriefing })
+            .field("cornerCuttingStringency", &{ self.cornerCuttingStringency })
+            .field("parcFermeRules", &{ self.parcFermeRules })
+            .field("pitStopExperience", &{ self.pitStopExperience })
+            .field("safetyCar", &{ self.safetyCar })
+            .field("safetyCarExperience", &{ self.safetyCarExperience })
+            .field("formationLap", &{ self.formationLap })
+            .field("formationLapExperience", &{ self.formationLapExperience })
+            .field("redFlags", &{ self.redFlags })
+            .field("affectsLicenceLevelSolo", &{ self.affectsLicenceLevelSolo })
+            .field("affectsLicenceLevelMP", &{ self.affectsLicenceLevelMP })
+            .field("numSessionsInWeekend", &{ self.numSessionsInWeekend })
+            .field("weekendStructure", weekend)
+            .field("sector2LapDistanceStart", &{ self.sector2LapDistanceStart })
+            .field("sector3LapDistanceStart", &{ self.sector3LapDistanceStart })
+            .end();
+    }
+}
+
+//
+// Lap
+//
+
+object!(Car {
+    lastLapTimeInMS, currentLapTimeInMS, sector1TimeMSPart, sector1TimeMinutesPart,
+    sector2TimeMSPart, sector2TimeMinutesPart, deltaToCarInFrontMSPart,
+    deltaToCarInFrontMinutesPart, deltaToRaceLeaderMSPart, deltaToRaceLeaderMinutesPart,
+    lapDistance, totalDistance, safetyCarDelta, racePosition, currentLapNum, pitStatus,
+    numPitStops, sector, currentLapInvalid, penalties, totalWarnings, cornerCuttingWarnings,
+    numUnservedDriveThroughPens, numUnservedStopGoPens, gridPosition, driverStatus, resultStatus,
+    pitLaneTimerActive, pitLaneTimeInLaneInMS, pitStopTimerInMS, pitStopShouldServePen,
+    speedTrapFastestSpeed, speedTrapFastestLap,
+});
+object!(PacketLap { header, cars, timeTrialPBCarIdx, timeTrialRivalCarIdx });
+
+//
+// Event
+//
+
+object!(FastestLap { vehicleIdx, lapTime });
+object!(Retirement { vehicleIdx, reason });
+object!(DRSDisabled { reason });
+object!(TeamMateInPits { vehicleIdx });
+object!(RaceWinner { vehicleIdx });
+object!(Penalty {
+    penaltyType, infringementType, vehicleIdx, otherVehicleIdx, time, lapNum, placesGained,
+});
+object!(SpeedTrap {
+    vehicleIdx, speed, isOverallFastestInSession, isDriverFastestInSession,
+    fastestVehicleIdxInSession, fastestSpeedInSession,
+});
+object!(StartLights { numLights });
+object!(DriveThroughPenaltyServed { vehicleIdx });
+object!(StopGoPenaltyServed { vehicleIdx, stopTime });
+object!(Flashback { flashbackFrameIdentifier, flashbackSessionTime });
+object!(Buttons { buttonStatus });
+object!(Overtake { overtakingVehicleIdx, beingOvertakenVehicleIdx });
+object!(SafetyCarEvent { safetyCarType, eventType });
+object!(Collision { vehicle1Idx, vehicle2Idx });
+
+// Events with no details write an empty object.
+struct Empty;
+
+impl ToJson for Empty
+{
+    fn json(&self, out: &mut String)
+    {
+        Object::new(out).end();
+    }
+}
+
+impl ToJson for PacketEvent
+{
+    fn json(&self, out: &mut String)
+    {
+        let eventType = self.eventType();
+        let details = self.eventDetails;
+
+        let mut object = Object::new(out);
+        object
+            .field("header", &{ self.header })
+            .field("eventStringCode", &name(&{ self.eventStringCode }.tag))
+            .field("eventType", &eventType);
+
+        // SAFETY: eventType() names the member unpack wrote for this string code.
+        unsafe {
+            match eventType
+            {
+                EventType::FastestLap                => object.field("eventDetails", &{ details.fastestLap }),
+                EventType::Retirement                => object.field("eventDetails", &{ details.retirement }),
+                EventType::DRSDisabled               => object.field("eventDetails", &{ details.drsDisabled }),
+                EventType::TeamMateInPits            => object.field("eventDetails", &{ details.teamMateInPits }),
+                EventType::RaceWinner                => object.field("eventDetails", &{ details.raceWinner }),
+                EventType::Penalty                   => object.field("eventDetails", &{ details.penalty }),
+                EventType::SpeedTrap                 => object.field("eventDetails", &{ details.speedTrap }),
+                EventType::StartLights               => object.field("eventDetails", &{ details.startLights }),
+                EventType::DriveThroughPenaltyServed => object.field("eventDetails", &{ details.driveThroughPenaltyServed }),
+                EventType::StopGoPenaltyServed       => object.field("eventDetails", &{ details.stopGoPenaltyServed }),
+                EventType::Flashback                 => object.field("eventDetails", &{ details.flashback }),
+                EventType::Buttons                   => object.field("eventDetails", &{ details.buttons }),
+                EventType::Overtake                  => object.field("eventDetails", &{ details.overtake }),
+                EventType::SafetyCar                 => object.field("eventDetails", &{ details.safetyCar }),
+                EventType::Collision                 => object.field("eventDetails", &{ details.collision }),
+                _                                    => object.field("eventDetails", &Empty),
+            };
+        }
+
+        object.end();
+    }
+}
+
+//
+// Participants
+//
+
+object!(LiveryColour { r, g, b });
+
+impl ToJson for Participant
+{
+    fn json(&self, out: &mut String)
+    {
+        Object::new(out)
+            .field("aiControlled", &{ self.aiControlled })
+            .field("driverId", &{ self.driverId })
+            .field("networkId", &{ self.networkId })
+            .field("teamId", &{ self.teamId })
+            .field("myTeam", &{ self.myTeam })
+            .field("raceNumber", &{ self.raceNumber })
+            .field("nationality", &{ self.nationality })
+            .field("name", &name(&{ self.name }))
+            .field("yourTelemetry", &{ self.yourTelemetry })
+            .field("showOnlineNames", &{ self.showOnlineNames })
+            .field("techLevel", &{ self.techLevel })
+            .field("platform", &{ self.platform })
+            .field("numColours", &{ self.numColours })
+            .field("liveryColours", &{ self.liveryColours })
+            .end();
+    }
+}
+
+impl ToJson for PacketParticipants
+{
+    fn json(&self, out: &mut String)
+    {
+        let participants = &self.participants[..(self.numActiveCars as usize).min(self.participants.len())];
+
+        Object::new(out)
+            .field("header", &{ self.header })
+            .field("numActiveCars", &{ self.numActiveCars })
+            .field("participants", participants)
+            .end();
+    }
+}
+
+//
+// Car Setups
+//
+
+object!(CarSetup {
+    frontWing, rearWing, onThrottle, offThrottle, frontCamber, rearCamber, frontToe, rearToe,
+    frontSuspension, rearSuspension, frontAntiRollBar, rearAntiRollBar, frontSuspensionHeight,
+    rearSuspensionHeight, brakePressure, brakeBias, engineBraking, tyrePressure, ballast, fuelLoad,
+});
+object!(PacketCarSetups { header, carSetups, nextFrontWingValue });
+
+//
+// Car Telemetry
+//
+
+object!(Kph { kph });
+object!(RevLights { LEDs });
+object!(CarTelemetry {
+    speed, throttle, steer, brake, clutch, gear, engineRPM, drs, revLightsPercent,
+    revLightsBitValue, brakesTemperature, tyresSurfaceTemperature, tyresInnerTemperature,
+    engineTemperature, tyresPressure, surfaceType,
+});
+object!(PacketCarTelemetry { header, carTelemetry, mfdFirstPlayer, mfdSecondaryPlayer, suggestedGear });
+
+//
+// Car Status
+//
+
+object!(CarStatus {
+    tractionControl, antiLockBrakes, fuelMix, frontBrakeBias, pitLimiterStatus, fuelInTank,
+    fuelCapacity, fuelRemainingLaps, maxRPM, idleRPM, maxGears, drsAllowed, drsActivationDistance,
+    actualTyre, visualTyre, tyresAgeLaps, vehicleFiaFlags, enginePowerICE, enginePowerMGUK,
+    ersStoreEnergy, ersDeployMode, ersHarvestedThisLapMGUK, ersHarvestedThisLapMGUH,
+    ersDeployedThisLap, networkPaused,
+});
+object!(PacketCarStatus { header, carStatus });
+
+//
+// Final Classification
+//
+
+impl ToJson for FinalClassification
+{
+    fn json(&self, out: &mut String)
+    {
+        // Only the stints run, the other slots are padding.
+        let stints = (self.numTyreStints as usize).min(self.tyreStintsEndLaps.len());
+
+        Object::new(out)
+            .field("position", &{ self.position })
+            .field("numLaps", &{ self.numLaps })
+            .field("gridPosition", &{ self.gridPosition })
+            .field("points", &{ self.points })
+            .field("numPitStops", &{ self.numPitStops })
+            .field("resultStatus", &{ self.resultStatus })
+            .field("resultReason", &{ self.resultReason })
+            .field("bestLapTimeInMS", &{ self.bestLapTimeInMS })
+            .field("totalRaceTime", &{ self.totalRaceTime })
+            .field("penaltiesTime", &{ self.penaltiesTime })
+            .field("numPenalties", &{ self.numPenalties })
+            .field("numTyreStints", &{ self.numTyreStints })
+            .field("tyreStintsActual", &self.tyreStintsActual[..stints])
+            .field("tyreStintsVisual", &self.tyreStintsVisual[..stints])
+            .field("tyreStintsEndLaps", &self.tyreStintsEndLaps[..stints])
+            .end();
+    }
+}
+
+impl ToJson for PacketFinalClassification
+{
+    fn json(&self, out: &mut String)
+    {
+        let classification = &self.classificationData[..(self.numCars as usize).min(self.classificationData.len())];
+
+        Object::new(out)
+            .field("header", &{ self.header })
+            .field("numCars", &{ self.numCars })
+            .field("classificationData", classification)
+            .end();
+    }
+}
+
+//
+// Lobby Info
+//
+
+impl ToJson for LobbyInfo
+{
+    fn json(&self, out: &mut String)
+    {
+        Object::new(out)
+            .field("aiControlled", &{ self.aiControlled })
+            .field("teamId", &{ self.teamId })
+            .field("nationality", &{ self.nationality })
+            .field("platform", &{ self.platform })
+            .field("name", &name(&{ self.name }))
+            .field("carNumber", &{ self.carNumber })
+            .field("yourTelemetry", &{ self.yourTelemetry })
+            .field("showOnlineNames", &{ self.showOnlineNames })
+            .field("techLevel", &{ self.techLevel })
+            .field("readyStatus", &{ self.readyStatus })
+            .end();
+    }
+}
+
+impl ToJson for PacketLobbyInfo
+{
+    fn json(&self, out: &mut String)
+    {
+        let players = &self.lobbyPlayers[..(self.numPlayers as usize).min(self.lobbyPlayers.len())];
+
+        Object::new(out)
+            .field("header", &{ self.header })
+            .field("numPlayers", &{ self.numPlayers })
+            .field("lobbyPlayers", players)
+            .end();
+    }
+}
+
+//
+// Car Damage
+//
+
+object!(CarDamage {
+    tyresWear, tyresDamage, brakesDamage, tyreBlisters, frontLeftWingDamage, frontRightWingDamage,
+    rearWingDamage, floorDamage, diffuserDamage, sidepodDamage, drsFault, ersFault, gearBoxDamage,
+    engineDamage, engineMGUHWear, engineESWear, engineCEWear, engineICEWear, engineMGUKWear,
+    engineTCWear, engineBlown, engineSeized,
+});
+object!(PacketCarDamage { header, carDamageData });
+
+//
+// Session History
+//
+
+object!(LapHistory {
+    lapTimeInMS, sector1TimeMSPart, sector1TimeMinutesPart, sector2TimeMSPart,
+    sector2TimeMinutesPart, sector3TimeMSPart, sector3TimeMinutesPart, lapValidBitFlags,
+});
+object!(TyreStintHistory { endLap, tyreActualCompound, tyreVisualCompound });
+
+impl ToJson for PacketSessionHistory
+{
+    fn json(&self, out: &mut String)
+    {
+        // Only the laps and stints driven, not the 100 lap buffer.
+        let laps = &self.lapHistory[..(self.numLaps as usize).min(self.lapHistory.len())];
+        let stints = &self.tyreStintsHistory[..(self.numTyreStints as usize).min(self.tyreStintsHistory.len())];
+
+        Object::new(out)
+            .field("header", &{ self.header })
+            .field("carIdx", &{ self.carIdx })
+            .field("numLaps", &{ self.numLaps })
+            .field("numTyreStints", &{ self.numTyreStints })
+            .field("bestLapTimeLapNum", &{ self.bestLapTimeLapNum })
+            .field("bestSector1LapNum", &{ self.bestSector1LapNum })
+            .field("bestSector2LapNum", &{ self.bestSector2LapNum })
+            .field("bestSector3LapNum", &{ self.bestSector3LapNum })
+            .field("lapHistory", laps)
+            .field("tyreStintsHistory", stints)
+            .end();
+    }
+}
+
+//
+// Tyre Sets
+//
+
+object!(TyreSet {
+    actualCompound, visualCompound, wear, available, recommendedSession, lifeSpan, usableLife,
+    lapDeltaTime, fitted,
+});
+object!(TyreSetsData { header, carIdx, tyreSetData, fittedIdx });
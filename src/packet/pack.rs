@@ -0,0 +1,984 @@
+use super::*;
+
+//
+// # Packing
+// The reverse of unpack: each type writes itself back out in wire order, so an
+// unpacked datagram packs to the same bytes it came from. Appendix ids keep the
+// byte they came with, known or not. The enums in packet.rs that unpacked to
+// Poisoned write that value back, not the original byte.
+//
+
+impl Header
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.packetFormat }.to_le_bytes());
+        bytes.push(self.gameYear);
+        bytes.push(self.gameMajorVersion);
+        bytes.push(self.gameMinorVersion);
+        bytes.push(self.packetVersion);
+        bytes.push(self.packetId as u8);
+        bytes.extend({ self.sessionUID }.to_le_bytes());
+        bytes.extend({ self.sessionTime }.to_le_bytes());
+        bytes.extend({ self.frameIdentifier }.to_le_bytes());
+        bytes.extend({ self.overallFrameIdentifier }.to_le_bytes());
+        bytes.push(self.playerCarIndex);
+        bytes.push(self.secondaryPlayerCarIndex);
+    }
+}
+
+//
+// Motion
+//
+
+impl Vector
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.X }.to_le_bytes());
+        bytes.extend({ self.Y }.to_le_bytes());
+        bytes.extend({ self.Z }.to_le_bytes());
+    }
+}
+
+impl Direction
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.X }.to_le_bytes());
+        bytes.extend({ self.Y }.to_le_bytes());
+        bytes.extend({ self.Z }.to_le_bytes());
+    }
+}
+
+impl Forces
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.Lateral }.to_le_bytes());
+        bytes.extend({ self.Longitudinal }.to_le_bytes());
+        bytes.extend({ self.Vertical }.to_le_bytes());
+    }
+}
+
+impl Wheels
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.RL }.to_le_bytes());
+        bytes.extend({ self.RR }.to_le_bytes());
+        bytes.extend({ self.FL }.to_le_bytes());
+        bytes.extend({ self.FR }.to_le_bytes());
+    }
+}
+
+impl CarMotion
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.worldPosition }.pack(bytes);
+        { self.worldVelocity }.pack(bytes);
+        { self.worldForward }.pack(bytes);
+        { self.worldRight }.pack(bytes);
+        { self.gForce }.pack(bytes);
+        bytes.extend({ self.yaw }.to_le_bytes());
+        bytes.extend({ self.pitch }.to_le_bytes());
+        bytes.extend({ self.roll }.to_le_bytes());
+    }
+}
+
+impl PacketMotion
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        for cm in self.carMotion {
+            cm.pack(bytes);
+        }
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Session
+//
+
+impl MarshalZone
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.zoneStart }.to_le_bytes());
+        bytes.push(self.zoneFlag as i8 as u8);
+    }
+}
+
+impl WeatherForecast
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.sessionType as u8);
+        bytes.push(self.timeOffset);
+        bytes.push(self.weather as u8);
+        bytes.push(self.trackTemperature as u8);
+        bytes.push(self.trackChange as i8 as u8);
+        bytes.push(self.airTemperature as u8);
+        bytes.push(self.airChange as i8 as u8);
+        bytes.push(self.rainPercentage);
+    }
+}
+
+impl SessionTime
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.time }.to_le_bytes());
+    }
+}
+
+impl PacketSession
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        bytes.push(self.weather as u8);
+        bytes.push(self.trackTemperature as u8);
+        bytes.push(self.airTemperature as u8);
+        bytes.push(self.totalLaps);
+        bytes.extend({ self.trackLength }.to_le_bytes());
+        bytes.push(self.sessionType as u8);
+        bytes.push(self.trackId.id as u8);
+        bytes.push(self.formula as u8);
+        { self.sessionTimeLeft }.pack(bytes);
+        { self.sessionDuration }.pack(bytes);
+        bytes.push(self.pitSpeedLimit);
+        bytes.push(self.gamePaused);
+        bytes.push(self.isSpectating);
+        bytes.push(self.spectatorCarIndex);
+        bytes.push(self.sliProNativeSupport as u8);
+        bytes.push(self.numMarshalZones);
+        for mz in self.marshalZones {
+            mz.pack(bytes);
+        }
+        bytes.push(self.safetyCarStatus as u8);
+        bytes.push(self.networkGame as u8);
+        bytes.push(self.numWeatherForecasts);
+        for wf in self.weatherForecast {
+            wf.pack(bytes);
+        }
+        bytes.push(self.forecastAccuracy as u8);
+        bytes.push(self.aiDifficulty);
+        bytes.extend({ self.seasonLinkIdentifier }.to_le_bytes());
+        bytes.extend({ self.weekendLinkIdentifier }.to_le_bytes());
+        bytes.extend({ self.sessionLinkIdentifier }.to_le_bytes());
+        bytes.push(self.pitStopWindowIdealLap);
+        bytes.push(self.pitStopWindowLatestLap);
+        bytes.push(self.pitStopRejoinPosition);
+        bytes.push(self.steeringAssist as u8);
+        bytes.push(self.brakingAssist);
+        bytes.push(self.gearboxAssist);
+        bytes.push(self.pitAssist as u8);
+        bytes.push(self.pitReleaseAssist as u8);
+        bytes.push(self.ERSAssist as u8);
+        bytes.push(self.DRSAssist as u8);
+        bytes.push(self.dynamicRacingLine);
+        bytes.push(self.dynamicRacingLineType);
+        bytes.push(self.gameMode.id);
+        bytes.push(self.ruleSet.id);
+        bytes.extend({ self.timeOfDay }.to_le_bytes());
+        bytes.push(self.sessionLength as u8);
+        bytes.extend([
+            self.speedUnitsLeadPlayer,
+            self.temperatureUnitsLeadPlayer,
+            self.speedUnitsSecondaryPlayer,
+            self.temperatureUnitsSecondaryPlayer,
+            self.numSafetyCarPeriods,
+            self.numVirtualSafetyCarPeriods,
+            self.numRedFlagPeriods,
+            self.equalCarPerformance as u8,
+            self.recoveryMode,
+            self.flashbackLimit,
+            self.surfaceType,
+            self.lowFuelMode,
+            self.raceStarts,
+            self.tyreTemperature,
+            self.pitLaneTyreSim,
+            self.carDamage,
+            self.carDamageRate,
+            self.collisions,
+            self.collisionsOffForFirstLapOnly,
+            self.mpUnsafePitRelease,
+            self.mpOffForGriefing,
+            self.cornerCuttingStringency,
+            self.parcFermeRules,
+            self.pitStopExperience,
+            self.safetyCar,
+            self.safetyCarExperience,
+            self.formationLap,
+            self.formationLapExperience,
+            self.redFlags,
+            self.affectsLicenceLevelSolo,
+            self.affectsLicenceLevelMP,
+            self.numSessionsInWeekend,
+        ]);
+        bytes.extend({ self.weekendStructure }.map(|session| session as u8));
+        bytes.extend({ self.sector2LapDistanceStart }.to_le_bytes());
+        bytes.extend({ self.sector3LapDistanceStart }.to_le_bytes());
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Lap Data
+//
+
+impl Car
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.lastLapTimeInMS }.to_le_bytes());
+        bytes.extend({ self.currentLapTimeInMS }.to_le_bytes());
+        bytes.extend({ self.sector1TimeMSPart }.to_le_bytes());
+        bytes.push(self.sector1TimeMinutesPart);
+        bytes.extend({ self.sector2TimeMSPart }.to_le_bytes());
+        bytes.push(self.sector2TimeMinutesPart);
+        bytes.extend({ self.deltaToCarInFrontMSPart }.to_le_bytes());
+        bytes.push(self.deltaToCarInFrontMinutesPart);
+        bytes.extend({ self.deltaToRaceLeaderMSPart }.to_le_bytes());
+        bytes.push(self.deltaToRaceLeaderMinutesPart);
+        bytes.extend({ self.lapDistance }.to_le_bytes());
+        bytes.extend({ self.totalDistance }.to_le_bytes());
+        bytes.extend({ self.safetyCarDelta }.to_le_bytes());
+        bytes.extend([
+            self.racePosition,
+            self.currentLapNum,
+            self.pitStatus as u8,
+            self.numPitStops,
+            self.sector,
+            self.currentLapInvalid,
+            self.penalties,
+            self.totalWarnings,
+            self.cornerCuttingWarnings,
+            self.numUnservedDriveThroughPens,
+            self.numUnservedStopGoPens,
+            self.gridPosition,
+            self.driverStatus as u8,
+            self.resultStatus as u8,
+            self.pitLaneTimerActive,
+        ]);
+        bytes.extend({ self.pitLaneTimeInLaneInMS }.to_le_bytes());
+        bytes.extend({ self.pitStopTimerInMS }.to_le_bytes());
+        bytes.push(self.pitStopShouldServePen);
+        bytes.extend({ self.speedTrapFastestSpeed }.to_le_bytes());
+        bytes.push(self.speedTrapFastestLap);
+    }
+}
+
+impl PacketLap
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        for car in self.cars {
+            car.pack(bytes);
+        }
+
+        bytes.push(self.timeTrialPBCarIdx);
+        bytes.push(self.timeTrialRivalCarIdx);
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Events
+// Only the live member of EventDetails is written, the rest of the 12 bytes are zeroed.
+//
+
+impl FastestLap
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.vehicleIdx);
+        bytes.extend({ self.lapTime }.to_le_bytes());
+    }
+}
+
+impl Retirement
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.vehicleIdx);
+        bytes.push(self.reason);
+    }
+}
+
+impl DRSDisabled
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.reason);
+    }
+}
+
+impl TeamMateInPits
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.vehicleIdx);
+    }
+}
+
+impl RaceWinner
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.vehicleIdx);
+    }
+}
+
+impl Penalty
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend([
+            self.penaltyType.id,
+            self.infringementType.id,
+            self.vehicleIdx,
+            self.otherVehicleIdx,
+            self.time,
+            self.lapNum,
+            self.placesGained,
+        ]);
+    }
+}
+
+impl SpeedTrap
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.vehicleIdx);
+        bytes.extend({ self.speed }.to_le_bytes());
+        bytes.push(self.isOverallFastestInSession);
+        bytes.push(self.isDriverFastestInSession);
+        bytes.push(self.fastestVehicleIdxInSession);
+        bytes.extend({ self.fastestSpeedInSession }.to_le_bytes());
+    }
+}
+
+impl StartLights
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.numLights);
+    }
+}
+
+impl DriveThroughPenaltyServed
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.vehicleIdx);
+    }
+}
+
+impl StopGoPenaltyServed
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.vehicleIdx);
+        bytes.extend({ self.stopTime }.to_le_bytes());
+    }
+}
+
+impl Flashback
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.flashbackFrameIdentifier }.to_le_bytes());
+        bytes.extend({ self.flashbackSessionTime }.to_le_bytes());
+    }
+}
+
+impl Buttons
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.buttonStatus }.to_le_bytes());
+    }
+}
+
+impl Overtake
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.overtakingVehicleIdx);
+        bytes.push(self.beingOvertakenVehicleIdx);
+    }
+}
+
+impl SafetyCarEvent
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.safetyCarType as u8);
+        bytes.push(self.eventType);
+    }
+}
+
+impl Collision
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.push(self.vehicle1Idx);
+        bytes.push(self.vehicle2Idx);
+    }
+}
+
+impl EventTag
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend(self.tag);
+    }
+}
+
+impl PacketEvent
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+        { self.eventStringCode }.pack(bytes);
+
+        let start = bytes.len();
+        let details = self.eventDetails;
+
+        // SAFETY: eventType() names the member unpack wrote for this string code.
+        unsafe {
+            match self.eventType()
+            {
+                EventType::FastestLap                => details.fastestLap.pack(bytes),
+                EventType::Retirement                => details.retirement.pack(bytes),
+                EventType::DRSDisabled               => details.drsDisabled.pack(bytes),
+                EventType::TeamMateInPits            => details.teamMateInPits.pack(bytes),
+                EventType::RaceWinner                => details.raceWinner.pack(bytes),
+                EventType::Penalty                   => details.penalty.pack(bytes),
+                EventType::SpeedTrap                 => details.speedTrap.pack(bytes),
+                EventType::StartLights               => details.startLights.pack(bytes),
+                EventType::DriveThroughPenaltyServed => details.driveThroughPenaltyServed.pack(bytes),
+                EventType::StopGoPenaltyServed       => details.stopGoPenaltyServed.pack(bytes),
+                EventType::Flashback                 => details.flashback.pack(bytes),
+                EventType::Buttons                   => details.buttons.pack(bytes),
+                EventType::Overtake                  => details.overtake.pack(bytes),
+                EventType::SafetyCar                 => details.safetyCar.pack(bytes),
+                EventType::Collision                 => details.collision.pack(bytes),
+                _ => {}
+            }
+        }
+
+        bytes.resize(start + size_of::<EventDetails>(), 0);
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Participants
+//
+
+impl LiveryColour
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend([self.r, self.g, self.b]);
+    }
+}
+
+impl Participant
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend([
+            self.aiControlled,
+            self.driverId.id,
+            self.networkId,
+            self.teamId.id,
+            self.myTeam,
+            self.raceNumber,
+            self.nationality.id,
+        ]);
+        bytes.extend(self.name);
+        bytes.push(self.yourTelemetry);
+        bytes.push(self.showOnlineNames);
+        bytes.extend({ self.techLevel }.to_le_bytes());
+        bytes.push(self.platform);
+        bytes.push(self.numColours);
+        for colour in self.liveryColours {
+            colour.pack(bytes);
+        }
+    }
+}
+
+impl PacketParticipants
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        bytes.push(self.numActiveCars);
+        for p in self.participants {
+            p.pack(bytes);
+        }
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Car Setups
+//
+
+impl CarSetup
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend([self.frontWing, self.rearWing, self.onThrottle, self.offThrottle]);
+        bytes.extend({ self.frontCamber }.to_le_bytes());
+        bytes.extend({ self.rearCamber }.to_le_bytes());
+        bytes.extend({ self.frontToe }.to_le_bytes());
+        bytes.extend({ self.rearToe }.to_le_bytes());
+        bytes.extend([
+            self.frontSuspension,
+            self.rearSuspension,
+            self.frontAntiRollBar,
+            self.rearAntiRollBar,
+            self.frontSuspensionHeight,
+            self.rearSuspensionHeight,
+            self.brakePressure,
+            self.brakeBias,
+            self.engineBraking,
+        ]);
+        { self.tyrePressure }.pack(bytes);
+        bytes.push(self.ballast);
+        bytes.extend({ self.fuelLoad }.to_le_bytes());
+    }
+}
+
+impl PacketCarSetups
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        for cs in self.carSetups {
+            cs.pack(bytes);
+        }
+        bytes.extend({ self.nextFrontWingValue }.to_le_bytes());
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Car Telemetry
+//
+
+impl Kph
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend(self.kph.to_le_bytes());
+    }
+}
+
+impl RevLights
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend(self.LEDs.to_le_bytes());
+    }
+}
+
+impl CarTelemetry
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.speed }.pack(bytes);
+        bytes.extend({ self.throttle }.to_le_bytes());
+        bytes.extend({ self.steer }.to_le_bytes());
+        bytes.extend({ self.brake }.to_le_bytes());
+        bytes.push(self.clutch);
+        bytes.push(self.gear as i8 as u8);
+        bytes.extend({ self.engineRPM }.to_le_bytes());
+        bytes.push(self.drs);
+        bytes.push(self.revLightsPercent);
+        { self.revLightsBitValue }.pack(bytes);
+        for temperature in self.brakesTemperature {
+            bytes.extend(temperature.to_le_bytes());
+        }
+        bytes.extend(self.tyresSurfaceTemperature);
+        bytes.extend(self.tyresInnerTemperature);
+        bytes.extend({ self.engineTemperature }.to_le_bytes());
+        for pressure in self.tyresPressure {
+            bytes.extend(pressure.to_le_bytes());
+        }
+        bytes.extend(self.surfaceType.map(|surface| surface.id));
+    }
+}
+
+impl PacketCarTelemetry
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        for ct in self.carTelemetry {
+            ct.pack(bytes);
+        }
+
+        bytes.push(self.mfdFirstPlayer as u8);
+        bytes.push(self.mfdSecondaryPlayer as u8);
+        bytes.push(self.suggestedGear as i8 as u8);
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Car Status
+//
+
+impl CarStatus
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend([
+            self.tractionControl as u8,
+            self.antiLockBrakes as u8,
+            self.fuelMix as u8,
+            self.frontBrakeBias,
+            self.pitLimiterStatus,
+        ]);
+        bytes.extend({ self.fuelInTank }.to_le_bytes());
+        bytes.extend({ self.fuelCapacity }.to_le_bytes());
+        bytes.extend({ self.fuelRemainingLaps }.to_le_bytes());
+        bytes.extend({ self.maxRPM }.to_le_bytes());
+        bytes.extend({ self.idleRPM }.to_le_bytes());
+        bytes.push(self.maxGears);
+        bytes.push(self.drsAllowed);
+        bytes.extend({ self.drsActivationDistance }.to_le_bytes());
+        bytes.extend([
+            self.actualTyre as u8,
+            self.visualTyre as u8,
+            self.tyresAgeLaps,
+            self.vehicleFiaFlags as i8 as u8,
+        ]);
+        bytes.extend({ self.enginePowerICE }.to_le_bytes());
+        bytes.extend({ self.enginePowerMGUK }.to_le_bytes());
+        bytes.extend({ self.ersStoreEnergy }.to_le_bytes());
+        bytes.push(self.ersDeployMode as u8);
+        bytes.extend({ self.ersHarvestedThisLapMGUK }.to_le_bytes());
+        bytes.extend({ self.ersHarvestedThisLapMGUH }.to_le_bytes());
+        bytes.extend({ self.ersDeployedThisLap }.to_le_bytes());
+        bytes.push(self.networkPaused);
+    }
+}
+
+impl PacketCarStatus
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        for cs in self.carStatus {
+            cs.pack(bytes);
+        }
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Final Classification
+//
+
+impl FinalClassification
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend([
+            self.position,
+            self.numLaps,
+            self.gridPosition,
+            self.points,
+            self.numPitStops,
+            self.resultStatus as u8,
+            self.resultReason,
+        ]);
+        bytes.extend({ self.bestLapTimeInMS }.to_le_bytes());
+        bytes.extend({ self.totalRaceTime }.to_le_bytes());
+        bytes.push(self.penaltiesTime);
+        bytes.push(self.numPenalties);
+        bytes.push(self.numTyreStints);
+        bytes.extend(self.tyreStintsActual.map(|tyre| tyre as u8));
+        bytes.extend(self.tyreStintsVisual.map(|tyre| tyre as u8));
+        bytes.extend(self.tyreStintsEndLaps);
+    }
+}
+
+impl PacketFinalClassification
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        bytes.push(self.numCars);
+        for fc in self.classificationData {
+            fc.pack(bytes);
+        }
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Lobby Info
+//
+
+impl LobbyInfo
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend([self.aiControlled, self.teamId.id, self.nationality.id, self.platform]);
+        bytes.extend(self.name);
+        bytes.push(self.carNumber);
+        bytes.push(self.yourTelemetry);
+        bytes.push(self.showOnlineNames);
+        bytes.extend({ self.techLevel }.to_le_bytes());
+        bytes.push(self.readyStatus as u8);
+    }
+}
+
+impl PacketLobbyInfo
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        bytes.push(self.numPlayers);
+        for li in self.lobbyPlayers {
+            li.pack(bytes);
+        }
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Car Damage
+//
+
+impl CarDamage
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.tyresWear }.pack(bytes);
+        bytes.extend(self.tyresDamage);
+        bytes.extend(self.brakesDamage);
+        bytes.extend(self.tyreBlisters);
+        bytes.extend([
+            self.frontLeftWingDamage,
+            self.frontRightWingDamage,
+            self.rearWingDamage,
+            self.floorDamage,
+            self.diffuserDamage,
+            self.sidepodDamage,
+            self.drsFault,
+            self.ersFault,
+            self.gearBoxDamage,
+            self.engineDamage,
+            self.engineMGUHWear,
+            self.engineESWear,
+            self.engineCEWear,
+            self.engineICEWear,
+            self.engineMGUKWear,
+            self.engineTCWear,
+            self.engineBlown,
+            self.engineSeized,
+        ]);
+    }
+}
+
+impl PacketCarDamage
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        for cd in self.carDamageData {
+            cd.pack(bytes);
+        }
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Session History
+//
+
+impl LapHistory
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend({ self.lapTimeInMS }.to_le_bytes());
+        bytes.extend({ self.sector1TimeMSPart }.to_le_bytes());
+        bytes.push(self.sector1TimeMinutesPart);
+        bytes.extend({ self.sector2TimeMSPart }.to_le_bytes());
+        bytes.push(self.sector2TimeMinutesPart);
+        bytes.extend({ self.sector3TimeMSPart }.to_le_bytes());
+        bytes.push(self.sector3TimeMinutesPart);
+        bytes.push(self.lapValidBitFlags as u8);
+    }
+}
+
+impl TyreStintHistory
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend([self.endLap, self.tyreActualCompound as u8, self.tyreVisualCompound as u8]);
+    }
+}
+
+impl PacketSessionHistory
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        bytes.extend([
+            self.carIdx,
+            self.numLaps,
+            self.numTyreStints,
+            self.bestLapTimeLapNum,
+            self.bestSector1LapNum,
+            self.bestSector2LapNum,
+            self.bestSector3LapNum,
+        ]);
+        for lh in self.lapHistory {
+            lh.pack(bytes);
+        }
+        for tsh in self.tyreStintsHistory {
+            tsh.pack(bytes);
+        }
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
+
+//
+// Tyre Sets
+//
+
+impl TyreSet
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        bytes.extend([
+            self.actualCompound as u8,
+            self.visualCompound as u8,
+            self.wear,
+            self.available,
+            self.recommendedSession as u8,
+            self.lifeSpan,
+            self.usableLife,
+        ]);
+        bytes.extend({ self.lapDeltaTime }.to_le_bytes());
+        bytes.push(self.fitted);
+    }
+}
+
+impl TyreSetsData
+{
+    pub fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        { self.header }.pack(bytes);
+
+        bytes.push(self.carIdx);
+        for ts in self.tyreSetData {
+            ts.pack(bytes);
+        }
+        bytes.push(self.fittedIdx);
+    }
+
+    pub fn to_bytes(self) -> Vec<u8>
+    {
+        let mut bytes = Vec::with_capacity(size_of::<Self>());
+        self.pack(&mut bytes);
+        bytes
+    }
+}
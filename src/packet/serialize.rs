@@ -0,0 +1,169 @@
+use super::*;
+use serde::de::Error;
+use serde::{Deserializer, Serializer};
+
+//
+// # Serde
+// Everything else derives. These cover what can't: nul padded names go out as
+// strings, arrays longer than serde's 32 go out as sequences, and the event union
+// goes out as an enum of whichever details the string code says were sent.
+//
+
+// Nul padded UTF-8 names, e.g. Participant.name and EventTag.tag.
+pub mod name
+{
+    use super::*;
+
+    pub fn serialize<S: Serializer, const N: usize>(bytes: &[u8; N], s: S) -> Result<S::Ok, S::Error>
+    {
+        s.serialize_str(&json::name(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(d: D) -> Result<[u8; N], D::Error>
+    {
+        let name = String::deserialize(d)?;
+        if name.len() > N {
+            return Err(D::Error::custom(format!("'{name}' is longer than {N} bytes")));
+        }
+
+        let mut bytes = [0; N];
+        bytes[..name.len()].copy_from_slice(name.as_bytes());
+        Ok(bytes)
+    }
+}
+
+// Arrays longer than serde's derives go up to, e.g. PacketSessionHistory.lapHistory.
+pub mod array
+{
+    use super::*;
+
+    pub fn serialize<S: Serializer, T: Serialize, const N: usize>(array: &[T; N], s: S) -> Result<S::Ok, S::Error>
+    {
+        s.collect_seq(array)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>, const N: usize>(d: D) -> Result<[T; N], D::Error>
+    {
+        let items = Vec::<T>::deserialize(d)?;
+        let len = items.len();
+        items
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(len, &format!("{N} elements").as_str()))
+    }
+}
+
+//
+// Event
+//
+
+#[derive(Serialize, Deserialize)]
+enum Details
+{
+    FastestLap(FastestLap),
+    Retirement(Retirement),
+    DRSDisabled(DRSDisabled),
+    TeamMateInPits(TeamMateInPits),
+    RaceWinner(RaceWinner),
+    Penalty(Penalty),
+    SpeedTrap(SpeedTrap),
+    StartLights(StartLights),
+    DriveThroughPenaltyServed(DriveThroughPenaltyServed),
+    StopGoPenaltyServed(StopGoPenaltyServed),
+    Flashback(Flashback),
+    Buttons(Buttons),
+    Overtake(Overtake),
+    SafetyCar(SafetyCarEvent),
+    Collision(Collision),
+    None,                               // The string code says it all
+}
+
+impl Details
+{
+    fn pack(&self, bytes: &mut Vec<u8>)
+    {
+        match self
+        {
+            Details::FastestLap(d)                => d.pack(bytes),
+            Details::Retirement(d)                => d.pack(bytes),
+            Details::DRSDisabled(d)               => d.pack(bytes),
+            Details::TeamMateInPits(d)            => d.pack(bytes),
+            Details::RaceWinner(d)                => d.pack(bytes),
+            Details::Penalty(d)                   => d.pack(bytes),
+            Details::SpeedTrap(d)                 => d.pack(bytes),
+            Details::StartLights(d)               => d.pack(bytes),
+            Details::DriveThroughPenaltyServed(d) => d.pack(bytes),
+            Details::StopGoPenaltyServed(d)       => d.pack(bytes),
+            Details::Flashback(d)                 => d.pack(bytes),
+            Details::Buttons(d)                   => d.pack(bytes),
+            Details::Overtake(d)                  => d.pack(bytes),
+            Details::SafetyCar(d)                 => d.pack(bytes),
+            Details::Collision(d)                 => d.pack(bytes),
+            Details::None                         => {}
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "PacketEvent")]
+struct Event
+{
+    header: Header,
+    eventStringCode: EventTag,
+    eventDetails: Details,
+}
+
+impl Serialize for PacketEvent
+{
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error>
+    {
+        let details = self.eventDetails;
+
+        // SAFETY: eventType() names the member unpack wrote for this string code.
+        let eventDetails = unsafe {
+            match self.eventType()
+            {
+                EventType::FastestLap                => Details::FastestLap(details.fastestLap),
+                EventType::Retirement                => Details::Retirement(details.retirement),
+                EventType::DRSDisabled               => Details::DRSDisabled(details.drsDisabled),
+                EventType::TeamMateInPits            => Details::TeamMateInPits(details.teamMateInPits),
+                EventType::RaceWinner                => Details::RaceWinner(details.raceWinner),
+                EventType::Penalty                   => Details::Penalty(details.penalty),
+                EventType::SpeedTrap                 => Details::SpeedTrap(details.speedTrap),
+                EventType::StartLights               => Details::StartLights(details.startLights),
+                EventType::DriveThroughPenaltyServed => Details::DriveThroughPenaltyServed(details.driveThroughPenaltyServed),
+                EventType::StopGoPenaltyServed       => Details::StopGoPenaltyServed(details.stopGoPenaltyServed),
+                EventType::Flashback                 => Details::Flashback(details.flashback),
+                EventType::Buttons                   => Details::Buttons(details.buttons),
+                EventType::Overtake                  => Details::Overtake(details.overtake),
+                EventType::SafetyCar                 => Details::SafetyCar(details.safetyCar),
+                EventType::Collision                 => Details::Collision(details.collision),
+                _                                    => Details::None,
+            }
+        };
+
+        Event {
+            header: self.header,
+            eventStringCode: self.eventStringCode,
+            eventDetails,
+        }
+        .serialize(s)
+    }
+}
+
+// Packed back into a datagram and unpacked again, so the union ends up holding
+// exactly what unpack would have put there for this string code.
+impl<'de> Deserialize<'de> for PacketEvent
+{
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error>
+    {
+        let event = Event::deserialize(d)?;
+
+        let mut bytes = Vec::with_capacity(size_of::<PacketEvent>());
+        event.header.pack(&mut bytes);
+        event.eventStringCode.pack(&mut bytes);
+        event.eventDetails.pack(&mut bytes);
+        bytes.resize(size_of::<PacketEvent>(), 0);
+
+        Ok(PacketEvent::unpack(&bytes))
+    }
+}
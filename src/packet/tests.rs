@@ -0,0 +1,1052 @@
+//
+// # Golden Packets
+// Each fixture is laid out field by field in specification order, independently
+// of the offsets used by unpack, so any drift between the two shows up here.
+//
+use super::*;
+
+#[derive(Default)]
+struct Bytes(Vec<u8>);
+
+impl Bytes
+{
+    fn u8(&mut self, value: u8) -> &mut Self
+    {
+        self.0.push(value);
+        self
+    }
+
+    fn i8(&mut self, value: i8) -> &mut Self
+    {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn u16(&mut self, value: u16) -> &mut Self
+    {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn i16(&mut self, value: i16) -> &mut Self
+    {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn u32(&mut self, value: u32) -> &mut Self
+    {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn u64(&mut self, value: u64) -> &mut Self
+    {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn f32(&mut self, value: f32) -> &mut Self
+    {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn f64(&mut self, value: f64) -> &mut Self
+    {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn raw(&mut self, value: &[u8]) -> &mut Self
+    {
+        self.0.extend(value);
+        self
+    }
+
+    // A null padded string field.
+    fn name(&mut self, value: &str, len: usize) -> &mut Self
+    {
+        let mut name = value.as_bytes().to_vec();
+        name.resize(len, 0);
+        self.raw(&name)
+    }
+
+    fn header(packetId: u8) -> Self
+    {
+        let mut b = Self::default();
+        b.u16(2025)
+         .u8(25)
+         .u8(1)
+         .u8(9)
+         .u8(1)
+         .u8(packetId)
+         .u64(0x0123_4567_89AB_CDEF)
+         .f32(123.5)
+         .u32(4242)
+         .u32(4343)
+         .u8(19)
+         .u8(255);
+        b
+    }
+}
+
+fn assert_header(header: &Header, packetId: u8)
+{
+    assert_eq!({ header.packetFormat }, 2025);
+    assert_eq!(header.gameYear, 25);
+    assert_eq!(header.gameMajorVersion, 1);
+    assert_eq!(header.gameMinorVersion, 9);
+    assert_eq!(header.packetVersion, 1);
+    assert_eq!(header.packetId as u8, packetId);
+    assert_eq!({ header.sessionUID }, 0x0123_4567_89AB_CDEF);
+    assert_eq!({ header.sessionTime }, 123.5);
+    assert_eq!({ header.frameIdentifier }, 4242);
+    assert_eq!({ header.overallFrameIdentifier }, 4343);
+    assert_eq!(header.playerCarIndex, 19);
+    assert_eq!(header.secondaryPlayerCarIndex, 255);
+    assert_eq!(header.get_version(), "1.09");
+}
+
+#[test]
+fn header()
+{
+    let b = Bytes::header(PacketId::Lap as u8);
+    assert_eq!(b.0.len(), 29);
+
+    let header = Header::unpack(&b.0);
+    assert_header(&header, 2);
+
+    let mut bytes = Vec::new();
+    header.pack(&mut bytes);
+    assert_eq!(bytes, b.0);
+}
+
+#[test]
+fn motion()
+{
+    let mut b = Bytes::header(0);
+    for i in 0..22 {
+        let f = i as f32;
+        let d = i as i16;
+        b.f32(f).f32(f + 0.5).f32(-f)
+         .f32(f * 2.0).f32(f * 3.0).f32(f * 4.0)
+         .i16(d * 100).i16(-d * 100).i16(32767)
+         .i16(d).i16(-d).i16(-32767)
+         .f32(f + 0.25).f32(f + 0.75).f32(1.0)
+         .f32(f / 8.0).f32(-f / 8.0).f32(f / 16.0);
+    }
+    assert_eq!(b.0.len(), 1349);
+
+    let p = PacketMotion::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 0);
+    for (i, car) in { p.carMotion }.iter().enumerate() {
+        let f = i as f32;
+        let d = i as i16;
+        let (position, velocity) = ({ car.worldPosition }, { car.worldVelocity });
+        let (forward, right, force) = ({ car.worldForward }, { car.worldRight }, { car.gForce });
+        assert_eq!(({ position.X }, { position.Y }, { position.Z }), (f, f + 0.5, -f));
+        assert_eq!(({ velocity.X }, { velocity.Y }, { velocity.Z }), (f * 2.0, f * 3.0, f * 4.0));
+        assert_eq!(({ forward.X }, { forward.Y }, { forward.Z }), (d * 100, -d * 100, 32767));
+        assert_eq!(({ right.X }, { right.Y }, { right.Z }), (d, -d, -32767));
+        assert_eq!(({ force.Lateral }, { force.Longitudinal }, { force.Vertical }), (f + 0.25, f + 0.75, 1.0));
+        assert_eq!(({ car.yaw }, { car.pitch }, { car.roll }), (f / 8.0, -f / 8.0, f / 16.0));
+    }
+}
+
+#[test]
+fn session()
+{
+    let mut b = Bytes::header(1);
+    b.u8(3).i8(31).i8(-2).u8(57).u16(5412).u8(10).i8(7).u8(0).u16(3600).u16(7200)
+     .u8(80).u8(0).u8(1).u8(4).u8(1).u8(17);
+    for i in 0..21 {
+        b.f32(i as f32 / 32.0).i8((i % 5) as i8 - 1);
+    }
+    b.u8(1).u8(1).u8(56);
+    for i in 0..64u8 {
+        b.u8(i % 13).u8(i).u8(i % 6).i8(20 + i as i8).i8((i % 3) as i8).i8(10 - i as i8).i8(((i + 1) % 3) as i8).u8(i);
+    }
+    b.u8(1).u8(95).u32(0xDEAD_BEEF).u32(0x0102_0304).u32(0xA0B0_C0D0)
+     .u8(20).u8(25).u8(12)
+     .u8(0).u8(2).u8(3).u8(1).u8(0).u8(1).u8(0).u8(1).u8(1)
+     .u8(27).u8(1).u32(900).u8(7)
+     .u8(1).u8(0).u8(0).u8(1)
+     .u8(2).u8(3).u8(1)
+     .u8(0).u8(1).u8(3).u8(1).u8(1).u8(0).u8(1).u8(0).u8(3).u8(2).u8(2).u8(1).u8(1).u8(1).u8(1).u8(1)
+     .u8(2).u8(3).u8(1).u8(1).u8(0).u8(2).u8(1).u8(0)
+     .u8(5).raw(&[1, 2, 3, 5, 6, 7, 10, 0, 0, 0, 0, 0])
+     .f32(1800.5).f32(3600.25);
+    assert_eq!(b.0.len(), 753);
+
+    let s = PacketSession::unpack(&b.0);
+    assert_eq!(s.to_bytes(), b.0);
+    assert_header(&{ s.header }, 1);
+    assert_eq!(s.weather as u8, 3);
+    assert_eq!(s.trackTemperature, 31);
+    assert_eq!(s.airTemperature, -2);
+    assert_eq!(s.totalLaps, 57);
+    assert_eq!({ s.trackLength }, 5412);
+    assert_eq!(s.sessionType as u8, 10);
+    assert_eq!(s.trackId, TrackId::Silverstone);
+    assert_eq!(s.formula as u8, 0);
+    assert_eq!({ s.sessionTimeLeft.time }, 3600);
+    assert_eq!({ s.sessionDuration.time }, 7200);
+    assert_eq!(s.pitSpeedLimit, 80);
+    assert_eq!(s.gamePaused, 0);
+    assert_eq!(s.isSpectating, 1);
+    assert_eq!(s.spectatorCarIndex, 4);
+    assert_eq!(s.sliProNativeSupport as u8, 1);
+    assert_eq!(s.numMarshalZones, 17);
+    for (i, zone) in { s.marshalZones }.iter().enumerate() {
+        assert_eq!({ zone.zoneStart }, i as f32 / 32.0);
+        assert_eq!(zone.zoneFlag as i8, (i % 5) as i8 - 1);
+    }
+    assert_eq!(s.safetyCarStatus as u8, 1);
+    assert_eq!(s.networkGame as u8, 1);
+    assert_eq!(s.numWeatherForecasts, 56);
+    for (i, sample) in { s.weatherForecast }.iter().enumerate() {
+        let i = i as u8;
+        assert_eq!(sample.sessionType as u8, i % 13);
+        assert_eq!(sample.timeOffset, i);
+        assert_eq!(sample.weather as u8, i % 6);
+        assert_eq!(sample.trackTemperature, 20 + i as i8);
+        assert_eq!(sample.trackChange as i8, (i % 3) as i8);
+        assert_eq!(sample.airTemperature, 10 - i as i8);
+        assert_eq!(sample.airChange as i8, ((i + 1) % 3) as i8);
+        assert_eq!(sample.rainPercentage, i);
+    }
+    assert_eq!(s.forecastAccuracy as u8, 1);
+    assert_eq!(s.aiDifficulty, 95);
+    assert_eq!({ s.seasonLinkIdentifier }, 0xDEAD_BEEF);
+    assert_eq!({ s.weekendLinkIdentifier }, 0x0102_0304);
+    assert_eq!({ s.sessionLinkIdentifier }, 0xA0B0_C0D0);
+    assert_eq!(s.pitStopWindowIdealLap, 20);
+    assert_eq!(s.pitStopWindowLatestLap, 25);
+    assert_eq!(s.pitStopRejoinPosition, 12);
+    assert_eq!(s.steeringAssist as u8, 0);
+    assert_eq!(s.brakingAssist, 2);
+    assert_eq!(s.gearboxAssist, 3);
+    assert_eq!(s.pitAssist as u8, 1);
+    assert_eq!(s.pitReleaseAssist as u8, 0);
+    assert_eq!(s.ERSAssist as u8, 1);
+    assert_eq!(s.DRSAssist as u8, 0);
+    assert_eq!(s.dynamicRacingLine, 1);
+    assert_eq!(s.dynamicRacingLineType, 1);
+    assert_eq!(s.gameMode, GameMode::MyTeamCareer25);
+    assert_eq!(s.ruleSet, RuleSet::Race);
+    assert_eq!({ s.timeOfDay }, 900);
+    assert_eq!(s.sessionLength as u8, 7);
+    assert_eq!(s.speedUnitsLeadPlayer, 1);
+    assert_eq!(s.temperatureUnitsLeadPlayer, 0);
+    assert_eq!(s.speedUnitsSecondaryPlayer, 0);
+    assert_eq!(s.temperatureUnitsSecondaryPlayer, 1);
+    assert_eq!(s.numSafetyCarPeriods, 2);
+    assert_eq!(s.numVirtualSafetyCarPeriods, 3);
+    assert_eq!(s.numRedFlagPeriods, 1);
+    assert_eq!(s.equalCarPerformance as u8, 0);
+    assert_eq!(s.recoveryMode, 1);
+    assert_eq!(s.flashbackLimit, 3);
+    assert_eq!(s.surfaceType, 1);
+    assert_eq!(s.lowFuelMode, 1);
+    assert_eq!(s.raceStarts, 0);
+    assert_eq!(s.tyreTemperature, 1);
+    assert_eq!(s.pitLaneTyreSim, 0);
+    assert_eq!(s.carDamage, 3);
+    assert_eq!(s.carDamageRate, 2);
+    assert_eq!(s.collisions, 2);
+    assert_eq!(s.collisionsOffForFirstLapOnly, 1);
+    assert_eq!(s.mpUnsafePitRelease, 1);
+    assert_eq!(s.mpOffForGriefing, 1);
+    assert_eq!(s.cornerCuttingStringency, 1);
+    assert_eq!(s.parcFermeRules, 1);
+    assert_eq!(s.pitStopExperience, 2);
+    assert_eq!(s.safetyCar, 3);
+    assert_eq!(s.safetyCarExperience, 1);
+    assert_eq!(s.formationLap, 1);
+    assert_eq!(s.formationLapExperience, 0);
+    assert_eq!(s.redFlags, 2);
+    assert_eq!(s.affectsLicenceLevelSolo, 1);
+    assert_eq!(s.affectsLicenceLevelMP, 0);
+    assert_eq!(s.numSessionsInWeekend, 5);
+    assert_eq!({ s.weekendStructure }.map(|session| session as u8), [1, 2, 3, 5, 6, 7, 10, 0, 0, 0, 0, 0]);
+    assert_eq!({ s.sector2LapDistanceStart }, 1800.5);
+    assert_eq!({ s.sector3LapDistanceStart }, 3600.25);
+}
+
+#[test]
+fn lap()
+{
+    let mut b = Bytes::header(2);
+    for i in 0..22u8 {
+        let n = i as u32;
+        b.u32(90_000 + n).u32(45_000 + n)
+         .u16(30_000 + n as u16).u8(0)
+         .u16(31_000 + n as u16).u8(1)
+         .u16(500 + n as u16).u8(0)
+         .u16(1500 + n as u16).u8(2)
+         .f32(i as f32 * 10.0).f32(i as f32 * 100.0).f32(-(i as f32) / 4.0)
+         .u8(i + 1).u8(i + 2).u8(i % 3).u8(i % 4).u8(i % 3).u8(i % 2)
+         .u8(i % 10).u8(i % 7).u8(i % 5).u8(i % 2).u8(i % 3).u8(22 - i)
+         .u8(i % 5).u8(i % 8).u8(i % 2).u16(20_000 + n as u16).u16(2_000 + n as u16).u8(i % 2)
+         .f32(300.0 + i as f32).u8(i + 3);
+    }
+    b.u8(3).u8(255);
+    assert_eq!(b.0.len(), 1285);
+
+    let p = PacketLap::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 2);
+    for (i, car) in { p.cars }.iter().enumerate() {
+        let i = i as u8;
+        let n = i as u32;
+        assert_eq!({ car.lastLapTimeInMS }, 90_000 + n);
+        assert_eq!({ car.currentLapTimeInMS }, 45_000 + n);
+        assert_eq!({ car.sector1TimeMSPart }, 30_000 + n as u16);
+        assert_eq!(car.sector1TimeMinutesPart, 0);
+        assert_eq!({ car.sector2TimeMSPart }, 31_000 + n as u16);
+        assert_eq!(car.sector2TimeMinutesPart, 1);
+        assert_eq!({ car.deltaToCarInFrontMSPart }, 500 + n as u16);
+        assert_eq!(car.deltaToCarInFrontMinutesPart, 0);
+        assert_eq!({ car.deltaToRaceLeaderMSPart }, 1500 + n as u16);
+        assert_eq!(car.deltaToRaceLeaderMinutesPart, 2);
+        assert_eq!({ car.lapDistance }, i as f32 * 10.0);
+        assert_eq!({ car.totalDistance }, i as f32 * 100.0);
+        assert_eq!({ car.safetyCarDelta }, -(i as f32) / 4.0);
+        assert_eq!(car.racePosition, i + 1);
+        assert_eq!(car.currentLapNum, i + 2);
+        assert_eq!(car.pitStatus as u8, i % 3);
+        assert_eq!(car.numPitStops, i % 4);
+        assert_eq!(car.sector, i % 3);
+        assert_eq!(car.currentLapInvalid, i % 2);
+        assert_eq!(car.penalties, i % 10);
+        assert_eq!(car.totalWarnings, i % 7);
+        assert_eq!(car.cornerCuttingWarnings, i % 5);
+        assert_eq!(car.numUnservedDriveThroughPens, i % 2);
+        assert_eq!(car.numUnservedStopGoPens, i % 3);
+        assert_eq!(car.gridPosition, 22 - i);
+        assert_eq!(car.driverStatus as u8, i % 5);
+        assert_eq!(car.resultStatus as u8, i % 8);
+        assert_eq!(car.pitLaneTimerActive, i % 2);
+        assert_eq!({ car.pitLaneTimeInLaneInMS }, 20_000 + n as u16);
+        assert_eq!({ car.pitStopTimerInMS }, 2_000 + n as u16);
+        assert_eq!(car.pitStopShouldServePen, i % 2);
+        assert_eq!({ car.speedTrapFastestSpeed }, 300.0 + i as f32);
+        assert_eq!(car.speedTrapFastestLap, i + 3);
+    }
+    assert_eq!(p.timeTrialPBCarIdx, 3);
+    assert_eq!(p.timeTrialRivalCarIdx, 255);
+}
+
+//
+// Events, one per string code.
+//
+
+fn event(code: &[u8; 4], details: &[u8]) -> PacketEvent
+{
+    let mut b = Bytes::header(3);
+    b.raw(code).raw(details);
+    b.0.resize(45, 0);
+
+    let e = PacketEvent::unpack(&b.0);
+    assert_eq!(e.to_bytes(), b.0);
+    assert_header(&{ e.header }, 3);
+    assert_eq!(&{ e.eventStringCode }.tag, code);
+    e
+}
+
+#[test]
+fn event_without_details()
+{
+    let cases = [
+        (b"SSTA", EventType::SessionStarted),
+        (b"SEND", EventType::SessionEnded),
+        (b"DRSE", EventType::DRSEnabled),
+        (b"CHQF", EventType::ChequeredFlag),
+        (b"LGOT", EventType::LightsOut),
+        (b"RDFL", EventType::RedFlag),
+    ];
+
+    for (code, eventType) in cases {
+        assert_eq!(event(code, &[]).eventType() as u8, eventType as u8);
+    }
+}
+
+#[test]
+fn event_unknown()
+{
+    let e = event(b"XXXX", &[]);
+    assert_eq!(e.eventType() as u8, EventType::Poisoned as u8);
+    assert_eq!(unsafe { e.eventDetails.unknownTag }, *b"XXXX");
+}
+
+#[test]
+fn event_fastest_lap()
+{
+    let e = event(b"FTLP", Bytes::default().u8(7).f32(88.25).0.as_slice());
+    assert_eq!(e.eventType() as u8, EventType::FastestLap as u8);
+
+    let d = unsafe { e.eventDetails.fastestLap };
+    assert_eq!(d.vehicleIdx, 7);
+    assert_eq!({ d.lapTime }, 88.25);
+}
+
+#[test]
+fn event_retirement()
+{
+    let e = event(b"RTMT", &[9, 8]);
+    assert_eq!(e.eventType() as u8, EventType::Retirement as u8);
+
+    let d = unsafe { e.eventDetails.retirement };
+    assert_eq!(d.vehicleIdx, 9);
+    assert_eq!(d.reason, 8);
+}
+
+#[test]
+fn event_drs_disabled()
+{
+    let e = event(b"DRSD", &[1]);
+    assert_eq!(e.eventType() as u8, EventType::DRSDisabled as u8);
+    assert_eq!(unsafe { e.eventDetails.drsDisabled }.reason, 1);
+}
+
+#[test]
+fn event_team_mate_in_pits()
+{
+    let e = event(b"TMPT", &[12]);
+    assert_eq!(e.eventType() as u8, EventType::TeamMateInPits as u8);
+    assert_eq!(unsafe { e.eventDetails.teamMateInPits }.vehicleIdx, 12);
+}
+
+#[test]
+fn event_race_winner()
+{
+    let e = event(b"RCWN", &[4]);
+    assert_eq!(e.eventType() as u8, EventType::RaceWinner as u8);
+    assert_eq!(unsafe { e.eventDetails.raceWinner }.vehicleIdx, 4);
+}
+
+#[test]
+fn event_penalty()
+{
+    let e = event(b"PENA", &[4, 26, 11, 255, 5, 23, 1]);
+    assert_eq!(e.eventType() as u8, EventType::Penalty as u8);
+
+    let d = unsafe { e.eventDetails.penalty };
+    assert_eq!(d.penaltyType, PenaltyType::TimePenalty);
+    assert_eq!(d.infringementType, InfringementType::LapInvalidatedRunningWide);
+    assert_eq!(d.vehicleIdx, 11);
+    assert_eq!(d.otherVehicleIdx, 255);
+    assert_eq!(d.time, 5);
+    assert_eq!(d.lapNum, 23);
+    assert_eq!(d.placesGained, 1);
+}
+
+#[test]
+fn event_penalty_unknown_ids()
+{
+    let e = event(b"PENA", &[200, 99, 11, 255, 5, 23, 1]);
+
+    let d = unsafe { e.eventDetails.penalty };
+    assert_eq!(d.penaltyType, PenaltyType { id: 200 });
+    assert_eq!(d.infringementType.name(), None);
+    assert_eq!(d.infringementType.to_string(), "???");
+    assert_eq!(format!("{:?}", d.infringementType), "Unknown(99)");
+}
+
+#[test]
+fn event_speed_trap()
+{
+    let e = event(b"SPTP", Bytes::default().u8(6).f32(331.5).u8(1).u8(1).u8(6).f32(331.5).0.as_slice());
+    assert_eq!(e.eventType() as u8, EventType::SpeedTrap as u8);
+
+    let d = unsafe { e.eventDetails.speedTrap };
+    assert_eq!(d.vehicleIdx, 6);
+    assert_eq!({ d.speed }, 331.5);
+    assert_eq!(d.isOverallFastestInSession, 1);
+    assert_eq!(d.isDriverFastestInSession, 1);
+    assert_eq!(d.fastestVehicleIdxInSession, 6);
+    assert_eq!({ d.fastestSpeedInSession }, 331.5);
+}
+
+#[test]
+fn event_start_lights()
+{
+    let e = event(b"STLG", &[3]);
+    assert_eq!(e.eventType() as u8, EventType::StartLights as u8);
+    assert_eq!(unsafe { e.eventDetails.startLights }.numLights, 3);
+}
+
+#[test]
+fn event_drive_through_served()
+{
+    let e = event(b"DTSV", &[15]);
+    assert_eq!(e.eventType() as u8, EventType::DriveThroughPenaltyServed as u8);
+    assert_eq!(unsafe { e.eventDetails.driveThroughPenaltyServed }.vehicleIdx, 15);
+}
+
+#[test]
+fn event_stop_go_served()
+{
+    let e = event(b"SGSV", Bytes::default().u8(16).f32(10.5).0.as_slice());
+    assert_eq!(e.eventType() as u8, EventType::StopGoPenaltyServed as u8);
+
+    let d = unsafe { e.eventDetails.stopGoPenaltyServed };
+    assert_eq!(d.vehicleIdx, 16);
+    assert_eq!({ d.stopTime }, 10.5);
+}
+
+#[test]
+fn event_flashback()
+{
+    let e = event(b"FLBK", Bytes::default().u32(9001).f32(612.75).0.as_slice());
+    assert_eq!(e.eventType() as u8, EventType::Flashback as u8);
+
+    let d = unsafe { e.eventDetails.flashback };
+    assert_eq!({ d.flashbackFrameIdentifier }, 9001);
+    assert_eq!({ d.flashbackSessionTime }, 612.75);
+}
+
+#[test]
+fn event_buttons()
+{
+    let pressed = Button::CrossOrA as u32 | Button::UDPAction12 as u32;
+    let e = event(b"BUTN", Bytes::default().u32(pressed).0.as_slice());
+    assert_eq!(e.eventType() as u8, EventType::Buttons as u8);
+    assert_eq!({ unsafe { e.eventDetails.buttons }.buttonStatus }, 0x8000_0001);
+}
+
+#[test]
+fn event_overtake()
+{
+    let e = event(b"OVTK", &[2, 5]);
+    assert_eq!(e.eventType() as u8, EventType::Overtake as u8);
+
+    let d = unsafe { e.eventDetails.overtake };
+    assert_eq!(d.overtakingVehicleIdx, 2);
+    assert_eq!(d.beingOvertakenVehicleIdx, 5);
+}
+
+#[test]
+fn event_safety_car()
+{
+    let e = event(b"SCAR", &[2, 1]);
+    assert_eq!(e.eventType() as u8, EventType::SafetyCar as u8);
+
+    let d = unsafe { e.eventDetails.safetyCar };
+    assert_eq!(d.safetyCarType as u8, SafetyCar::Virtual as u8);
+    assert_eq!(d.eventType, 1);
+}
+
+#[test]
+fn event_collision()
+{
+    let e = event(b"COLL", &[8, 13]);
+    assert_eq!(e.eventType() as u8, EventType::Collision as u8);
+
+    let d = unsafe { e.eventDetails.collision };
+    assert_eq!(d.vehicle1Idx, 8);
+    assert_eq!(d.vehicle2Idx, 13);
+}
+
+#[test]
+fn participants()
+{
+    let mut b = Bytes::header(4);
+    b.u8(20);
+    for i in 0..22u8 {
+        b.u8(i % 2).u8(i * 3).u8(i).u8(i % 10).u8(i % 2).u8(i + 1).u8(i + 10)
+         .name(&format!("Driver {i}"), 32)
+         .u8(1).u8(i % 2).u16(1000 + i as u16).u8(3).u8(4);
+        for c in 0..4u8 {
+            b.u8(i).u8(c).u8(i + c);
+        }
+    }
+    assert_eq!(b.0.len(), 1284);
+
+    let p = PacketParticipants::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 4);
+    assert_eq!(p.numActiveCars, 20);
+    for (i, participant) in { p.participants }.iter().enumerate() {
+        let i = i as u8;
+        assert_eq!(participant.aiControlled, i % 2);
+        assert_eq!(participant.driverId, DriverId { id: i * 3 });
+        assert_eq!(participant.networkId, i);
+        assert_eq!(participant.teamId, TeamId::from_u8(&(i % 10)));
+        assert_eq!(participant.myTeam, i % 2);
+        assert_eq!(participant.raceNumber, i + 1);
+        assert_eq!(participant.nationality, Nationality { id: i + 10 });
+        assert_eq!(participant.name_to_string(), format!("Driver {i}"));
+        assert_eq!(participant.yourTelemetry, 1);
+        assert_eq!(participant.showOnlineNames, i % 2);
+        assert_eq!({ participant.techLevel }, 1000 + i as u16);
+        assert_eq!(participant.platform, 3);
+        assert_eq!(participant.numColours, 4);
+        for (c, colour) in participant.liveryColours.iter().enumerate() {
+            let c = c as u8;
+            assert_eq!((colour.r, colour.g, colour.b), (i, c, i + c));
+        }
+    }
+    assert_eq!({ p.participants[8] }.teamId.to_string(), "McLaren");
+}
+
+#[test]
+fn car_setups()
+{
+    let mut b = Bytes::header(5);
+    for i in 0..22u8 {
+        let f = i as f32;
+        b.u8(i).u8(i + 1).u8(50 + i).u8(60 + i)
+         .f32(-3.5).f32(-2.0).f32(0.05).f32(0.25)
+         .u8(i % 41).u8(i % 41 + 1).u8(i % 21).u8(i % 21 + 1).u8(i % 50).u8(i % 50 + 2)
+         .u8(90 + i % 10).u8(50 + i).u8(40 + i)
+         .f32(22.5 + f).f32(22.0 + f).f32(24.5 + f).f32(24.0 + f)
+         .u8(i % 12).f32(100.0 - f);
+    }
+    b.f32(32.0);
+    assert_eq!(b.0.len(), 1133);
+
+    let p = PacketCarSetups::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 5);
+    for (i, setup) in { p.carSetups }.iter().enumerate() {
+        let i = i as u8;
+        let f = i as f32;
+        assert_eq!(setup.frontWing, i);
+        assert_eq!(setup.rearWing, i + 1);
+        assert_eq!(setup.onThrottle, 50 + i);
+        assert_eq!(setup.offThrottle, 60 + i);
+        assert_eq!({ setup.frontCamber }, -3.5);
+        assert_eq!({ setup.rearCamber }, -2.0);
+        assert_eq!({ setup.frontToe }, 0.05);
+        assert_eq!({ setup.rearToe }, 0.25);
+        assert_eq!(setup.frontSuspension, i % 41);
+        assert_eq!(setup.rearSuspension, i % 41 + 1);
+        assert_eq!(setup.frontAntiRollBar, i % 21);
+        assert_eq!(setup.rearAntiRollBar, i % 21 + 1);
+        assert_eq!(setup.frontSuspensionHeight, i % 50);
+        assert_eq!(setup.rearSuspensionHeight, i % 50 + 2);
+        assert_eq!(setup.brakePressure, 90 + i % 10);
+        assert_eq!(setup.brakeBias, 50 + i);
+        assert_eq!(setup.engineBraking, 40 + i);
+        let pressure = { setup.tyrePressure };
+        assert_eq!(({ pressure.RL }, { pressure.RR }, { pressure.FL }, { pressure.FR }), (22.5 + f, 22.0 + f, 24.5 + f, 24.0 + f));
+        assert_eq!(setup.ballast, i % 12);
+        assert_eq!({ setup.fuelLoad }, 100.0 - f);
+    }
+    assert_eq!({ p.nextFrontWingValue }, 32.0);
+}
+
+#[test]
+fn car_telemetry()
+{
+    let mut b = Bytes::header(6);
+    for i in 0..22u8 {
+        let n = i as u16;
+        let f = i as f32;
+        b.u16(300 + n).f32(1.0).f32(-0.5).f32(0.25).u8(i % 101).i8((i % 10) as i8 - 1)
+         .u16(11_000 + n).u8(i % 2).u8(i * 4).u16(0x7FFF >> (i % 15))
+         .u16(400 + n).u16(410 + n).u16(420 + n).u16(430 + n)
+         .u8(80 + i).u8(81 + i).u8(82 + i).u8(83 + i)
+         .u8(90 + i).u8(91 + i).u8(92 + i).u8(93 + i)
+         .u16(100 + n)
+         .f32(22.5 + f).f32(22.25 + f).f32(23.5 + f).f32(23.25 + f)
+         .u8(i % 12).u8((i + 1) % 12).u8((i + 2) % 12).u8((i + 3) % 12);
+    }
+    b.u8(255).u8(1).i8(7);
+    assert_eq!(b.0.len(), 1352);
+
+    let p = PacketCarTelemetry::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 6);
+    for (i, car) in { p.carTelemetry }.iter().enumerate() {
+        let i = i as u8;
+        let n = i as u16;
+        let f = i as f32;
+        assert_eq!({ car.speed.kph }, 300 + n);
+        assert_eq!({ car.throttle }, 1.0);
+        assert_eq!({ car.steer }, -0.5);
+        assert_eq!({ car.brake }, 0.25);
+        assert_eq!(car.clutch, i % 101);
+        assert_eq!(car.gear as i8, (i % 10) as i8 - 1);
+        assert_eq!({ car.engineRPM }, 11_000 + n);
+        assert_eq!(car.drs, i % 2);
+        assert_eq!(car.revLightsPercent, i * 4);
+        assert_eq!({ car.revLightsBitValue.LEDs }, 0x7FFF >> (i % 15));
+        assert_eq!({ car.brakesTemperature }, [400 + n, 410 + n, 420 + n, 430 + n]);
+        assert_eq!(car.tyresSurfaceTemperature, [80 + i, 81 + i, 82 + i, 83 + i]);
+        assert_eq!(car.tyresInnerTemperature, [90 + i, 91 + i, 92 + i, 93 + i]);
+        assert_eq!({ car.engineTemperature }, 100 + n);
+        assert_eq!({ car.tyresPressure }, [22.5 + f, 22.25 + f, 23.5 + f, 23.25 + f]);
+        assert_eq!(car.surfaceType, [0, 1, 2, 3].map(|w| Surface::from_u8(&((i + w) % 12))));
+    }
+    assert_eq!(p.mfdFirstPlayer as u8, MFDPanel::Closed as u8);
+    assert_eq!(p.mfdSecondaryPlayer as u8, MFDPanel::Pits as u8);
+    assert_eq!(p.suggestedGear as i8, 7);
+}
+
+#[test]
+fn car_status()
+{
+    let mut b = Bytes::header(7);
+    for i in 0..22u8 {
+        let f = i as f32;
+        b.u8(i % 3).u8(i % 2).u8(i % 4).u8(50 + i).u8(i % 2)
+         .f32(100.0 - f).f32(110.0).f32(20.5 - f)
+         .u16(13_000).u16(4_000 + i as u16).u8(8).u8(i % 2).u16(i as u16 * 10)
+         .u8(16 + i % 7).u8(16 + i % 3).u8(i).i8((i % 5) as i8 - 1)
+         .f32(600_000.0 + f).f32(120_000.0 + f).f32(4_000_000.0 - f).u8(i % 4)
+         .f32(1_000.0 + f).f32(2_000.0 + f).f32(3_000.0 + f).u8(i % 2);
+    }
+    assert_eq!(b.0.len(), 1239);
+
+    let p = PacketCarStatus::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 7);
+    for (i, car) in { p.carStatus }.iter().enumerate() {
+        let i = i as u8;
+        let f = i as f32;
+        assert_eq!(car.tractionControl as u8, i % 3);
+        assert_eq!(car.antiLockBrakes as u8, i % 2);
+        assert_eq!(car.fuelMix as u8, i % 4);
+        assert_eq!(car.frontBrakeBias, 50 + i);
+        assert_eq!(car.pitLimiterStatus, i % 2);
+        assert_eq!({ car.fuelInTank }, 100.0 - f);
+        assert_eq!({ car.fuelCapacity }, 110.0);
+        assert_eq!({ car.fuelRemainingLaps }, 20.5 - f);
+        assert_eq!({ car.maxRPM }, 13_000);
+        assert_eq!({ car.idleRPM }, 4_000 + i as u16);
+        assert_eq!(car.maxGears, 8);
+        assert_eq!(car.drsAllowed, i % 2);
+        assert_eq!({ car.drsActivationDistance }, i as u16 * 10);
+        assert_eq!(car.actualTyre as u8, 16 + i % 7);
+        assert_eq!(car.visualTyre as u8, 16 + i % 3);
+        assert_eq!(car.tyresAgeLaps, i);
+        assert_eq!(car.vehicleFiaFlags as i8, (i % 5) as i8 - 1);
+        assert_eq!({ car.enginePowerICE }, 600_000.0 + f);
+        assert_eq!({ car.enginePowerMGUK }, 120_000.0 + f);
+        assert_eq!({ car.ersStoreEnergy }, 4_000_000.0 - f);
+        assert_eq!(car.ersDeployMode as u8, i % 4);
+        assert_eq!({ car.ersHarvestedThisLapMGUK }, 1_000.0 + f);
+        assert_eq!({ car.ersHarvestedThisLapMGUH }, 2_000.0 + f);
+        assert_eq!({ car.ersDeployedThisLap }, 3_000.0 + f);
+        assert_eq!(car.networkPaused, i % 2);
+    }
+}
+
+#[test]
+fn final_classification()
+{
+    let mut b = Bytes::header(8);
+    b.u8(20);
+    for i in 0..22u8 {
+        b.u8(i + 1).u8(57).u8(22 - i).u8(25u8.saturating_sub(i)).u8(i % 3).u8(i % 8).u8(i % 11)
+         .u32(90_000 + i as u32).f64(5_400.125 + i as f64).u8(i % 10).u8(i % 4).u8(3);
+        for s in 0..8u8 { b.u8(16 + (i + s) % 7); }
+        for s in 0..8u8 { b.u8(16 + (i + s) % 3); }
+        for s in 0..8u8 { b.u8(s * 7 + i); }
+    }
+    assert_eq!(b.0.len(), 1042);
+
+    let p = PacketFinalClassification::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 8);
+    assert_eq!(p.numCars, 20);
+    for (i, car) in { p.classificationData }.iter().enumerate() {
+        let i = i as u8;
+        assert_eq!(car.position, i + 1);
+        assert_eq!(car.numLaps, 57);
+        assert_eq!(car.gridPosition, 22 - i);
+        assert_eq!(car.points, 25u8.saturating_sub(i));
+        assert_eq!(car.numPitStops, i % 3);
+        assert_eq!(car.resultStatus as u8, i % 8);
+        assert_eq!(car.resultReason, i % 11);
+        assert_eq!({ car.bestLapTimeInMS }, 90_000 + i as u32);
+        assert_eq!({ car.totalRaceTime }, 5_400.125 + i as f64);
+        assert_eq!(car.penaltiesTime, i % 10);
+        assert_eq!(car.numPenalties, i % 4);
+        assert_eq!(car.numTyreStints, 3);
+        assert_eq!(car.tyreStintsActual.map(|t| t as u8), [0, 1, 2, 3, 4, 5, 6, 7].map(|s| 16 + (i + s) % 7));
+        assert_eq!(car.tyreStintsVisual.map(|t| t as u8), [0, 1, 2, 3, 4, 5, 6, 7].map(|s| 16 + (i + s) % 3));
+        assert_eq!(car.tyreStintsEndLaps, [0, 1, 2, 3, 4, 5, 6, 7].map(|s| s * 7 + i));
+    }
+}
+
+#[test]
+fn lobby_info()
+{
+    let mut b = Bytes::header(9);
+    b.u8(2);
+    for i in 0..22u8 {
+        b.u8(i % 2).u8(i % 10).u8(i + 1).u8(1)
+         .name(&format!("Player {i}"), 32)
+         .u8(i + 2).u8(1).u8(i % 2).u16(2000 + i as u16).u8(i % 3);
+    }
+    assert_eq!(b.0.len(), 954);
+
+    let p = PacketLobbyInfo::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 9);
+    assert_eq!(p.numPlayers, 2);
+    for (i, player) in { p.lobbyPlayers }.iter().enumerate() {
+        let i = i as u8;
+        assert_eq!(player.aiControlled, i % 2);
+        assert_eq!(player.teamId, TeamId::from_u8(&(i % 10)));
+        assert_eq!(player.nationality, Nationality { id: i + 1 });
+        assert_eq!(player.platform, 1);
+        assert_eq!(player.name_to_string(), format!("Player {i}"));
+        assert_eq!(player.carNumber, i + 2);
+        assert_eq!(player.yourTelemetry, 1);
+        assert_eq!(player.showOnlineNames, i % 2);
+        assert_eq!({ player.techLevel }, 2000 + i as u16);
+        assert_eq!(player.readyStatus as u8, i % 3);
+    }
+}
+
+#[test]
+fn car_damage()
+{
+    let mut b = Bytes::header(10);
+    for i in 0..22u8 {
+        let f = i as f32;
+        b.f32(f).f32(f + 0.5).f32(f + 1.0).f32(f + 1.5);
+        for n in 0..30u8 { b.u8(i + n); }
+    }
+    assert_eq!(b.0.len(), 1041);
+
+    let p = PacketCarDamage::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 10);
+    for (i, car) in { p.carDamageData }.iter().enumerate() {
+        let i = i as u8;
+        let f = i as f32;
+        let wear = { car.tyresWear };
+        assert_eq!(({ wear.RL }, { wear.RR }, { wear.FL }, { wear.FR }), (f, f + 0.5, f + 1.0, f + 1.5));
+        assert_eq!(car.tyresDamage, [i, i + 1, i + 2, i + 3]);
+        assert_eq!(car.brakesDamage, [i + 4, i + 5, i + 6, i + 7]);
+        assert_eq!(car.tyreBlisters, [i + 8, i + 9, i + 10, i + 11]);
+        assert_eq!(car.frontLeftWingDamage, i + 12);
+        assert_eq!(car.frontRightWingDamage, i + 13);
+        assert_eq!(car.rearWingDamage, i + 14);
+        assert_eq!(car.floorDamage, i + 15);
+        assert_eq!(car.diffuserDamage, i + 16);
+        assert_eq!(car.sidepodDamage, i + 17);
+        assert_eq!(car.drsFault, i + 18);
+        assert_eq!(car.ersFault, i + 19);
+        assert_eq!(car.gearBoxDamage, i + 20);
+        assert_eq!(car.engineDamage, i + 21);
+        assert_eq!(car.engineMGUHWear, i + 22);
+        assert_eq!(car.engineESWear, i + 23);
+        assert_eq!(car.engineCEWear, i + 24);
+        assert_eq!(car.engineICEWear, i + 25);
+        assert_eq!(car.engineMGUKWear, i + 26);
+        assert_eq!(car.engineTCWear, i + 27);
+        assert_eq!(car.engineBlown, i + 28);
+        assert_eq!(car.engineSeized, i + 29);
+    }
+}
+
+#[test]
+fn session_history()
+{
+    let valid = [1, 2, 4, 6, 8, 10, 12, 15];
+
+    let mut b = Bytes::header(11);
+    b.u8(5).u8(3).u8(2).u8(2).u8(1).u8(3).u8(2);
+    for i in 0..100u16 {
+        b.u32(80_000 + i as u32)
+         .u16(25_000 + i).u8(0)
+         .u16(26_000 + i).u8(1)
+         .u16(27_000 + i).u8(0)
+         .u8(valid[i as usize % 8]);
+    }
+    for i in 0..8u8 {
+        b.u8(10 * i + 9).u8(16 + i % 7).u8(16 + i % 3);
+    }
+    assert_eq!(b.0.len(), 1460);
+
+    let p = PacketSessionHistory::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 11);
+    assert_eq!(p.carIdx, 5);
+    assert_eq!(p.numLaps, 3);
+    assert_eq!(p.numTyreStints, 2);
+    assert_eq!(p.bestLapTimeLapNum, 2);
+    assert_eq!(p.bestSector1LapNum, 1);
+    assert_eq!(p.bestSector2LapNum, 3);
+    assert_eq!(p.bestSector3LapNum, 2);
+    for (i, lap) in { p.lapHistory }.iter().enumerate() {
+        let n = i as u16;
+        assert_eq!({ lap.lapTimeInMS }, 80_000 + n as u32);
+        assert_eq!({ lap.sector1TimeMSPart }, 25_000 + n);
+        assert_eq!(lap.sector1TimeMinutesPart, 0);
+        assert_eq!({ lap.sector2TimeMSPart }, 26_000 + n);
+        assert_eq!(lap.sector2TimeMinutesPart, 1);
+        assert_eq!({ lap.sector3TimeMSPart }, 27_000 + n);
+        assert_eq!(lap.sector3TimeMinutesPart, 0);
+        assert_eq!(lap.lapValidBitFlags as u8, valid[i % 8]);
+        assert_eq!(lap.sector2TimeInMS(), 60_000 + 26_000 + n as u32);
+    }
+    for (i, stint) in { p.tyreStintsHistory }.iter().enumerate() {
+        let i = i as u8;
+        assert_eq!(stint.endLap, 10 * i + 9);
+        assert_eq!(stint.tyreActualCompound as u8, 16 + i % 7);
+        assert_eq!(stint.tyreVisualCompound as u8, 16 + i % 3);
+    }
+}
+
+#[test]
+fn tyre_sets()
+{
+    let mut b = Bytes::header(12);
+    b.u8(7);
+    for i in 0..20u8 {
+        b.u8(16 + i % 7).u8(16 + i % 3).u8(i * 5).u8(i % 2).u8(i % 14)
+         .u8(30 - i).u8(25).i16(i as i16 * 100 - 1000).u8((i == 2) as u8);
+    }
+    b.u8(2);
+    assert_eq!(b.0.len(), 231);
+
+    let p = TyreSetsData::unpack(&b.0);
+    assert_eq!(p.to_bytes(), b.0);
+    assert_header(&{ p.header }, 12);
+    assert_eq!(p.carIdx, 7);
+    for (i, set) in { p.tyreSetData }.iter().enumerate() {
+        let i = i as u8;
+        assert_eq!(set.actualCompound as u8, 16 + i % 7);
+        assert_eq!(set.visualCompound as u8, 16 + i % 3);
+        assert_eq!(set.wear, i * 5);
+        assert_eq!(set.available, i % 2);
+        assert_eq!(set.recommendedSession as u8, i % 14);
+        assert_eq!(set.lifeSpan, 30 - i);
+        assert_eq!(set.usableLife, 25);
+        assert_eq!({ set.lapDeltaTime }, i as i16 * 100 - 1000);
+        assert_eq!(set.fitted, (i == 2) as u8);
+    }
+    assert_eq!(p.fittedIdx, 2);
+}
+
+//
+// JSON, enums by name and only the used part of padded arrays.
+//
+
+#[test]
+fn json_event()
+{
+    let e = event(b"PENA", &[4, 26, 11, 255, 5, 23, 1]);
+    let json = e.to_json();
+
+    assert!(json.starts_with("{\"header\":{\"packetFormat\":2025,"));
+    assert!(json.contains("\"packetId\":\"Event\""));
+    assert!(json.contains("\"sessionTime\":123.5"));
+    assert!(json.contains("\"sessionUID\":\"81985529216486895\""));
+    assert!(json.ends_with(
+        "\"eventStringCode\":\"PENA\",\"eventType\":\"Penalty\",\"eventDetails\":{\"penaltyType\":\"TimePenalty\",\
+         \"infringementType\":\"LapInvalidatedRunningWide\",\"vehicleIdx\":11,\"otherVehicleIdx\":255,\
+         \"time\":5,\"lapNum\":23,\"placesGained\":1}}"
+    ));
+
+    assert!(event(b"LGOT", &[]).to_json().ends_with("\"eventType\":\"LightsOut\",\"eventDetails\":{}}"));
+}
+
+#[test]
+fn json_participants()
+{
+    let mut p = PacketParticipants { numActiveCars: 1, ..Default::default() };
+    p.participants[0].driverId = DriverId { id: 7 };
+    p.participants[0].nationality = Nationality { id: 10 };
+    p.participants[0].name[..9].copy_from_slice(b"Lew\"is\\\n\xff");
+
+    let json = p.to_json();
+    assert!(json.contains("\"participants\":[{\"aiControlled\":0,\"driverId\":\"Lewis Hamilton\","));
+    assert!(json.contains("\"nationality\":\"British\",\"name\":\"Lew\\\"is\\\\\\n\u{fffd}\""));
+    assert_eq!(json.matches("aiControlled").count(), 1);
+
+    p.participants[0].driverId = DriverId { id: 250 };
+    assert!(p.to_json().contains("\"driverId\":\"Driver 250\","));
+}
+
+#[test]
+fn json_numbers()
+{
+    assert_eq!(f32::NAN.to_json(), "null");
+    assert_eq!(f32::INFINITY.to_json(), "null");
+    assert_eq!(1e-7f32.to_json(), "0.0000001");
+    assert_eq!((-3i8).to_json(), "-3");
+    assert_eq!([true, false].to_json(), "[true,false]");
+    assert_eq!([TrackId::Silverstone, TrackId::Monza].to_json(), "[\"Silverstone\",\"Monza\"]");
+}
+
+//
+// Serde, round trips through JSON back to the same bytes.
+//
+
+#[cfg(feature = "serde")]
+fn round_trip<T: serde::Serialize + serde::de::DeserializeOwned>(packet: &T) -> (String, T)
+{
+    let json = serde_json::to_string(packet).unwrap();
+    (json.clone(), serde_json::from_str(&json).unwrap())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_event()
+{
+    let e = event(b"PENA", &[4, 26, 11, 255, 5, 23, 1]);
+    let (json, back) = round_trip(&e);
+    assert!(json.contains("\"eventStringCode\":\"PENA\""));
+    assert!(json.contains("\"eventDetails\":{\"Penalty\":{\"penaltyType\":\"TimePenalty\","));
+    assert_eq!(back.to_bytes(), e.to_bytes());
+
+    let e = event(b"PENA", &[200, 99, 11, 255, 5, 23, 1]);
+    let (json, back) = round_trip(&e);
+    assert!(json.contains("{\"penaltyType\":200,\"infringementType\":99,"));
+    assert_eq!(back.to_bytes(), e.to_bytes());
+
+    for code in [b"SSTA", b"LGOT", b"XXXX"] {
+        let e = event(code, &[]);
+        let (json, back) = round_trip(&e);
+        assert!(json.ends_with("\"eventDetails\":\"None\"}"));
+        assert_eq!(back.to_bytes(), e.to_bytes());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_participants()
+{
+    let mut p = PacketParticipants { numActiveCars: 1, ..Default::default() };
+    p.participants[0].driverId = DriverId { id: 7 };
+    p.participants[0].teamId = TeamId::Mercedes;
+    p.participants[0].name[..14].copy_from_slice(b"Lewis Hamilton");
+
+    let (json, back) = round_trip(&p);
+    assert!(json.contains("\"name\":\"Lewis Hamilton\""));
+    assert!(json.contains("\"teamId\":\"Mercedes\""));
+    assert_eq!(back.to_bytes(), p.to_bytes());
+
+    let long = json.replace("Lewis Hamilton", &"x".repeat(33));
+    assert!(serde_json::from_str::<PacketParticipants>(&long).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_long_arrays()
+{
+    let mut b = Bytes::header(11);
+    b.u8(5).u8(3).u8(2).u8(2).u8(1).u8(3).u8(2);
+    for i in 0..100u16 {
+        b.u32(80_000 + i as u32).u16(25_000).u8(0).u16(26_000).u8(1).u16(27_000).u8(0).u8(15);
+    }
+    b.raw(&[0; 24]);
+    let history = PacketSessionHistory::unpack(&b.0);
+    let (_, back) = round_trip(&history);
+    assert_eq!(back.to_bytes(), history.to_bytes());
+
+    let mut session = PacketSession::unpack(&[0; size_of::<PacketSession>()]);
+    session.weatherForecast[63].rainPercentage = 80;
+    let (_, back) = round_trip(&session);
+    assert_eq!(back.to_bytes(), session.to_bytes());
+}
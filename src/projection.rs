@@ -0,0 +1,130 @@
+use crate::{Car, Page};
+use pitwall::packet::*;
+
+// Where a car is heading at the flag.
+#[derive(Debug, Clone, Copy)]
+pub struct Finish {
+    pub idx: usize,
+    pub position: usize, // Projected, from 1
+    pub laps: u8,        // Laps done when it takes the flag
+    pub time: f32,       // Milliseconds from now until it does, penalties included
+    pub stops: u8,       // Compulsory stops still to make
+}
+
+impl Finish {
+    // The gap to the winner at the flag, or the laps it's down.
+    pub fn gap(&self, winner: &Finish) -> String {
+        match winner.laps.saturating_sub(self.laps) {
+            0 if self.idx == winner.idx => String::new(),
+            0 => format!("+{:.1}", (self.time - winner.time) / 1000.0),
+            1 => "+1 Lap".to_string(),
+            laps => format!("+{laps} Laps"),
+        }
+    }
+}
+
+// Laps done so far, with the lap in progress as a fraction.
+fn progress(car: &Car, trackLength: u16) -> f32 {
+    let fraction = match trackLength {
+        0 => 0.0,
+        length => (car.lapDistance / length as f32).clamp(0.0, 1.0),
+    };
+    car.lapNum.saturating_sub(1) as f32 + fraction
+}
+
+// A dry race has to see two dry compounds, unless it goes wet.
+fn stopsOwed(page: &Page, car: &Car) -> u8 {
+    if !matches!(page.session, Session::Race) {
+        return 0;
+    }
+    let mut compounds: Vec<u8> = car.laps.iter().map(|lap| lap.tyre as u8).collect();
+    compounds.push(car.tyres.visual as u8);
+    compounds.sort();
+    compounds.dedup();
+
+    let isWet = compounds
+        .iter()
+        .any(|tyre| matches!(*tyre, 7 | 8 | 10 | 15));
+    let dry = compounds.iter().filter(|tyre| **tyre >= 16).count();
+    (!isWet && dry == 1) as u8
+}
+
+// Time still to be paid, in milliseconds: the time penalties, drive throughs and stop goes
+// not yet served, and the compulsory stop if there's one to make.
+fn owed(car: &Car, stops: u8, loss: f32) -> f32 {
+    let penalties = car.penalties;
+    penalties.timePenalty as f32 * 1000.0
+        + penalties.driveThroughs as f32 * loss
+        + penalties.stopGoes as f32 * (loss + 10_000.0)
+        + stops as f32 * loss
+}
+
+// The finish of every car still running, in the order they're heading for. Each runs out the
+// race at the pace of its last few green laps. The winner sets the flag, and everyone else finishes
+// the lap they're on when it falls, so a lapped car comes home a lap or more down.
+pub fn project(page: &Page) -> Vec<Finish> {
+    let mut order = page.order();
+    order.retain(|idx| !page.car[*idx].isOut());
+    let total = page.lap.total;
+    if total == 0 || order.is_empty() {
+        return vec![];
+    }
+
+    // Nothing to go on until someone has a green lap, then the average stands in for
+    // anyone without one yet.
+    let paces: Vec<u32> = order
+        .iter()
+        .filter_map(|idx| page.car[*idx].pace(3))
+        .collect();
+    if paces.is_empty() {
+        return vec![];
+    }
+    let average = paces.iter().sum::<u32>() as f32 / paces.len() as f32;
+    let loss = page.pitLoss().map_or(0, |(ms, _)| ms) as f32;
+
+    let cars: Vec<(usize, f32, f32, f32, u8)> = order
+        .iter()
+        .map(|idx| {
+            let car = &page.car[*idx];
+            let pace = car.pace(3).map_or(average, |ms| ms as f32);
+            let stops = stopsOwed(page, car);
+            (
+                *idx,
+                progress(car, page.track.length),
+                pace,
+                owed(car, stops, loss),
+                stops,
+            )
+        })
+        .collect();
+
+    let flag = cars
+        .iter()
+        .map(|(_, progress, pace, owed, _)| (total as f32 - progress).max(0.0) * pace + owed)
+        .fold(f32::MAX, f32::min);
+
+    let mut finishes: Vec<Finish> = cars
+        .iter()
+        .map(|(idx, progress, pace, owed, stops)| {
+            let covered = progress + (flag - owed).max(0.0) / pace;
+            let laps = if covered >= total as f32 {
+                total
+            } else {
+                (covered.floor() as u8 + 1).min(total)
+            };
+            Finish {
+                idx: *idx,
+                position: 0,
+                laps,
+                time: (laps as f32 - progress).max(0.0) * pace + owed,
+                stops: *stops,
+            }
+        })
+        .collect();
+
+    finishes.sort_by(|a, b| b.laps.cmp(&a.laps).then(a.time.total_cmp(&b.time)));
+    for (pos, finish) in finishes.iter_mut().enumerate() {
+        finish.position = pos + 1;
+    }
+    finishes
+}
@@ -0,0 +1,180 @@
+use crate::results::lapTime;
+use rusqlite::{params, Connection, Result};
+use std::process;
+
+// A report from `pitwall query`, run against the session database.
+struct Query {
+    report: String,
+    database: String,       // --database
+    driver: Option<String>, // --driver, any part of the name
+    track: Option<String>,  // --track, any part of the name
+}
+
+// `pitwall query <report> [options]`, the exit code for main to return.
+pub fn run(args: impl Iterator<Item = String>) -> i32 {
+    let query = Query::from_args(args);
+
+    let result =
+        Connection::open_with_flags(&query.database, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .and_then(|conn| match query.report.as_str() {
+                "sessions" => sessions(&conn, &query),
+                "best-laps" => bestLaps(&conn, &query),
+                "pace" => pace(&conn, &query),
+                report => Query::usage(&format!("Unknown report {report}")),
+            });
+
+    match result {
+        Ok(()) => 0,
+        Err(err) => {
+            eprintln!("{}: {err}", query.database);
+            1
+        }
+    }
+}
+
+impl Query {
+    fn from_args(mut args: impl Iterator<Item = String>) -> Self {
+        let mut query = Self {
+            report: String::new(),
+            database: "pitwall.db".to_string(),
+            driver: None,
+            track: None,
+        };
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--database" => query.database = Self::value(&arg, args.next()),
+                "--driver" => query.driver = Some(Self::value(&arg, args.next())),
+                "--track" => query.track = Some(Self::value(&arg, args.next())),
+                "--help" => Self::usage(""),
+                _ if query.report.is_empty() && !arg.starts_with("--") => query.report = arg,
+                _ => Self::usage(&format!("Unknown argument {arg}")),
+            }
+        }
+
+        if query.report.is_empty() {
+            Self::usage("Which report?");
+        }
+        query
+    }
+
+    fn value(arg: &str, value: Option<String>) -> String {
+        value.unwrap_or_else(|| Self::usage(&format!("Missing value for {arg}")))
+    }
+
+    fn usage(error: &str) -> ! {
+        if !error.is_empty() {
+            eprintln!("{error}");
+            eprintln!();
+        }
+        eprintln!("Usage: pitwall query <report> [options]");
+        eprintln!("Reports:");
+        eprintln!("  sessions                  Every session stored, newest first");
+        eprintln!("  best-laps                 Each driver's best valid lap at each track");
+        eprintln!("  pace                      Average green lap per driver, track and compound");
+        eprintln!("Options:");
+        eprintln!("  --database <file>         SQLite database to read (default pitwall.db)");
+        eprintln!("  --driver <name>           Only drivers whose name contains this");
+        eprintln!("  --track <name>            Only tracks whose name contains this");
+        process::exit(2);
+    }
+}
+
+fn sessions(conn: &Connection, query: &Query) -> Result<()> {
+    let mut select = conn.prepare(
+        "SELECT s.sessionUID, datetime(s.startedAt, 'unixepoch'), s.session, s.track,
+                (SELECT COUNT(*) FROM laps l WHERE l.sessionUID = s.sessionUID)
+         FROM sessions s
+         WHERE s.track LIKE '%' || ?1 || '%'
+         ORDER BY s.startedAt DESC",
+    )?;
+
+    println!(
+        "{:<16} {:<19} {:<13} {:<15} {:>5}",
+        "Session UID", "Started", "Session", "Track", "Laps"
+    );
+    let mut rows = select.query(params![query.track.as_deref().unwrap_or("")])?;
+    while let Some(row) = rows.next()? {
+        println!(
+            "{:<16x} {:<19} {:<13} {:<15} {:>5}",
+            row.get::<_, i64>(0)? as u64,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, u32>(4)?,
+        );
+    }
+    Ok(())
+}
+
+// SQLite takes the other columns from the row MIN() picked.
+fn bestLaps(conn: &Connection, query: &Query) -> Result<()> {
+    let mut select = conn.prepare(
+        "SELECT s.track, p.driver, MIN(l.lapTimeMS), l.sector1MS, l.sector2MS, l.sector3MS, l.tyre, s.session
+         FROM laps l
+         JOIN participants p USING (sessionUID, carIdx)
+         JOIN sessions s USING (sessionUID)
+         WHERE l.isValid AND l.lapTimeMS > 0
+           AND p.driver LIKE '%' || ?1 || '%' AND s.track LIKE '%' || ?2 || '%'
+         GROUP BY s.track, p.driver
+         ORDER BY s.track, MIN(l.lapTimeMS)",
+    )?;
+
+    println!(
+        "{:<15} {:<22} {:>9} {:>9} {:>9} {:>9} {:<6} {:<13}",
+        "Track", "Driver", "Best", "S1", "S2", "S3", "Tyre", "Session"
+    );
+    let mut rows = select.query(params![
+        query.driver.as_deref().unwrap_or(""),
+        query.track.as_deref().unwrap_or("")
+    ])?;
+    while let Some(row) = rows.next()? {
+        println!(
+            "{:<15} {:<22} {:>9} {:>9} {:>9} {:>9} {:<6} {:<13}",
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            lapTime(row.get(2)?),
+            lapTime(row.get(3)?),
+            lapTime(row.get(4)?),
+            lapTime(row.get(5)?),
+            row.get::<_, String>(6)?,
+            row.get::<_, String>(7)?,
+        );
+    }
+    Ok(())
+}
+
+// Green laps only, so no SC / VSC, no trips down the pit lane and no track limits.
+fn pace(conn: &Connection, query: &Query) -> Result<()> {
+    let mut select = conn.prepare(
+        "SELECT s.track, p.driver, l.tyre, COUNT(*), AVG(l.lapTimeMS), MIN(l.lapTimeMS)
+         FROM laps l
+         JOIN participants p USING (sessionUID, carIdx)
+         JOIN sessions s USING (sessionUID)
+         WHERE l.isValid AND NOT l.isPit AND NOT l.isNeutralised AND l.lapTimeMS > 0
+           AND p.driver LIKE '%' || ?1 || '%' AND s.track LIKE '%' || ?2 || '%'
+         GROUP BY s.track, p.driver, l.tyre
+         ORDER BY s.track, p.driver, AVG(l.lapTimeMS)",
+    )?;
+
+    println!(
+        "{:<15} {:<22} {:<6} {:>5} {:>9} {:>9}",
+        "Track", "Driver", "Tyre", "Laps", "Average", "Best"
+    );
+    let mut rows = select.query(params![
+        query.driver.as_deref().unwrap_or(""),
+        query.track.as_deref().unwrap_or("")
+    ])?;
+    while let Some(row) = rows.next()? {
+        println!(
+            "{:<15} {:<22} {:<6} {:>5} {:>9} {:>9}",
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, u32>(3)?,
+            lapTime(row.get::<_, f64>(4)?.round() as u32),
+            lapTime(row.get(5)?),
+        );
+    }
+    Ok(())
+}
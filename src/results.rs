@@ -0,0 +1,418 @@
+use crate::export;
+use colored::*;
+use pitwall::packet::*;
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Width of the stint strip on the dashboard, in characters.
+static STRIP_WIDTH: usize = 30;
+
+// One set of tyres, from PacketFinalClassification.classificationData.tyreStints*.
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Stint {
+    pub tyre: VisualCompound, // tyreStintsVisual
+    pub from: u8,             // First lap on these tyres
+    pub to: u8,               // tyreStintsEndLaps, numLaps for one that hasn't ended
+}
+
+impl Stint {
+    pub fn laps(&self) -> u8 {
+        self.to.saturating_add(1).saturating_sub(self.from)
+    }
+}
+
+// One car's result, from PacketFinalClassification.classificationData.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Row {
+    pub carIdx: usize,        // Index into classificationData
+    pub position: u8,         // position
+    pub grid: u8,             // gridPosition
+    pub driver: String,       // PacketParticipants.participants.name
+    pub team: String,         // PacketParticipants.participants.teamId
+    pub laps: u8,             // numLaps
+    pub points: u8,           // points
+    pub pitStops: u8,         // numPitStops
+    pub status: ResultStatus, // resultStatus
+    pub bestLapInMS: u32,     // bestLapTimeInMS
+    pub raceTime: f64,        // totalRaceTime, in seconds without penalties
+    pub penalties: u8,        // penaltiesTime, in seconds
+    pub numPenalties: u8,     // numPenalties
+    pub stints: Vec<Stint>,
+}
+
+impl Row {
+    fn from(result: &FinalClassification, carIdx: usize, driver: String, team: String) -> Self {
+        let numStints = (result.numTyreStints as usize).min(result.tyreStintsEndLaps.len());
+        let mut from = 1;
+        let stints = (0..numStints)
+            .map(|i| {
+                let stint = Stint {
+                    tyre: result.tyreStintsVisual[i],
+                    from,
+                    // 255 until the stint ends.
+                    to: match result.tyreStintsEndLaps[i] {
+                        255 => result.numLaps,
+                        to => to,
+                    },
+                };
+                from = stint.to.saturating_add(1);
+                stint
+            })
+            .collect();
+
+        Self {
+            carIdx,
+            position: result.position,
+            grid: result.gridPosition,
+            driver,
+            team,
+            laps: result.numLaps,
+            points: result.points,
+            pitStops: result.numPitStops,
+            status: result.resultStatus,
+            bestLapInMS: result.bestLapTimeInMS,
+            raceTime: result.totalRaceTime,
+            penalties: result.penaltiesTime,
+            numPenalties: result.numPenalties,
+            stints,
+        }
+    }
+
+    // Race time with the time penalties added on.
+    pub fn adjustedTime(&self) -> f64 {
+        self.raceTime + self.penalties as f64
+    }
+
+    // Places made up from the grid, negative when lost. None for a pit lane start.
+    pub fn gained(&self) -> Option<i16> {
+        (self.grid > 0).then(|| self.grid as i16 - self.position as i16)
+    }
+
+    fn isClassified(&self) -> bool {
+        matches!(self.status, ResultStatus::Finished | ResultStatus::Active)
+    }
+
+    // "S 1-12 M 13-30", the stints as text.
+    pub fn stintList(&self) -> String {
+        self.stints
+            .iter()
+            .map(|stint| format!("{} {}-{}", stint.tyre, stint.from, stint.to))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    // The stints as coloured blocks, each as wide as its share of the race.
+    fn stintStrip(&self, totalLaps: u8) -> String {
+        let totalLaps = totalLaps.max(1) as usize;
+        let mut used = 0;
+
+        let strip: String = self
+            .stints
+            .iter()
+            .map(|stint| {
+                let width = (stint.laps() as usize * STRIP_WIDTH / totalLaps).max(1);
+                used += width;
+                let block = format!("{:<width$}", stint.tyre.to_string());
+                format!("{}", match stint.tyre {
+                    VisualCompound::Soft | VisualCompound::OldSoft => block.black().on_red(),
+                    VisualCompound::Medium | VisualCompound::OldMedium => block.black().on_yellow(),
+                    VisualCompound::Hard | VisualCompound::OldHard => block.black().on_white(),
+                    VisualCompound::Inter => block.black().on_green(),
+                    VisualCompound::Wet | VisualCompound::OldWet => block.white().on_blue(),
+                    _ => block.normal(),
+                })
+            })
+            .collect();
+
+        // Retirements stop short, pad so the points still line up.
+        format!("{strip}{:width$}", "", width = STRIP_WIDTH.saturating_sub(used))
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Results {
+    pub session: Session, // PacketSession.sessionType
+    pub trackId: TrackId, // PacketSession.trackId
+    pub rows: Vec<Row>,   // In finishing order
+}
+
+impl Results {
+    // `names` gives the driver and team for a vehicle index.
+    pub fn new(
+        session: Session,
+        trackId: TrackId,
+        classification: &PacketFinalClassification,
+        names: impl Fn(usize) -> (String, String),
+    ) -> Self {
+        let results = classification.classificationData;
+        let numCars = (classification.numCars as usize).min(results.len());
+
+        let mut rows: Vec<Row> = results[..numCars]
+            .iter()
+            .enumerate()
+            .filter(|(_, result)| result.position > 0)
+            .map(|(idx, result)| {
+                let (driver, team) = names(idx);
+                Row::from(result, idx, driver, team)
+            })
+            .collect();
+        rows.sort_by_key(|row| row.position);
+
+        Self {
+            session,
+            trackId,
+            rows,
+        }
+    }
+
+    fn winner(&self) -> Option<&Row> {
+        self.rows.first()
+    }
+
+    fn totalLaps(&self) -> u8 {
+        self.winner().map_or(0, |winner| winner.laps)
+    }
+
+    // Behind the winner on penalty adjusted time, in laps when lapped, or why they weren't classified.
+    pub fn gap(&self, row: &Row) -> String {
+        let Some(winner) = self.winner() else {
+            return String::new();
+        };
+
+        if !row.isClassified() {
+            return status(row.status).to_string();
+        }
+        if row.position == winner.position {
+            return raceTime(row.adjustedTime());
+        }
+        if row.laps < winner.laps {
+            let down = winner.laps - row.laps;
+            return format!("+{down} Lap{}", if down == 1 { "" } else { "s" });
+        }
+
+        format!("{:+.3}", row.adjustedTime() - winner.adjustedTime())
+    }
+
+    fn title(&self) -> String {
+        format!("{} {} Results", self.trackId, self.session)
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut md = format!("# {}\n\n", self.title());
+        md += "| Pos | Driver | Team | Grid | +/- | Laps | Time / Gap | Penalties | Best Lap | Stops | Stints | Points |\n";
+        md += "|----:|--------|------|-----:|----:|-----:|-----------:|----------:|---------:|------:|--------|-------:|\n";
+
+        for row in &self.rows {
+            md += &format!(
+                "| {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                row.position,
+                row.driver.replace('|', "\\|"),
+                row.team,
+                grid(row.grid),
+                gained(row.gained()),
+                row.laps,
+                self.gap(row),
+                penalties(row),
+                lapTime(row.bestLapInMS),
+                row.pitStops,
+                row.stintList(),
+                row.points,
+            );
+        }
+
+        md
+    }
+
+    pub fn html(&self) -> String {
+        let totalLaps = self.totalLaps().max(1) as f32;
+        let title = html(&self.title());
+
+        let mut page = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n\
+             body {{ font-family: sans-serif; }}\n\
+             table {{ border-collapse: collapse; }}\n\
+             th, td {{ padding: 2px 8px; text-align: right; }}\n\
+             td.name {{ text-align: left; }}\n\
+             .strip {{ display: flex; width: 240px; }}\n\
+             .strip span {{ font-size: small; text-align: center; }}\n\
+             .S {{ background: #e10600; color: white; }} .M {{ background: #ffd700; }} .H {{ background: #eeeeee; }}\n\
+             .I {{ background: #43b02a; color: white; }} .W {{ background: #0067ad; color: white; }}\n\
+             .up {{ color: green; }} .down {{ color: red; }}\n\
+             </style>\n</head>\n<body>\n<h1>{title}</h1>\n<table>\n\
+             <tr><th>Pos</th><th>Driver</th><th>Team</th><th>Grid</th><th>+/-</th><th>Laps</th><th>Time / Gap</th>\
+             <th>Penalties</th><th>Best Lap</th><th>Stops</th><th>Stints</th><th>Points</th></tr>\n"
+        );
+
+        for row in &self.rows {
+            let strip: String = row
+                .stints
+                .iter()
+                .map(|stint| {
+                    format!(
+                        "<span class=\"{}\" style=\"width: {:.1}%\">{}</span>",
+                        stint.tyre.to_string().to_uppercase(),
+                        stint.laps() as f32 * 100.0 / totalLaps,
+                        stint.tyre,
+                    )
+                })
+                .collect();
+            let change = match row.gained() {
+                Some(places) if places > 0 => "up",
+                Some(places) if places < 0 => "down",
+                _ => "",
+            };
+
+            page += &format!(
+                "<tr><td>{}</td><td class=\"name\">{}</td><td class=\"name\">{}</td><td>{}</td><td class=\"{change}\">{}</td>\
+                 <td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><div class=\"strip\">{strip}</div></td><td>{}</td></tr>\n",
+                row.position,
+                html(&row.driver),
+                html(&row.team),
+                grid(row.grid),
+                gained(row.gained()),
+                row.laps,
+                self.gap(row),
+                penalties(row),
+                lapTime(row.bestLapInMS),
+                row.pitStops,
+                row.points,
+            );
+        }
+
+        page + "</table>\n</body>\n</html>\n"
+    }
+
+    pub fn csv(&self) -> String {
+        let mut csv = String::from(
+            "Position,Driver,Team,Grid,Gained,Laps,Status,Race Time,Penalties,Penalty Count,Adjusted Time,Gap,Best Lap,Pit Stops,Stints,Points\n",
+        );
+
+        for row in &self.rows {
+            csv += &format!(
+                "{},{},{},{},{},{},{:?},{:.3},{},{},{:.3},{},{},{},{},{}\n",
+                row.position,
+                export::field(&row.driver),
+                export::field(&row.team),
+                row.grid,
+                row.gained().map_or(String::new(), |places| places.to_string()),
+                row.laps,
+                row.status,
+                row.raceTime,
+                row.penalties,
+                row.numPenalties,
+                row.adjustedTime(),
+                export::field(&self.gap(row)),
+                export::seconds(row.bestLapInMS),
+                row.pitStops,
+                row.stintList(),
+                row.points,
+            );
+        }
+
+        csv
+    }
+}
+
+impl fmt::Display for Results {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.title().bold())?;
+        writeln!(
+            f,
+            "{:>3} {:<22} {:<15} {:>4} {:>4} {:>4} {:>13} {:>9} {:>9} {:>2} {:<width$} {:>3}",
+            "Pos", "Driver", "Team", "Grid", "+/-", "Laps", "Time / Gap", "Pen", "Best", "PS", "Stints", "Pts",
+            width = STRIP_WIDTH,
+        )?;
+
+        let totalLaps = self.totalLaps();
+        for row in &self.rows {
+            let change = gained(row.gained());
+            let change = match row.gained() {
+                Some(places) if places > 0 => change.green(),
+                Some(places) if places < 0 => change.red(),
+                _ => change.normal(),
+            };
+
+            writeln!(
+                f,
+                "{:>3} {:<22.22} {:<15.15} {:>4} {:>4} {:>4} {:>13} {:>9} {:>9} {:>2} {} {:>3}",
+                row.position,
+                row.driver,
+                row.team,
+                grid(row.grid),
+                change,
+                row.laps,
+                self.gap(row),
+                penalties(row),
+                lapTime(row.bestLapInMS),
+                row.pitStops,
+                row.stintStrip(totalLaps),
+                row.points,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn status(status: ResultStatus) -> &'static str {
+    match status {
+        ResultStatus::DidNotFinish => "DNF",
+        ResultStatus::Disqualified => "DSQ",
+        ResultStatus::NotClassified => "NC",
+        ResultStatus::Retired => "Retired",
+        _ => "",
+    }
+}
+
+fn grid(grid: u8) -> String {
+    if grid == 0 {
+        "Pit".to_string()
+    } else {
+        grid.to_string()
+    }
+}
+
+fn gained(places: Option<i16>) -> String {
+    match places {
+        Some(places) if places > 0 => format!("+{places}"),
+        Some(places) => places.to_string(),
+        None => String::new(),
+    }
+}
+
+fn penalties(row: &Row) -> String {
+    match (row.penalties, row.numPenalties) {
+        (0, _) => String::new(),
+        (time, 0 | 1) => format!("+{time}s"),
+        (time, count) => format!("+{time}s ({count})"),
+    }
+}
+
+// 1:28.123
+pub fn lapTime(inMS: u32) -> String {
+    if inMS == 0 {
+        return String::new();
+    }
+
+    format!("{}:{:02}.{:03}", inMS / 60_000, inMS / 1000 % 60, inMS % 1000)
+}
+
+// 1:32:05.123
+fn raceTime(seconds: f64) -> String {
+    let inMS = (seconds * 1000.0).round() as u64;
+    let hours = inMS / 3_600_000;
+
+    if hours > 0 {
+        format!("{hours}:{:02}:{:02}.{:03}", inMS / 60_000 % 60, inMS / 1000 % 60, inMS % 1000)
+    } else {
+        format!("{}:{:02}.{:03}", inMS / 60_000, inMS / 1000 % 60, inMS % 1000)
+    }
+}
+
+fn html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
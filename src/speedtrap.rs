@@ -0,0 +1,39 @@
+use pitwall::packet::SpeedTrap;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// A car's runs through the speed trap, from SPTP events and PacketLap.
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Trap {
+    pub speed: f32,     // Best in kph, SpeedTrap.speed or speedTrapFastestSpeed
+    pub lap: u8,        // Lap the best was set on, speedTrapFastestLap
+    pub last: f32,      // SpeedTrap.speed of the latest event
+    pub wasBest: bool,  // SpeedTrap.isDriverFastestInSession of the latest event
+    pub wasOB: bool,    // SpeedTrap.isOverallFastestInSession of the latest event
+}
+
+impl Trap {
+    // A SPTP event for this car, seen on lap `lap`.
+    pub fn event(&mut self, trap: &SpeedTrap, lap: u8) {
+        self.last = trap.speed;
+        self.wasBest = trap.isDriverFastestInSession == 1;
+        self.wasOB = trap.isOverallFastestInSession == 1;
+        if self.wasBest || trap.speed > self.speed {
+            self.speed = trap.speed;
+            self.lap = lap;
+        }
+    }
+
+    // PacketLap's record of the best, which catches anything the events missed.
+    pub fn lapData(&mut self, speed: f32, lap: u8) {
+        if lap != 255 && speed > self.speed {
+            self.speed = speed;
+            self.lap = lap;
+        }
+    }
+
+    pub fn isSet(&self) -> bool {
+        self.speed > 0.0
+    }
+}
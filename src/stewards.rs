@@ -0,0 +1,143 @@
+use pitwall::packet::*;
+use colored::*;
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// One decision from the stewards, as announced by a PENA event.
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Decision {
+    pub sessionTime: f32,     // PacketEvent.header.sessionTime
+    pub penaltyType: PenaltyType,           // Penalty.penaltyType
+    pub infringementType: InfringementType, // Penalty.infringementType
+    pub vehicleIdx: u8,       // Penalty.vehicleIdx
+    pub otherVehicleIdx: u8,  // Penalty.otherVehicleIdx, 255 if nobody else was involved
+    pub time: u8,             // Penalty.time
+    pub lapNum: u8,           // Penalty.lapNum
+    pub placesGained: u8,     // Penalty.placesGained
+    pub isServed: bool,       // DTSV / SGSV seen for this one
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ledger {
+    pub decisions: Vec<Decision>,
+}
+
+impl Ledger {
+    pub fn record(&mut self, sessionTime: f32, penalty: &Penalty) {
+        self.decisions.push(Decision {
+            sessionTime,
+            penaltyType: penalty.penaltyType,
+            infringementType: penalty.infringementType,
+            vehicleIdx: penalty.vehicleIdx,
+            otherVehicleIdx: penalty.otherVehicleIdx,
+            time: penalty.time,
+            lapNum: penalty.lapNum,
+            placesGained: penalty.placesGained,
+            isServed: false,
+        });
+    }
+
+    // The oldest outstanding drive through for this car has been served.
+    pub fn servedDriveThrough(&mut self, vehicleIdx: u8) {
+        self.served(vehicleIdx, PenaltyType::DriveThrough);
+    }
+
+    // The oldest outstanding stop go for this car has been served.
+    pub fn servedStopGo(&mut self, vehicleIdx: u8) {
+        self.served(vehicleIdx, PenaltyType::StopGo);
+    }
+
+    fn served(&mut self, vehicleIdx: u8, penaltyType: PenaltyType) {
+        if let Some(decision) = self.decisions.iter_mut().find(|d| {
+            d.vehicleIdx == vehicleIdx && d.penaltyType == penaltyType && !d.isServed
+        }) {
+            decision.isServed = true;
+        }
+    }
+
+    pub fn latest(&self, count: usize) -> impl Iterator<Item = &Decision> {
+        self.decisions.iter().rev().take(count)
+    }
+}
+
+// What a car still has hanging over it, straight from PacketLap.
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Outstanding {
+    pub timePenalty: u8,    // penalties, in seconds
+    pub warnings: u8,       // totalWarnings
+    pub cornerCutting: u8,  // cornerCuttingWarnings
+    pub driveThroughs: u8,  // numUnservedDriveThroughPens
+    pub stopGoes: u8,       // numUnservedStopGoPens
+    pub serveAtStop: bool,  // pitStopShouldServePen
+}
+
+impl Outstanding {
+    pub fn update(&mut self, car: &Car) {
+        self.timePenalty = car.penalties;
+        self.warnings = car.totalWarnings;
+        self.cornerCutting = car.cornerCuttingWarnings;
+        self.driveThroughs = car.numUnservedDriveThroughPens;
+        self.stopGoes = car.numUnservedStopGoPens;
+        self.serveAtStop = car.pitStopShouldServePen == 1;
+    }
+}
+
+impl fmt::Display for Outstanding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts: Vec<String> = vec![];
+
+        if self.timePenalty > 0 {
+            let time = format!("+{}s", self.timePenalty);
+            parts.push(format!("{}", if self.serveAtStop { time.black().on_yellow() } else { time.yellow() }));
+        }
+        if self.driveThroughs > 0 {
+            parts.push(format!("{}", format!("DT{}", self.driveThroughs).white().on_red()));
+        }
+        if self.stopGoes > 0 {
+            parts.push(format!("{}", format!("SG{}", self.stopGoes).white().on_red()));
+        }
+        if self.warnings > 0 {
+            parts.push(format!("W{}", self.warnings));
+        }
+        if self.cornerCutting > 0 {
+            parts.push(format!("CC{}", self.cornerCutting));
+        }
+
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let minutes = self.sessionTime as u32 / 60;
+        let seconds = self.sessionTime as u32 % 60;
+
+        write!(
+            f,
+            "{:02}:{:02} L{:<2} {}: {}",
+            minutes, seconds, self.lapNum, self.penaltyType, self.infringementType
+        )?;
+
+        if self.time > 0 {
+            write!(f, " {}s", self.time)?;
+        }
+
+        if self.placesGained > 0 {
+            write!(f, " +{} places", self.placesGained)?;
+        }
+
+        if self.otherVehicleIdx != 255 {
+            write!(f, " (with car {})", self.otherVehicleIdx)?;
+        }
+
+        if self.isServed {
+            write!(f, " {}", "Served".green())?;
+        }
+
+        Ok(())
+    }
+}
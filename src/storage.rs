@@ -0,0 +1,330 @@
+use crate::export::LapRow;
+use crate::results::Stint;
+use crate::stewards::Decision;
+use pitwall::packet::*;
+use rusqlite::{params, Connection, Result};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Seconds of session time between telemetry samples, and between commits.
+static SAMPLE_INTERVAL: f32 = 1.0;
+
+// sessionUID is a u64, SQLite integers are i64, so it's stored as the same bits.
+static SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    sessionUID   INTEGER PRIMARY KEY,
+    startedAt    INTEGER NOT NULL,
+    session      TEXT NOT NULL,
+    track        TEXT NOT NULL,
+    season       INTEGER NOT NULL,
+    weekend      INTEGER NOT NULL,
+    totalLaps    INTEGER NOT NULL,
+    trackLength  INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS participants (
+    sessionUID   INTEGER NOT NULL,
+    carIdx       INTEGER NOT NULL,
+    driver       TEXT NOT NULL,
+    team         TEXT NOT NULL,
+    raceNumber   INTEGER NOT NULL,
+    isAI         INTEGER NOT NULL,
+    PRIMARY KEY (sessionUID, carIdx)
+);
+CREATE TABLE IF NOT EXISTS laps (
+    sessionUID    INTEGER NOT NULL,
+    carIdx        INTEGER NOT NULL,
+    lap           INTEGER NOT NULL,
+    lapTimeMS     INTEGER NOT NULL,
+    sector1MS     INTEGER NOT NULL,
+    sector2MS     INTEGER NOT NULL,
+    sector3MS     INTEGER NOT NULL,
+    isValid       INTEGER NOT NULL,
+    isPit         INTEGER NOT NULL,
+    isNeutralised INTEGER NOT NULL,
+    tyre          TEXT NOT NULL,
+    tyreAge       INTEGER NOT NULL,
+    position      INTEGER NOT NULL,
+    gapToLeaderMS INTEGER NOT NULL,
+    PRIMARY KEY (sessionUID, carIdx, lap)
+);
+CREATE TABLE IF NOT EXISTS stints (
+    sessionUID   INTEGER NOT NULL,
+    carIdx       INTEGER NOT NULL,
+    stint        INTEGER NOT NULL,
+    tyre         TEXT NOT NULL,
+    fromLap      INTEGER NOT NULL,
+    toLap        INTEGER NOT NULL,
+    PRIMARY KEY (sessionUID, carIdx, stint)
+);
+CREATE TABLE IF NOT EXISTS pitStops (
+    sessionUID   INTEGER NOT NULL,
+    carIdx       INTEGER NOT NULL,
+    lap          INTEGER NOT NULL,
+    laneMS       INTEGER NOT NULL,
+    stopMS       INTEGER NOT NULL,
+    PRIMARY KEY (sessionUID, carIdx, lap)
+);
+CREATE TABLE IF NOT EXISTS penalties (
+    sessionUID   INTEGER NOT NULL,
+    sessionTime  REAL NOT NULL,
+    carIdx       INTEGER NOT NULL,
+    otherCarIdx  INTEGER NOT NULL,
+    penalty      TEXT NOT NULL,
+    infringement TEXT NOT NULL,
+    time         INTEGER NOT NULL,
+    lap          INTEGER NOT NULL,
+    placesGained INTEGER NOT NULL,
+    isServed     INTEGER NOT NULL,
+    PRIMARY KEY (sessionUID, sessionTime, carIdx)
+);
+CREATE TABLE IF NOT EXISTS events (
+    sessionUID   INTEGER NOT NULL,
+    sessionTime  REAL NOT NULL,
+    frame        INTEGER NOT NULL,
+    code         TEXT NOT NULL,
+    json         TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS telemetry (
+    sessionUID   INTEGER NOT NULL,
+    sessionTime  REAL NOT NULL,
+    carIdx       INTEGER NOT NULL,
+    speed        INTEGER NOT NULL,
+    throttle     REAL NOT NULL,
+    brake        REAL NOT NULL,
+    steer        REAL NOT NULL,
+    gear         INTEGER NOT NULL,
+    rpm          INTEGER NOT NULL,
+    drs          INTEGER NOT NULL,
+    PRIMARY KEY (sessionUID, sessionTime, carIdx)
+);
+CREATE INDEX IF NOT EXISTS eventsBySession ON events (sessionUID);
+";
+
+// Every session run, in an SQLite file keyed by sessionUID.
+// Writes are batched in a transaction that's committed once per telemetry sample.
+pub struct Store {
+    conn: Connection,
+    sessionUID: u64, // Last session written, the packet arrives twice a second
+    nextSample: f32, // Session time of the next telemetry sample
+}
+
+impl Store {
+    pub fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute_batch(SCHEMA)?;
+        conn.execute_batch("BEGIN")?;
+
+        Ok(Self {
+            conn,
+            sessionUID: 0,
+            nextSample: 0.0,
+        })
+    }
+
+    // Make everything so far permanent, and start the next batch.
+    pub fn commit(&mut self) -> Result<()> {
+        self.conn.execute_batch("COMMIT; BEGIN")
+    }
+
+    pub fn session(&mut self, s: &PacketSession) -> Result<()> {
+        if s.header.sessionUID == self.sessionUID {
+            return Ok(());
+        }
+        self.sessionUID = s.header.sessionUID;
+        self.nextSample = 0.0;
+
+        let startedAt = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.conn.execute(
+            "INSERT OR IGNORE INTO sessions VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                { s.header.sessionUID } as i64,
+                startedAt as i64,
+                format!("{:?}", s.sessionType),
+                format!("{:?}", s.trackId),
+                { s.seasonLinkIdentifier },
+                { s.weekendLinkIdentifier },
+                { s.totalLaps },
+                { s.trackLength },
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn participants(&mut self, p: &PacketParticipants) -> Result<()> {
+        let mut insert = self.conn.prepare_cached(
+            "INSERT OR REPLACE INTO participants VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        let participants = p.participants;
+        let numCars = (p.numActiveCars as usize).min(participants.len());
+
+        for (idx, participant) in participants[..numCars].iter().enumerate() {
+            insert.execute(params![
+                { p.header.sessionUID } as i64,
+                idx as u8,
+                participant.name_to_string(),
+                participant.teamId.to_string(),
+                { participant.raceNumber },
+                { participant.aiControlled } == 1,
+            ])?;
+        }
+        Ok(())
+    }
+
+    // Written again as the game's own times arrive, so the last write wins.
+    pub fn lap(&mut self, sessionUID: u64, row: &LapRow) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "INSERT OR REPLACE INTO laps VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            )?
+            .execute(params![
+                sessionUID as i64,
+                row.carIdx as u8,
+                row.lap,
+                row.lapTime,
+                row.sector1,
+                row.sector2,
+                row.sector3,
+                row.isValid,
+                row.isPit,
+                row.isNeutralised,
+                row.tyre,
+                row.tyreAge,
+                row.position,
+                row.gapToLeader,
+            ])?;
+        Ok(())
+    }
+
+    pub fn stints(&mut self, sessionUID: u64, carIdx: usize, stints: &[Stint]) -> Result<()> {
+        let mut insert = self
+            .conn
+            .prepare_cached("INSERT OR REPLACE INTO stints VALUES (?1, ?2, ?3, ?4, ?5, ?6)")?;
+
+        for (idx, stint) in stints.iter().enumerate() {
+            insert.execute(params![
+                sessionUID as i64,
+                carIdx as u8,
+                idx as u8 + 1,
+                format!("{:?}", stint.tyre),
+                stint.from,
+                stint.to,
+            ])?;
+        }
+        Ok(())
+    }
+
+    pub fn pitStop(
+        &mut self,
+        sessionUID: u64,
+        carIdx: usize,
+        lap: u8,
+        laneInMS: u16,
+        stopInMS: u16,
+    ) -> Result<()> {
+        self.conn
+            .prepare_cached("INSERT OR REPLACE INTO pitStops VALUES (?1, ?2, ?3, ?4, ?5)")?
+            .execute(params![
+                sessionUID as i64,
+                carIdx as u8,
+                lap,
+                laneInMS,
+                stopInMS
+            ])?;
+        Ok(())
+    }
+
+    // Average net pit loss in earlier races at `track`: each stop's pit laps against the car's
+    // green laps, leaving out drive throughs and stops made under the SC / VSC. None until a race there has stored some.
+    pub fn pitLoss(&self, track: &str, sessionUID: u64) -> Result<Option<u32>> {
+        self.conn.query_row(
+            "WITH pace AS (
+                SELECT sessionUID, carIdx, AVG(lapTimeMS) AS ms FROM laps
+                WHERE isPit = 0 AND isNeutralised = 0 AND lapTimeMS > 0
+                GROUP BY sessionUID, carIdx
+            ), lost AS (
+                SELECT stop.sessionUID, SUM(laps.lapTimeMS - pace.ms) AS ms
+                FROM pitStops stop
+                JOIN laps ON laps.sessionUID = stop.sessionUID AND laps.carIdx = stop.carIdx
+                    AND laps.lap IN (stop.lap, stop.lap + 1) AND laps.isPit = 1
+                JOIN pace ON pace.sessionUID = stop.sessionUID AND pace.carIdx = stop.carIdx
+                WHERE stop.stopMS > 0
+                GROUP BY stop.sessionUID, stop.carIdx, stop.lap
+                HAVING MAX(laps.isNeutralised) = 0 AND MIN(laps.lapTimeMS) > 0
+            )
+            SELECT CAST(AVG(lost.ms) AS INTEGER)
+            FROM lost JOIN sessions USING (sessionUID)
+            WHERE sessions.track = ?1 AND sessions.session IN ('Race', 'Race2', 'Race3')
+                AND sessions.sessionUID != ?2 AND lost.ms > 0",
+            params![track, sessionUID as i64],
+            |row| row.get(0),
+        )
+    }
+
+    pub fn penalty(&mut self, sessionUID: u64, decision: &Decision) -> Result<()> {
+        self.conn
+            .prepare_cached(
+                "INSERT OR REPLACE INTO penalties VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?
+            .execute(params![
+                sessionUID as i64,
+                decision.sessionTime,
+                decision.vehicleIdx,
+                decision.otherVehicleIdx,
+                format!("{:?}", decision.penaltyType),
+                format!("{:?}", decision.infringementType),
+                decision.time,
+                decision.lapNum,
+                decision.placesGained,
+                decision.isServed,
+            ])?;
+        Ok(())
+    }
+
+    // Each event in full, as the --json output would print it.
+    pub fn event(&mut self, e: &PacketEvent) -> Result<()> {
+        self.conn
+            .prepare_cached("INSERT INTO events VALUES (?1, ?2, ?3, ?4, ?5)")?
+            .execute(params![
+                { e.header.sessionUID } as i64,
+                { e.header.sessionTime },
+                { e.header.frameIdentifier },
+                format!("{:?}", e.eventType()),
+                e.to_json(),
+            ])?;
+        Ok(())
+    }
+
+    // Every car, once each SAMPLE_INTERVAL, which is also when the batch is committed.
+    // True when it was, so a database that was failing is known to be back.
+    pub fn telemetry(&mut self, t: &PacketCarTelemetry, numCars: usize) -> Result<bool> {
+        let sessionTime = t.header.sessionTime;
+        if sessionTime < self.nextSample {
+            return Ok(false);
+        }
+        self.nextSample = sessionTime + SAMPLE_INTERVAL;
+
+        {
+            let mut insert = self.conn.prepare_cached(
+                "INSERT OR REPLACE INTO telemetry VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            let cars = t.carTelemetry;
+            for (idx, car) in cars[..numCars.min(cars.len())].iter().enumerate() {
+                insert.execute(params![
+                    { t.header.sessionUID } as i64,
+                    sessionTime,
+                    idx as u8,
+                    { car.speed }.kph,
+                    { car.throttle },
+                    { car.brake },
+                    { car.steer },
+                    car.gear as i8,
+                    { car.engineRPM },
+                    { car.drs },
+                ])?;
+            }
+        }
+
+        self.commit().map(|_| true)
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{Car, Page};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// The game's advice for the player, from PacketSession. Zero when it has none.
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PitWindow {
+    pub idealLap: u8,       // pitStopWindowIdealLap
+    pub latestLap: u8,      // pitStopWindowLatestLap
+    pub rejoinPosition: u8, // pitStopRejoinPosition
+}
+
+// A car's current stint, in milliseconds a lap.
+#[derive(Debug, Clone, Copy)]
+pub struct Stint {
+    pub pace: f32,  // Last three green laps
+    pub deg: f32,   // Lost each lap of tyre age
+    pub fresh: f32, // The pace with the tyre age taken back to nothing
+}
+
+impl Stint {
+    pub fn of(car: &Car) -> Option<Self> {
+        let pace = car.pace(3)? as f32;
+        let deg = car.degradation().unwrap_or(0.0).max(0.0);
+        Some(Self {
+            pace,
+            deg,
+            fresh: pace - deg * car.tyres.age as f32,
+        })
+    }
+}
+
+// What `first` gains on `second` by pitting this lap, with `second` pitting the lap after.
+// Both pay the pit loss, so it's three laps: `first` does its in lap, out lap, and a lap
+// on new tyres with the warm-up, while `second` does a lap on old tyres, then its in and out laps.
+fn gain(first: &Stint, second: &Stint, warmUp: f32) -> f32 {
+    3.0 * (second.pace + second.deg) - (2.0 * (first.pace + first.deg) + first.fresh + warmUp)
+}
+
+// The car in front or behind, and the gap to it at the line, in milliseconds.
+#[derive(Debug, Clone, Copy)]
+pub struct Rival {
+    pub idx: usize,
+    pub gap: f32,
+    pub stint: Option<Stint>,
+}
+
+// What pitting this lap does for a car, against the cars either side of it.
+#[derive(Debug, Clone, Copy)]
+pub struct Call {
+    pub stint: Stint,
+    pub warmUp: f32,
+    pub ahead: Option<Rival>,
+    pub behind: Option<Rival>,
+}
+
+impl Call {
+    // For `idx`, once it has a stint to go on. Gaps are from the last lap both cars finished,
+    // so the call changes once a lap rather than with every packet.
+    pub fn new(page: &Page, idx: usize) -> Option<Self> {
+        let stint = Stint::of(&page.car[idx])?;
+        let order = page.order();
+        let pos = order.iter().position(|other| *other == idx)?;
+
+        let rival = |other: usize, gap: Option<f32>| {
+            gap.map(|gap| Rival {
+                idx: other,
+                gap,
+                stint: Stint::of(&page.car[other]),
+            })
+        };
+        let ahead = pos
+            .checked_sub(1)
+            .and_then(|pos| rival(order[pos], gapAtLine(page, order[pos], idx)));
+        let behind = order
+            .get(pos + 1)
+            .and_then(|other| rival(*other, gapAtLine(page, idx, *other)));
+
+        Some(Self {
+            stint,
+            warmUp: warmUp(page).unwrap_or(0.0),
+            ahead,
+            behind,
+        })
+    }
+
+    // Pitting now against the car ahead staying out a lap: above zero, the margin we'd come out ahead by.
+    pub fn undercut(&self) -> Option<f32> {
+        let ahead = self.ahead?;
+        Some(gain(&self.stint, &ahead.stint?, self.warmUp) - ahead.gap)
+    }
+
+    // The car ahead pitting now and us staying out a lap: above zero, the margin we'd come out ahead by.
+    pub fn overcut(&self) -> Option<f32> {
+        let ahead = self.ahead?;
+        Some(-gain(&ahead.stint?, &self.stint, self.warmUp) - ahead.gap)
+    }
+
+    // Pitting now with the car behind answering a lap later: above zero, what we'd still be ahead by.
+    pub fn covered(&self) -> Option<f32> {
+        let behind = self.behind?;
+        Some(behind.gap + gain(&self.stint, &behind.stint?, self.warmUp))
+    }
+
+    // The car behind pitting now and us a lap later: above zero, what it would get ahead by.
+    pub fn threat(&self) -> Option<f32> {
+        let behind = self.behind?;
+        Some(gain(&behind.stint?, &self.stint, self.warmUp) - behind.gap)
+    }
+}
+
+// How far `behind` was behind `ahead` at the end of the last lap both finished.
+fn gapAtLine(page: &Page, ahead: usize, behind: usize) -> Option<f32> {
+    let last = page.car[behind].laps.last()?;
+    let other = page.car[ahead]
+        .laps
+        .iter()
+        .rev()
+        .find(|lap| lap.lapNum == last.lapNum)?;
+    Some(last.leaderInMS as f32 - other.leaderInMS as f32)
+}
+
+// Time cold tyres cost on the first flying lap after a stop, against the two laps after it.
+// Averaged over every stop with those three laps run green, None before the first.
+pub fn warmUp(page: &Page) -> Option<f32> {
+    let mut costs = vec![];
+    for car in page.activeCars() {
+        for stop in car.pitStops.iter().filter(|stop| !stop.isDriveThrough()) {
+            let laps: Vec<u32> = (2..=4)
+                .filter_map(|after| {
+                    car.laps.iter().find(|lap| {
+                        lap.lapNum == stop.lap.saturating_add(after)
+                            && lap.inMS > 0
+                            && !lap.isPit
+                            && !lap.isNeutralised
+                    })
+                })
+                .map(|lap| lap.inMS)
+                .collect();
+            if let [first, second, third] = laps[..] {
+                costs.push((first as f32 - (second + third) as f32 / 2.0).max(0.0));
+            }
+        }
+    }
+
+    if costs.is_empty() {
+        return None;
+    }
+    Some(costs.iter().sum::<f32>() / costs.len() as f32)
+}
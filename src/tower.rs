@@ -0,0 +1,742 @@
+use crate::metrics::Metrics;
+use crate::projection::{self, Finish};
+use crate::Page;
+use colored::*;
+use std::fs;
+
+// The presets every layout starts with. A layout file uses the same format, and its
+// presets come first, replacing any built in preset of the same name.
+static PRESETS: &str = "
+[race]
+pos driver number team last pace deg | interval | leader | s1 s2 s3 | time | lap sector tyre | gear drs ers kph | state | pen
+
+[strategy]
+pos driver number tyre age wear | stops pit | last pace deg best | interval leader | fuel | pen
+
+[engineer]
+pos driver number | kph trap gear drs ers fuel | tyre age wear | last best | state
+
+[stewards]
+pos driver number team | grid | interval | pen | stops pit | state
+
+[projection]
+pos driver number | proj flag owed | interval leader | last pace deg | tyre age stops | pen
+";
+
+#[derive(Debug, Clone, Copy)]
+enum Align {
+    Left,
+    Right,
+    Centre,
+}
+
+// A tower column: its name in a layout, heading, default width, the cell for each car,
+// and what goes under it in the Bests row. Cells are handed the projected finishes,
+// worked out once for the whole tower.
+struct Column {
+    name: &'static str,
+    header: &'static str,
+    width: usize,
+    align: Align,
+    cell: fn(&Page, &Metrics, &[Finish], usize) -> String,
+    best: fn(&Page) -> String,
+}
+
+fn none(_: &Page) -> String {
+    String::new()
+}
+
+static COLUMNS: [Column; 34] = [
+    Column {
+        name: "pos",
+        header: "P",
+        width: 2,
+        align: Align::Right,
+        cell: |page, _, _, idx| format!("{:02}", page.car[idx].spotRace),
+        best: none,
+    },
+    Column {
+        name: "driver",
+        header: "Driver",
+        width: 15,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].driver.flagged().to_string(),
+        best: |_| "Bests".to_string(),
+    },
+    Column {
+        name: "number",
+        header: "(##)",
+        width: 4,
+        align: Align::Left,
+        cell: |page, _, _, idx| format!("({:2})", page.car[idx].driver.number),
+        best: none,
+    },
+    Column {
+        name: "team",
+        header: "Team",
+        width: 15,
+        align: Align::Left,
+        cell: |page, _, _, idx| page.car[idx].team.id.to_string(),
+        best: none,
+    },
+    Column {
+        name: "last",
+        header: "Last",
+        width: 8,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].time.lastLap.to_string(),
+        best: |page| page.ob.lapTime.to_string(),
+    },
+    Column {
+        name: "pace",
+        header: "Pace",
+        width: 8,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].getPace(),
+        best: none,
+    },
+    Column {
+        name: "deg",
+        header: "Deg",
+        width: 6,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].getDegradation(),
+        best: none,
+    },
+    Column {
+        name: "interval",
+        header: "Interval",
+        width: 8,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].getInterval(page.raceControl.isNeutralised()),
+        best: none,
+    },
+    Column {
+        name: "leader",
+        header: "Leader",
+        width: 8,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].time.leader.to_string(),
+        best: none,
+    },
+    Column {
+        name: "s1",
+        header: "S1",
+        width: 8,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].time.sector1.to_string(),
+        best: |page| page.ob.sector1.to_string(),
+    },
+    Column {
+        name: "s2",
+        header: "S2",
+        width: 8,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].time.sector2.to_string(),
+        best: |page| page.ob.sector2.to_string(),
+    },
+    Column {
+        name: "s3",
+        header: "S3",
+        width: 8,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].time.sector3.to_string(),
+        best: |page| page.ob.sector3.to_string(),
+    },
+    Column {
+        name: "time",
+        header: "Time",
+        width: 8,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].time.current.to_string(),
+        best: |page| format!("{:.3}", page.ob.possible as f32 / 1000_f32),
+    },
+    Column {
+        name: "best",
+        header: "Best",
+        width: 8,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].personalBest(idx).lapTime.to_string(),
+        best: |page| page.ob.lapTime.to_string(),
+    },
+    Column {
+        name: "lap",
+        header: "Lap",
+        width: 3,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].lapNum.to_string(),
+        best: none,
+    },
+    Column {
+        name: "sector",
+        header: "S",
+        width: 1,
+        align: Align::Centre,
+        cell: |page, _, _, idx| page.car[idx].sector.to_string(),
+        best: none,
+    },
+    Column {
+        name: "tyre",
+        header: "Tyre",
+        width: 4,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].tyres.to_string(),
+        best: none,
+    },
+    Column {
+        name: "age",
+        header: "Age",
+        width: 3,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].tyres.age.to_string(),
+        best: none,
+    },
+    Column {
+        name: "wear",
+        header: "Wear",
+        width: 4,
+        align: Align::Right,
+        cell: |_, metrics, _, idx| wear(metrics, idx),
+        best: none,
+    },
+    Column {
+        name: "gear",
+        header: "G",
+        width: 1,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].telemetry.gear.to_string(),
+        best: none,
+    },
+    Column {
+        name: "drs",
+        header: "DRS",
+        width: 3,
+        align: Align::Centre,
+        cell: |page, _, _, idx| page.car[idx].Drs.to_string(),
+        best: none,
+    },
+    Column {
+        name: "ers",
+        header: "ERS",
+        width: 3,
+        align: Align::Centre,
+        cell: |page, _, _, idx| page.car[idx].Ers.to_string(),
+        best: none,
+    },
+    Column {
+        name: "kph",
+        header: "KPH",
+        width: 3,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].telemetry.speed.kph.to_string(),
+        best: none,
+    },
+    Column {
+        name: "trap",
+        header: "Trap",
+        width: 4,
+        align: Align::Right,
+        cell: |page, _, _, idx| trap(page, idx),
+        best: |page| match page.speedTraps().first() {
+            Some(idx) => format!("{:.0}", page.car[*idx].speedTrap.speed),
+            None => String::new(),
+        },
+    },
+    Column {
+        name: "traplap",
+        header: "TL",
+        width: 3,
+        align: Align::Right,
+        cell: |page, _, _, idx| {
+            let trap = page.car[idx].speedTrap;
+            if trap.isSet() {
+                format!("L{}", trap.lap)
+            } else {
+                String::new()
+            }
+        },
+        best: none,
+    },
+    Column {
+        name: "fuel",
+        header: "Fuel",
+        width: 5,
+        align: Align::Right,
+        cell: |_, metrics, _, idx| fuel(metrics, idx),
+        best: none,
+    },
+    Column {
+        name: "stops",
+        header: "Stops",
+        width: 5,
+        align: Align::Right,
+        cell: |page, _, _, idx| page.car[idx].pitCount.to_string(),
+        best: none,
+    },
+    Column {
+        name: "pit",
+        header: "Last Stop",
+        width: 9,
+        align: Align::Left,
+        cell: |page, _, _, idx| lastStop(page, idx),
+        best: none,
+    },
+    Column {
+        name: "grid",
+        header: "Grid",
+        width: 4,
+        align: Align::Right,
+        cell: |page, _, _, idx| grid(page, idx),
+        best: none,
+    },
+    Column {
+        name: "state",
+        header: "State",
+        width: 5,
+        align: Align::Centre,
+        cell: |page, _, _, idx| page.car[idx].carStatus.to_string(),
+        best: none,
+    },
+    Column {
+        name: "proj",
+        header: "Proj",
+        width: 4,
+        align: Align::Right,
+        cell: |page, _, finishes, idx| projected(page, finishes, idx),
+        best: none,
+    },
+    Column {
+        name: "flag",
+        header: "At Flag",
+        width: 8,
+        align: Align::Right,
+        cell: |_, _, finishes, idx| match (finishes.first(), finish(finishes, idx)) {
+            (Some(winner), Some(finish)) => finish.gap(winner),
+            _ => String::new(),
+        },
+        best: none,
+    },
+    Column {
+        name: "owed",
+        header: "Owed",
+        width: 4,
+        align: Align::Right,
+        cell: |_, _, finishes, idx| match finish(finishes, idx) {
+            Some(finish) if finish.stops > 0 => finish.stops.to_string(),
+            _ => String::new(),
+        },
+        best: none,
+    },
+    Column {
+        name: "pen",
+        header: "Pen",
+        width: 12,
+        align: Align::Left,
+        cell: |page, _, _, idx| page.car[idx].penalties.to_string(),
+        best: none,
+    },
+];
+
+// Most worn of the four tyres.
+fn wear(metrics: &Metrics, idx: usize) -> String {
+    match metrics.cars.get(idx) {
+        Some(car) => {
+            let wear = car.damage.tyresWear;
+            format!("{:.0}%", wear.RL.max(wear.RR).max(wear.FL).max(wear.FR))
+        }
+        None => String::new(),
+    }
+}
+
+// Laps of fuel over what's needed to finish, as on the MFD.
+fn fuel(metrics: &Metrics, idx: usize) -> String {
+    match metrics.cars.get(idx) {
+        Some(car) => {
+            let laps = { car.status.fuelRemainingLaps };
+            let text = format!("{laps:+.1}");
+            if laps < 0.0 {
+                text.red().to_string()
+            } else {
+                text
+            }
+        }
+        None => String::new(),
+    }
+}
+
+// Best through the speed trap, purple for the fastest of the session.
+fn trap(page: &Page, idx: usize) -> String {
+    let trap = page.car[idx].speedTrap;
+    if !trap.isSet() {
+        return String::new();
+    }
+    let speed = format!("{:.0}", trap.speed);
+    if page.speedTraps().first() == Some(&idx) {
+        speed.purple().to_string()
+    } else {
+        speed
+    }
+}
+
+fn lastStop(page: &Page, idx: usize) -> String {
+    match page.car[idx].pitStops.last() {
+        Some(stop) if stop.isDriveThrough() => format!("L{} DT", stop.lap),
+        Some(stop) => format!("L{} {:.1}s", stop.lap, stop.stopInMS as f32 / 1000.0),
+        None => String::new(),
+    }
+}
+
+// Places made since the start, green, or lost, red.
+fn grid(page: &Page, idx: usize) -> String {
+    let car = &page.car[idx];
+    if car.spotGrid == 0 || car.spotRace == 0 {
+        return String::new();
+    }
+    let gained = car.spotGrid as i16 - car.spotRace as i16;
+    match gained {
+        0 => "=".to_string(),
+        1.. => format!("+{gained}").green().to_string(),
+        _ => format!("{gained}").red().to_string(),
+    }
+}
+
+fn finish(finishes: &[Finish], idx: usize) -> Option<&Finish> {
+    finishes.iter().find(|finish| finish.idx == idx)
+}
+
+// Projected finishing position, with the places it would make, green, or lose, red.
+fn projected(page: &Page, finishes: &[Finish], idx: usize) -> String {
+    let Some(finish) = finish(finishes, idx) else {
+        return String::new();
+    };
+    let gained = page.car[idx].spotRace as i16 - finish.position as i16;
+    let position = format!("P{}", finish.position);
+    match gained {
+        0 => position,
+        1.. => position.green().to_string(),
+        _ => position.red().to_string(),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Slot {
+    Column(&'static Column, usize), // With its width in this preset
+    Divider,
+}
+
+// A named set of tower columns.
+pub struct Preset {
+    pub name: String,
+    slots: Vec<Slot>,
+}
+
+impl Preset {
+    // The heading, a line per car in race order, or in projected finishing order with the
+    // cars that are out after the rest, and the Bests row when any of the columns has one.
+    // Columns that would run past `width` characters are left off.
+    pub fn render(
+        &self,
+        page: &Page,
+        metrics: &Metrics,
+        isProjected: bool,
+        width: usize,
+        marker: impl Fn(usize) -> &'static str,
+    ) -> Vec<String> {
+        let mut used = 1; // The focus marker
+        let mut slots = vec![];
+        for slot in &self.slots {
+            let room = match slot {
+                Slot::Column(_, width) => *width,
+                Slot::Divider => 1,
+            };
+            if used + room > width {
+                break;
+            }
+            used += room + 1;
+            slots.push(*slot);
+        }
+        while matches!(slots.last(), Some(Slot::Divider)) {
+            slots.pop();
+        }
+
+        let line = |cell: &dyn Fn(&Column) -> String| {
+            slots
+                .iter()
+                .map(|slot| match slot {
+                    Slot::Column(column, width) => fit(&cell(column), *width, column.align),
+                    Slot::Divider => "|".to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        let mut lines = vec![format!(
+            " {}",
+            line(&|column| match column.name {
+                "interval" if page.raceControl.isNeutralised() => "SC Delta".to_string(),
+                _ => column.header.to_string(),
+            })
+        )];
+        let finishes = projection::project(page);
+        let mut order = page.order();
+        if isProjected && !finishes.is_empty() {
+            order.retain(|idx| finish(&finishes, *idx).is_none());
+            order.splice(0..0, finishes.iter().map(|finish| finish.idx));
+        }
+        for idx in order {
+            lines.push(format!(
+                "{}{}",
+                marker(idx),
+                line(&|column| (column.cell)(page, metrics, &finishes, idx))
+            ));
+        }
+
+        let hasBests = slots.iter().any(|slot| {
+            matches!(slot, Slot::Column(column, _) if column.name != "driver" && !(column.best)(page).is_empty())
+        });
+        if hasBests {
+            lines.push(String::new());
+            lines.push(format!(" {}", line(&|column| (column.best)(page))));
+        }
+
+        lines
+    }
+}
+
+// Every preset the timing view can cycle through, starting with the one in use.
+pub struct Layout {
+    pub presets: Vec<Preset>,
+}
+
+impl Layout {
+    // The built in presets, then those in `path`. `start` picks the preset shown first.
+    pub fn load(path: Option<&str>, start: Option<&str>) -> Result<Self, String> {
+        let mut layout = Self::parse(PRESETS).expect("the built in presets are valid");
+
+        if let Some(path) = path {
+            let text = fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?;
+            let file = Self::parse(&text).map_err(|err| format!("{path}: {err}"))?;
+            for preset in file.presets.into_iter().rev() {
+                layout.presets.retain(|p| p.name != preset.name);
+                layout.presets.insert(0, preset);
+            }
+        }
+
+        if let Some(start) = start {
+            let pos = layout
+                .presets
+                .iter()
+                .position(|preset| preset.name == start)
+                .ok_or_else(|| {
+                    format!(
+                        "No tower preset called {start}, there's {}",
+                        layout.names().join(", ")
+                    )
+                })?;
+            layout.presets.rotate_left(pos);
+        }
+
+        Ok(layout)
+    }
+
+    fn names(&self) -> Vec<&str> {
+        self.presets
+            .iter()
+            .map(|preset| preset.name.as_str())
+            .collect()
+    }
+
+    // [name] starts a preset, and each line after it adds columns to it, separated by spaces.
+    // A column is its name, or name:width, and | puts a divider between columns.
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut presets: Vec<Preset> = vec![];
+
+        for (num, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                presets.push(Preset {
+                    name: name.trim().to_string(),
+                    slots: vec![],
+                });
+                continue;
+            }
+
+            let preset = presets
+                .last_mut()
+                .ok_or_else(|| format!("line {}: columns before any [preset]", num + 1))?;
+            for word in line.split_whitespace() {
+                if word == "|" {
+                    preset.slots.push(Slot::Divider);
+                    continue;
+                }
+                let (name, width) = match word.split_once(':') {
+                    Some((name, width)) => (name, Some(width)),
+                    None => (word, None),
+                };
+                let column = COLUMNS.iter().find(|c| c.name == name).ok_or_else(|| {
+                    format!(
+                        "line {}: no column called {name}, there's {}",
+                        num + 1,
+                        COLUMNS
+                            .iter()
+                            .map(|c| c.name)
+                            .collect::<Vec<&str>>()
+                            .join(" ")
+                    )
+                })?;
+                let width = match width {
+                    Some(width) => width
+                        .parse()
+                        .ok()
+                        .filter(|width| *width > 0)
+                        .ok_or_else(|| format!("line {}: invalid width in {word}", num + 1))?,
+                    None => column.width,
+                };
+                preset.slots.push(Slot::Column(column, width));
+            }
+        }
+
+        if let Some(empty) = presets.iter().find(|preset| preset.slots.is_empty()) {
+            return Err(format!("[{}] has no columns", empty.name));
+        }
+        Ok(Self { presets })
+    }
+}
+
+// Pad or cut `cell` to `width` characters on screen. Colour escapes take no room, and the
+// cell's own padding goes first, so a narrow column loses spaces before it loses digits.
+fn fit(cell: &str, width: usize, align: Align) -> String {
+    // Escapes, and the characters that show, in order.
+    let mut parts: Vec<(bool, &str)> = vec![];
+    let mut rest = cell;
+    while let Some(c) = rest.chars().next() {
+        let len = if c == '\x1b' {
+            rest.find(|c: char| c.is_ascii_alphabetic())
+                .map_or(rest.len(), |end| end + 1)
+        } else {
+            c.len_utf8()
+        };
+        parts.push((c != '\x1b', &rest[..len]));
+        rest = &rest[len..];
+    }
+
+    let isText = |(shows, part): &(bool, &str)| *shows && *part != " ";
+    let first = parts.iter().position(isText).unwrap_or(parts.len());
+    let last = parts.iter().rposition(isText).map_or(0, |pos| pos + 1);
+
+    let mut text = String::new();
+    let mut shown = 0;
+    for (pos, (shows, part)) in parts.iter().enumerate() {
+        if !shows {
+            text += part;
+        } else if pos >= first && pos < last && shown < width {
+            text += part;
+            shown += 1;
+        }
+    }
+    if parts.iter().any(|(shows, _)| !shows) {
+        text += "\x1b[0m";
+    }
+
+    let pad = width - shown;
+    match align {
+        Align::Left => format!("{text}{}", " ".repeat(pad)),
+        Align::Right => format!("{}{text}", " ".repeat(pad)),
+        Align::Centre => format!("{}{text}{}", " ".repeat(pad / 2), " ".repeat(pad - pad / 2)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slots(preset: &Preset) -> Vec<String> {
+        preset
+            .slots
+            .iter()
+            .map(|slot| match slot {
+                Slot::Column(column, width) => format!("{}:{width}", column.name),
+                Slot::Divider => "|".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parse_presets() {
+        let layout = Layout::parse(
+            "# Comments and blank lines are skipped
+
+            [mine]
+            pos driver:10 | # so is the rest of a line
+            last
+
+            [other]
+            team",
+        )
+        .unwrap();
+
+        assert_eq!(layout.names(), ["mine", "other"]);
+        assert_eq!(
+            slots(&layout.presets[0]),
+            ["pos:2", "driver:10", "|", "last:8"]
+        );
+        assert_eq!(slots(&layout.presets[1]), ["team:15"]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text: &str| Layout::parse(text).err().unwrap();
+
+        assert!(
+            error("[a]\npos nope").starts_with("line 2: no column called nope, there's pos driver")
+        );
+        assert_eq!(error("[a]\npos:0"), "line 2: invalid width in pos:0");
+        assert_eq!(error("[a]\npos:x"), "line 2: invalid width in pos:x");
+        assert_eq!(error("pos"), "line 1: columns before any [preset]");
+        assert_eq!(error("[a]\n[b]\npos"), "[a] has no columns");
+    }
+
+    #[test]
+    fn built_in_presets() {
+        let layout = Layout::load(None, Some("stewards")).unwrap();
+        assert_eq!(
+            layout.names(),
+            ["stewards", "projection", "race", "strategy", "engineer"]
+        );
+        assert!(Layout::load(None, Some("nope"))
+            .err()
+            .unwrap()
+            .starts_with("No tower preset called nope, there's race"));
+    }
+
+    #[test]
+    fn fit_plain() {
+        assert_eq!(fit("abc", 5, Align::Left), "abc  ");
+        assert_eq!(fit("abc", 5, Align::Right), "  abc");
+        assert_eq!(fit("abc", 6, Align::Centre), " abc  ");
+        assert_eq!(fit("abcdef", 3, Align::Left), "abc");
+
+        // The cell's own padding goes before any of the text does.
+        assert_eq!(fit("   12.345", 6, Align::Right), "12.345");
+        assert_eq!(fit("(S) ", 3, Align::Left), "(S)");
+        assert_eq!(fit("Hülkenberg", 4, Align::Left), "Hülk");
+    }
+
+    #[test]
+    fn fit_escapes() {
+        // Escapes take no room, and are closed off so a cut one doesn't bleed.
+        let red = "\x1b[31m+2\x1b[0m";
+        assert_eq!(fit(red, 4, Align::Right), "  \x1b[31m+2\x1b[0m\x1b[0m");
+        assert_eq!(
+            fit("\x1b[31m12345\x1b[0m", 2, Align::Left),
+            "\x1b[31m12\x1b[0m\x1b[0m"
+        );
+        assert_eq!(
+            fit("\x1b[1m\x1b[0m", 2, Align::Left),
+            "\x1b[1m\x1b[0m\x1b[0m  "
+        );
+    }
+}
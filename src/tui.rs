@@ -0,0 +1,1111 @@
+use crate::metrics::Metrics;
+use crate::strategy::Call;
+use crate::tower::Layout;
+use crate::{Page, PitStop, Tyres};
+use colored::*;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, queue, style, terminal};
+use pitwall::packet::*;
+use std::io::{self, Write};
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The four wheel arrays come RL RR FL FR, they're shown FL FR RL RR.
+static WHEEL_ORDER: [usize; 4] = [2, 3, 0, 1];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum View {
+    Timing,
+    Telemetry,
+    Damage,
+    Weather,
+    Tyres,
+    Traps,
+    Pits,
+    Events,
+    Results,
+}
+
+// Tab order, also what the number keys pick.
+static VIEWS: [(View, &str); 9] = [
+    (View::Timing, "Timing"),
+    (View::Telemetry, "Telemetry"),
+    (View::Damage, "Damage"),
+    (View::Weather, "Weather"),
+    (View::Tyres, "Tyres"),
+    (View::Traps, "Traps"),
+    (View::Pits, "Pits"),
+    (View::Events, "Events"),
+    (View::Results, "Results"),
+];
+
+// What a key press asks the main loop to do.
+pub enum Action {
+    Nothing,
+    Export,
+    Quit,
+}
+
+// Draw the dashboard from a thread of its own, so a slow terminal only ever slows the frames
+// and never the packets. Key presses the main loop has to act on come back on the channel.
+pub fn spawn(
+    fps: u32,
+    rainThreshold: u8,
+    layout: Layout,
+    page: Arc<Mutex<Page>>,
+    metrics: Arc<Mutex<Metrics>>,
+    changed: Arc<AtomicBool>,
+) -> io::Result<Receiver<Action>> {
+    let tui = Tui::new(fps, rainThreshold, layout)?;
+    let (tx, actions) = mpsc::channel();
+
+    // A panic anywhere would otherwise leave the terminal in raw mode.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        restore();
+        hook(info);
+    }));
+
+    thread::spawn(move || tui.run(&page, &metrics, &changed, &tx));
+    Ok(actions)
+}
+
+// The full screen dashboard. Each view is rendered as lines of text, and only
+// the lines that differ from what's on screen are written out again.
+struct Tui {
+    view: View,
+    focus: Option<usize>, // Car index picked with the arrow keys, the player's car until then
+    scroll: usize,        // Lines of the view scrolled past
+    drawn: Vec<String>,   // What's on screen, line by line
+    drawnAt: Instant,
+    interval: Duration, // Between frames, from --fps
+    isDirty: bool,      // Something changed since the last frame
+    hadResults: bool,   // The results view is brought up once, when the classification arrives
+    rainThreshold: u8,
+    layout: Layout, // Tower presets, cycled with c
+    preset: usize,
+    isProjected: bool, // The tower in projected finishing order, toggled with p
+    keys: Receiver<Event>,
+}
+
+impl Tui {
+    fn new(fps: u32, rainThreshold: u8, layout: Layout) -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let mut stdout = io::stdout();
+        queue!(
+            stdout,
+            terminal::EnterAlternateScreen,
+            terminal::DisableLineWrap,
+            cursor::Hide
+        )?;
+        stdout.flush()?;
+
+        let (tx, keys) = mpsc::channel();
+        thread::spawn(move || {
+            while let Ok(event) = event::read() {
+                if tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            view: View::Timing,
+            focus: None,
+            scroll: 0,
+            drawn: vec![],
+            drawnAt: Instant::now(),
+            interval: Duration::from_secs(1) / fps.max(1),
+            isDirty: true,
+            hadResults: false,
+            rainThreshold,
+            layout,
+            preset: 0,
+            isProjected: false,
+            keys,
+        })
+    }
+
+    // Answer keys as they come, and draw when something changed and the last frame was
+    // long enough ago. The page is only locked to render the lines, never while they're written.
+    fn run(
+        mut self,
+        page: &Mutex<Page>,
+        metrics: &Mutex<Metrics>,
+        changed: &AtomicBool,
+        tx: &Sender<Action>,
+    ) {
+        loop {
+            let wait = self.interval.saturating_sub(self.drawnAt.elapsed());
+            match self.keys.recv_timeout(wait) {
+                Ok(event) => match self.input(event, &page.lock().unwrap()) {
+                    Action::Nothing => {}
+                    Action::Quit => break,
+                    action => {
+                        if tx.send(action).is_err() {
+                            return;
+                        }
+                    }
+                },
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            if changed.swap(false, Ordering::Relaxed) {
+                self.isDirty = true;
+            }
+            if !self.isDirty || self.drawnAt.elapsed() < self.interval {
+                continue;
+            }
+
+            let frame = terminal::size().map(|(width, height)| {
+                self.frame(
+                    &page.lock().unwrap(),
+                    &metrics.lock().unwrap(),
+                    width as usize,
+                    height as usize,
+                )
+            });
+            if let Err(err) = frame.and_then(|frame| self.paint(frame)) {
+                drop(self);
+                eprintln!("Couldn't draw the dashboard: {err}");
+                let _ = tx.send(Action::Quit);
+                return;
+            }
+        }
+
+        // Put the terminal back before the main loop winds up.
+        drop(self);
+        let _ = tx.send(Action::Quit);
+    }
+
+    // The leader's when spectating and nothing has been picked.
+    fn focused(&self, page: &Page) -> usize {
+        self.focus
+            .or(page.player())
+            .or_else(|| page.order().first().copied())
+            .unwrap_or(0)
+    }
+
+    fn input(&mut self, event: Event, page: &Page) -> Action {
+        self.isDirty = true;
+        match event {
+            Event::Key(key) if key.kind != KeyEventKind::Release => self.key(key, page),
+            Event::Resize(..) => {
+                // Everything goes again.
+                self.drawn.clear();
+                Action::Nothing
+            }
+            _ => Action::Nothing,
+        }
+    }
+
+    fn key(&mut self, key: KeyEvent, page: &Page) -> Action {
+        let current = VIEWS
+            .iter()
+            .position(|(view, _)| *view == self.view)
+            .unwrap_or(0);
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit
+            }
+            KeyCode::Char('q') => return Action::Quit,
+            KeyCode::Enter => return Action::Export,
+            KeyCode::Char('c') => {
+                self.show(View::Timing);
+                self.preset = (self.preset + 1) % self.layout.presets.len();
+            }
+            KeyCode::Char('p') => {
+                self.show(View::Timing);
+                self.isProjected = !self.isProjected;
+            }
+            KeyCode::Char(digit @ '1'..='9') => self.show(VIEWS[digit as usize - '1' as usize].0),
+            KeyCode::Tab | KeyCode::Right => self.show(VIEWS[(current + 1) % VIEWS.len()].0),
+            KeyCode::BackTab | KeyCode::Left => {
+                self.show(VIEWS[(current + VIEWS.len() - 1) % VIEWS.len()].0)
+            }
+            KeyCode::Up => self.step(page, -1),
+            KeyCode::Down => self.step(page, 1),
+            KeyCode::Esc => self.focus = None,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll += 10,
+            KeyCode::Home => self.scroll = 0,
+            _ => {}
+        }
+
+        Action::Nothing
+    }
+
+    fn show(&mut self, view: View) {
+        self.view = view;
+        self.scroll = 0;
+    }
+
+    // Move the focus up or down the running order.
+    fn step(&mut self, page: &Page, by: isize) {
+        let order = page.order();
+        if order.is_empty() {
+            return;
+        }
+        let focused = self.focused(page);
+        let next = match order.iter().position(|idx| *idx == focused) {
+            Some(pos) => (pos as isize + by).clamp(0, order.len() as isize - 1) as usize,
+            None => 0,
+        };
+        self.focus = Some(order[next]);
+    }
+
+    // Every line of the screen, `height` of them.
+    fn frame(
+        &mut self,
+        page: &Page,
+        metrics: &Metrics,
+        width: usize,
+        height: usize,
+    ) -> Vec<String> {
+        if page.results.is_some() != self.hadResults {
+            self.hadResults = page.results.is_some();
+            if self.hadResults {
+                self.show(View::Results);
+            }
+        }
+
+        let body = match self.view {
+            View::Timing => self.timing(page, metrics, width),
+            View::Telemetry => self.telemetry(page, metrics),
+            View::Damage => self.damage(page, metrics),
+            View::Weather => weather(page, self.rainThreshold),
+            View::Tyres => self.tyres(page, metrics),
+            View::Traps => self.traps(page),
+            View::Pits => self.pits(page),
+            View::Events => page.log.iter().rev().cloned().collect(),
+            View::Results => results(page),
+        };
+
+        let mut footer = vec![format!(
+            "{:>18} {}",
+            "",
+            "Tab 1-9 view  c columns  p projection  Up/Down focus  Esc player  PgUp/PgDn scroll  Enter export  q quit"
+                .dimmed()
+        )];
+        footer.extend(
+            page.latestNotices()
+                .iter()
+                .map(|notice| format!("{:>18} {notice}", "")),
+        );
+
+        // Header, tabs, then as much of the view as fits above the footer.
+        let rows = height.saturating_sub(3 + footer.len());
+        self.scroll = self.scroll.min(body.len().saturating_sub(rows));
+
+        let mut frame = vec![header(page), self.tabs(), String::new()];
+        frame.extend(body.into_iter().skip(self.scroll).take(rows));
+        frame.resize(height.saturating_sub(footer.len()), String::new());
+        frame.extend(footer);
+        frame.truncate(height);
+        frame
+    }
+
+    // Write out the lines that changed since the last frame.
+    fn paint(&mut self, frame: Vec<String>) -> io::Result<()> {
+        self.isDirty = false;
+        self.drawnAt = Instant::now();
+
+        let mut stdout = io::stdout();
+        if self.drawn.len() != frame.len() {
+            self.drawn.clear();
+            queue!(stdout, terminal::Clear(terminal::ClearType::All))?;
+        }
+        for (row, line) in frame.iter().enumerate() {
+            if self.drawn.get(row) == Some(line) {
+                continue;
+            }
+            queue!(
+                stdout,
+                cursor::MoveTo(0, row as u16),
+                style::Print(line),
+                style::ResetColor,
+                terminal::Clear(terminal::ClearType::UntilNewLine)
+            )?;
+        }
+        stdout.flush()?;
+
+        self.drawn = frame;
+        Ok(())
+    }
+
+    fn tabs(&self) -> String {
+        VIEWS
+            .iter()
+            .enumerate()
+            .map(|(idx, (view, name))| {
+                let tab = match view {
+                    View::Timing => format!(
+                        " {} {name} ({}{}) ",
+                        idx + 1,
+                        self.layout.presets[self.preset].name,
+                        if self.isProjected { ", projected" } else { "" }
+                    ),
+                    _ => format!(" {} {name} ", idx + 1),
+                };
+                if *view == self.view {
+                    tab.black().on_white().to_string()
+                } else {
+                    tab
+                }
+            })
+            .collect()
+    }
+
+    // "> " against the car in focus.
+    fn marker(&self, page: &Page, idx: usize) -> &'static str {
+        if idx == self.focused(page) {
+            ">"
+        } else {
+            " "
+        }
+    }
+
+    fn timing(&self, page: &Page, metrics: &Metrics, width: usize) -> Vec<String> {
+        let mut lines = vec![];
+
+        // Marshal Zones, none when spectating
+        if let Some(player) = page.player() {
+            let lapDistance = page.car[player].lapDistance;
+            lines.push(page.track.getZones(lapDistance));
+            if let Some(alert) = page.track.getAlert(lapDistance) {
+                lines.push(alert);
+            }
+        }
+
+        // The tower, in the columns of the preset picked with c, and in the order the cars
+        // are heading for at the flag once p is pressed and there's pace to go on.
+        lines.extend(self.layout.presets[self.preset].render(
+            page,
+            metrics,
+            self.isProjected,
+            width,
+            |idx| self.marker(page, idx),
+        ));
+
+        // Stewards
+        let provisional = page.provisional();
+        if provisional
+            .iter()
+            .any(|idx| page.car[*idx].penalties.timePenalty > 0)
+        {
+            lines.push(String::new());
+            let mut line = format!("{:>18}", "Provisional");
+            for (pos, idx) in provisional.iter().enumerate() {
+                let car = &page.car[*idx];
+                line += &format!(" {}.{}", pos + 1, car.driver.name);
+                if car.penalties.timePenalty > 0 {
+                    line += &format!("(+{}s)", car.penalties.timePenalty)
+                        .yellow()
+                        .to_string();
+                }
+            }
+            lines.push(line);
+        }
+        for decision in page.stewards.latest(5) {
+            lines.push(format!(
+                "{:>18} {decision}",
+                page.car[decision.vehicleIdx as usize % page.car.len()]
+                    .driver
+                    .name
+            ));
+        }
+
+        // The rain alerts stay here, the rest of the forecast has its own view.
+        let alerts = page.forecast.getAlerts(page.session, self.rainThreshold);
+        if !alerts.is_empty() {
+            lines.push(String::new());
+            lines.extend(alerts);
+        }
+
+        lines
+    }
+
+    fn telemetry(&self, page: &Page, metrics: &Metrics) -> Vec<String> {
+        let mut lines = vec![format!(
+            " {:2} {:>15} {:>3} {:>1} {:>5} {:<10} {:<10} {:>5} {:^3} {:>3} {:<8} {:>5} {:>5} {:<8}",
+            "P", "Driver", "KPH", "G", "RPM", "Throttle", "Brake", "Steer", "DRS", "ERS", "Deploy", "Fuel", "Laps", "Mix"
+        )];
+
+        for (pos, idx) in page.order().into_iter().enumerate() {
+            let Some(live) = metrics.cars.get(idx) else {
+                continue;
+            };
+            let car = &page.car[idx];
+            let t = live.telemetry;
+            let s = live.status;
+
+            lines.push(format!(
+                "{}{:02} {:>15.15} {:>3} {:>1} {:>5} {} {} {:>+5.2} {} {} {:<8} {:>5.1} {:>+5.1} {:<8}",
+                self.marker(page, idx),
+                pos + 1,
+                car.driver.name,
+                { t.speed }.kph,
+                { t.gear },
+                { t.engineRPM },
+                bar(t.throttle, Color::Green),
+                bar(t.brake, Color::Red),
+                { t.steer },
+                car.Drs,
+                car.Ers,
+                format!("{:?}", { s.ersDeployMode }),
+                { s.fuelInTank },
+                { s.fuelRemainingLaps },
+                format!("{:?}", { s.fuelMix }),
+            ));
+        }
+
+        // Everything else about the car in focus.
+        let idx = self.focused(page);
+        if let Some(live) = metrics.cars.get(idx) {
+            let t = live.telemetry;
+            let s = live.status;
+
+            lines.push(String::new());
+            lines.push(format!(
+                "{:>18} {}",
+                "Focus",
+                page.car[idx].driver.name.bold()
+            ));
+            lines.push(format!(
+                "{:>18} {}",
+                "Brakes C",
+                wheels({ t.brakesTemperature }.map(|v| v as f32), 0)
+            ));
+            lines.push(format!(
+                "{:>18} {}",
+                "Tyre surface C",
+                wheels(t.tyresSurfaceTemperature.map(|v| v as f32), 0)
+            ));
+            lines.push(format!(
+                "{:>18} {}",
+                "Tyre inner C",
+                wheels(t.tyresInnerTemperature.map(|v| v as f32), 0)
+            ));
+            lines.push(format!(
+                "{:>18} {}",
+                "Tyre pressure psi",
+                wheels(t.tyresPressure, 1)
+            ));
+            lines.push(format!("{:>18} {}", "Engine C", { t.engineTemperature }));
+            lines.push(format!("{:>18} {}% front", "Brake bias", {
+                s.frontBrakeBias
+            }));
+            lines.push(format!(
+                "{:>18} {}% of {} max",
+                "Rev lights",
+                { t.revLightsPercent },
+                { s.maxRPM }
+            ));
+            lines.push(format!(
+                "{:>18} ICE {:.0}kW MGU-K {:.0}kW",
+                "Power",
+                { s.enginePowerICE } / 1000.0,
+                { s.enginePowerMGUK } / 1000.0
+            ));
+            lines.push(format!(
+                "{:>18} {:.2}MJ deployed, {:.2}MJ harvested (K {:.2} H {:.2})",
+                "ERS this lap",
+                { s.ersDeployedThisLap } / 1e6,
+                ({ s.ersHarvestedThisLapMGUK } + { s.ersHarvestedThisLapMGUH }) / 1e6,
+                { s.ersHarvestedThisLapMGUK } / 1e6,
+                { s.ersHarvestedThisLapMGUH } / 1e6
+            ));
+            lines.push(format!(
+                "{:>18} TC {:?} ABS {:?}",
+                "Assists",
+                { s.tractionControl },
+                { s.antiLockBrakes }
+            ));
+        }
+
+        lines
+    }
+
+    fn damage(&self, page: &Page, metrics: &Metrics) -> Vec<String> {
+        let mut lines = vec![format!(
+            " {:2} {:>15} {:>5} {:>5} {:>5} {:>5} {:>5} {:>5} {:>5} {:>5} {:>5}",
+            "P", "Driver", "FW L", "FW R", "RW", "Floor", "Diff", "Pod", "GBox", "Eng", "Fault"
+        )];
+
+        for (pos, idx) in page.order().into_iter().enumerate() {
+            let Some(live) = metrics.cars.get(idx) else {
+                continue;
+            };
+            let d = live.damage;
+            let mut faults = vec![];
+            if d.drsFault == 1 {
+                faults.push("DRS");
+            }
+            if d.ersFault == 1 {
+                faults.push("ERS");
+            }
+            if d.engineBlown == 1 {
+                faults.push("Blown");
+            }
+            if d.engineSeized == 1 {
+                faults.push("Seized");
+            }
+
+            lines.push(format!(
+                "{}{:02} {:>15.15} {} {} {} {} {} {} {} {} {}",
+                self.marker(page, idx),
+                pos + 1,
+                page.car[idx].driver.name,
+                percent(d.frontLeftWingDamage),
+                percent(d.frontRightWingDamage),
+                percent(d.rearWingDamage),
+                percent(d.floorDamage),
+                percent(d.diffuserDamage),
+                percent(d.sidepodDamage),
+                percent(d.gearBoxDamage),
+                percent(d.engineDamage),
+                faults.join(" ").red(),
+            ));
+        }
+
+        // Wear that builds up over the weekend, and the wheels, for the car in focus.
+        let idx = self.focused(page);
+        if let Some(live) = metrics.cars.get(idx) {
+            let d = live.damage;
+
+            lines.push(String::new());
+            lines.push(format!(
+                "{:>18} {}",
+                "Focus",
+                page.car[idx].driver.name.bold()
+            ));
+            lines.push(format!(
+                "{:>18} ICE {} MGU-H {} MGU-K {} ES {} CE {} TC {}",
+                "Engine wear",
+                percent(d.engineICEWear),
+                percent(d.engineMGUHWear),
+                percent(d.engineMGUKWear),
+                percent(d.engineESWear),
+                percent(d.engineCEWear),
+                percent(d.engineTCWear)
+            ));
+            lines.push(format!(
+                "{:>18} {}",
+                "Brake damage %",
+                wheels(d.brakesDamage.map(|v| v as f32), 0)
+            ));
+            lines.push(format!(
+                "{:>18} {}",
+                "Tyre damage %",
+                wheels(d.tyresDamage.map(|v| v as f32), 0)
+            ));
+            lines.push(format!(
+                "{:>18} {}",
+                "Tyre blisters %",
+                wheels(d.tyreBlisters.map(|v| v as f32), 0)
+            ));
+        }
+
+        lines
+    }
+
+    fn tyres(&self, page: &Page, metrics: &Metrics) -> Vec<String> {
+        let mut lines = vec![format!(
+            " {:2} {:>15} {:>4} {:>3} {:>4} {:>6} | {:^23} | {:^23} | {:^23}",
+            "P",
+            "Driver",
+            "Tyre",
+            "Age",
+            "Pits",
+            "Deg",
+            "Wear % FL FR RL RR",
+            "Surface C",
+            "Inner C"
+        )];
+
+        for (pos, idx) in page.order().into_iter().enumerate() {
+            let Some(live) = metrics.cars.get(idx) else {
+                continue;
+            };
+            let car = &page.car[idx];
+            let t = live.telemetry;
+            let wear = live.damage.tyresWear;
+
+            lines.push(format!(
+                "{}{:02} {:>15.15} {:>4} {:>3} {:>4} {} | {} | {} | {}",
+                self.marker(page, idx),
+                pos + 1,
+                car.driver.name,
+                car.tyres,
+                car.tyres.age,
+                car.pitCount,
+                car.getDegradation(),
+                wheels([wear.RL, wear.RR, wear.FL, wear.FR], 0),
+                wheels(t.tyresSurfaceTemperature.map(|v| v as f32), 0),
+                wheels(t.tyresInnerTemperature.map(|v| v as f32), 0),
+            ));
+        }
+
+        // The stints so far of the car in focus, from its laps.
+        let idx = self.focused(page);
+        let car = &page.car[idx];
+        lines.push(String::new());
+        lines.push(format!("{:>18} {}", "Focus", car.driver.name.bold()));
+        let mut stints: Vec<(VisualCompound, u8, u8)> = vec![];
+        for lap in &car.laps {
+            match stints.last_mut() {
+                Some((tyre, _, to)) if *tyre as u8 == lap.tyre as u8 && !lap.isPit => {
+                    *to = lap.lapNum
+                }
+                _ => stints.push((lap.tyre, lap.lapNum, lap.lapNum)),
+            }
+        }
+        for (stint, (tyre, from, to)) in stints.iter().enumerate() {
+            lines.push(format!(
+                "{:>18} {tyre} laps {from}-{to}",
+                format!("Stint {}", stint + 1)
+            ));
+        }
+        for stop in &car.pitStops {
+            lines.push(format!(
+                "{:>18} lap {} lane {:.1}s stop {:.1}s",
+                if stop.isDriveThrough() { "Drive through" } else { "Pit stop" },
+                stop.lap,
+                stop.laneInMS as f32 / 1000.0,
+                stop.stopInMS as f32 / 1000.0
+            ));
+        }
+
+        lines
+    }
+
+    // Every stop so far, in the order they were made, then what a stop costs here
+    // and where the car in focus would come out if it came in now.
+    fn pits(&self, page: &Page) -> Vec<String> {
+        let mut lines = vec![format!(
+            " {:>3} {:>15} {:>6} {:>6} {:>6} {:>6}  {:<14} {:<9}",
+            "Lap", "Driver", "Lane", "Stop", "Drive", "Loss", "Tyres", "Position"
+        )];
+
+        let mut stops: Vec<(usize, &PitStop)> = page
+            .order()
+            .into_iter()
+            .flat_map(|idx| page.car[idx].pitStops.iter().map(move |stop| (idx, stop)))
+            .collect();
+        stops.sort_by_key(|(_, stop)| stop.lap);
+
+        for (idx, stop) in &stops {
+            let car = &page.car[*idx];
+            let loss = match car.netLoss(stop) {
+                Some(ms) => format!("{:.1}", ms as f32 / 1000.0),
+                None => String::new(),
+            };
+            let lost = match stop.positionsLost() {
+                0 => String::new(),
+                n @ 1.. => format!("-{n}").red().to_string(),
+                n => format!("+{}", -n).green().to_string(),
+            };
+            let stationary = match stop.isDriveThrough() {
+                true => "DT".to_string(),
+                false => format!("{:.1}", stop.stopInMS as f32 / 1000.0),
+            };
+            lines.push(format!(
+                "{}{:>3} {:>15.15} {:>6.1} {:>6} {:>6.1} {:>6}  {} {:>2} > {}  P{:<2} > P{:<2} {}",
+                self.marker(page, *idx),
+                stop.lap,
+                car.driver.name,
+                stop.laneInMS as f32 / 1000.0,
+                stationary,
+                stop.laneInMS.saturating_sub(stop.stopInMS) as f32 / 1000.0,
+                loss,
+                Tyres { visual: stop.tyreIn, ..Tyres::default() },
+                stop.ageIn,
+                Tyres { visual: stop.tyreOut, ..Tyres::default() },
+                stop.positionIn,
+                stop.positionOut,
+                lost
+            ));
+        }
+        if stops.is_empty() {
+            lines.push(format!("{:>18} {}", "", "No stops yet".dimmed()));
+        }
+
+        lines.push(String::new());
+        lines.push(format!(
+            "{:>18} {}",
+            "Pit loss",
+            match page.pitLoss() {
+                Some((ms, 0)) => format!("{:.1}s, from earlier sessions here", ms as f32 / 1000.0),
+                Some((ms, 1)) => format!("{:.1}s, from 1 stop", ms as f32 / 1000.0),
+                Some((ms, n)) => format!("{:.1}s, from {n} stops", ms as f32 / 1000.0),
+                None => "Not known until a stop's out lap is done".to_string(),
+            }
+        ));
+        if let Some((idx, stop)) = stops
+            .iter()
+            .filter(|(_, stop)| !stop.isDriveThrough())
+            .min_by_key(|(_, stop)| stop.stopInMS)
+        {
+            lines.push(format!(
+                "{:>18} {} {:.1}s on lap {}",
+                "Quickest stop",
+                page.car[*idx].driver.name,
+                stop.stopInMS as f32 / 1000.0,
+                stop.lap
+            ));
+        }
+
+        let idx = self.focused(page);
+        if let Some(rejoin) = page.rejoin(idx) {
+            let mut line = format!("P{}", rejoin.position).bold().to_string();
+            if let Some((ahead, by)) = rejoin.ahead {
+                line += &format!(", {:.1}s behind {}", by as f32 / 1000.0, page.car[ahead].driver.name);
+            }
+            if let Some((behind, by)) = rejoin.behind {
+                line += &format!(", {:.1}s ahead of {}", by as f32 / 1000.0, page.car[behind].driver.name);
+            }
+            lines.push(format!(
+                "{:>18} {} would rejoin {line}",
+                "Pitting now",
+                page.car[idx].driver.name
+            ));
+        }
+
+        // Whether this is the lap to stop, for the player and their team-mate, unless spectating.
+        for idx in page.player().into_iter().chain(page.teammate()) {
+            lines.push(String::new());
+            lines.extend(strategy(page, idx));
+        }
+
+        lines
+    }
+
+    // Everyone's best through the speed trap, fastest first, against the fastest and the car in focus.
+    fn traps(&self, page: &Page) -> Vec<String> {
+        let mut lines = vec![format!(
+            " {:2} {:>15} {:<15} {:>6} {:>3} {:>6} {:>6} {:>6}",
+            "P", "Driver", "Team", "Best", "Lap", "Gap", "Focus", "Last"
+        )];
+
+        let ranking = page.speedTraps();
+        let fastest = ranking.first().map(|idx| page.car[*idx].speedTrap.speed);
+        let focused = self.focused(page);
+        let focus = page.car[focused].speedTrap;
+
+        for (pos, idx) in ranking.iter().enumerate() {
+            let car = &page.car[*idx];
+            let trap = car.speedTrap;
+
+            let best = format!("{:>6.1}", trap.speed);
+            let gap = match fastest {
+                Some(speed) if pos > 0 => format!("{:+.1}", trap.speed - speed),
+                _ => String::new(),
+            };
+            let versus = if focus.isSet() && *idx != focused {
+                format!("{:+.1}", trap.speed - focus.speed)
+            } else {
+                String::new()
+            };
+            let last = match trap.last {
+                0.0 => format!("{:>6}", ""),
+                speed if trap.wasOB => format!("{speed:>6.1}").purple().to_string(),
+                speed if trap.wasBest => format!("{speed:>6.1}").green().to_string(),
+                speed => format!("{speed:>6.1}"),
+            };
+
+            lines.push(format!(
+                "{}{:02} {:>15.15} {:<15.15} {} {:>3} {:>6} {:>6} {}",
+                self.marker(page, *idx),
+                pos + 1,
+                car.driver.name,
+                car.team.id.to_string(),
+                if pos == 0 { best.purple().to_string() } else { best },
+                format!("L{}", trap.lap),
+                gap,
+                versus,
+                last
+            ));
+        }
+
+        // Anyone yet to go through, in race order.
+        for idx in page.order().into_iter().filter(|idx| !ranking.contains(idx)) {
+            let car = &page.car[idx];
+            lines.push(format!(
+                "{}{:>2} {:>15.15} {:<15.15}",
+                self.marker(page, idx),
+                "-",
+                car.driver.name,
+                car.team.id.to_string()
+            ));
+        }
+
+        lines
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+// The terminal as it was before the dashboard took it over.
+fn restore() {
+    let mut stdout = io::stdout();
+    let _ = queue!(
+        stdout,
+        terminal::EnableLineWrap,
+        cursor::Show,
+        terminal::LeaveAlternateScreen
+    );
+    let _ = stdout.flush();
+    let _ = terminal::disable_raw_mode();
+}
+
+fn header(page: &Page) -> String {
+    format!(
+        "{session:>5} {track} {lapLeader:02} {lapTotal:02} [{timeLeft} of {duration}] {raceControl}",
+        session     = page.session,
+        track       = page.trackId,
+        lapLeader   = page.lap.leader,
+        lapTotal    = page.lap.total,
+        timeLeft    = page.sessionTimeLeft,
+        duration    = page.sessionDuration,
+        raceControl = page.raceControl,
+    )
+}
+
+fn weather(page: &Page, rainThreshold: u8) -> Vec<String> {
+    let mut lines = vec![page.forecast.to_string()];
+    lines.extend(page.forecast.getTimeline(page.session));
+    lines.extend(page.forecast.getAlerts(page.session, rainThreshold));
+    lines
+}
+
+fn results(page: &Page) -> Vec<String> {
+    let Some(results) = &page.results else {
+        return vec!["No classification yet, it arrives as the session ends.".to_string()];
+    };
+
+    let mut lines: Vec<String> = results.to_string().lines().map(String::from).collect();
+    if let Some(standings) = page.standings.as_ref().filter(|s| !s.drivers.is_empty()) {
+        lines.push(String::new());
+        lines.extend(standings.to_string().lines().map(String::from));
+    }
+    lines
+}
+
+// Whether pitting this lap gets `idx` past the car ahead, and whether the car behind can answer it.
+fn strategy(page: &Page, idx: usize) -> Vec<String> {
+    let car = &page.car[idx];
+    let seconds = |ms: f32| format!("{:.1}s", ms.abs() / 1000.0);
+    let name = |idx: usize| &page.car[idx].driver.name;
+
+    let Some(call) = Call::new(page, idx) else {
+        return vec![format!(
+            "{:>18} {} {}",
+            "Strategy",
+            car.driver.name.bold(),
+            "waiting for some green laps".dimmed()
+        )];
+    };
+
+    let mut lines = vec![format!(
+        "{:>18} {} lap {}, {} {} laps old, pace {:.3} {:+.3}/lap, {:.3} on new tyres, warm-up {:.1}s",
+        "Strategy",
+        car.driver.name.bold(),
+        car.lapNum,
+        car.tyres,
+        car.tyres.age,
+        call.stint.pace / 1000.0,
+        call.stint.deg / 1000.0,
+        call.stint.fresh / 1000.0,
+        call.warmUp / 1000.0
+    )];
+
+    let window = page.pitWindow;
+    if idx == page.playerCarIndex as usize && window.idealLap > 0 {
+        lines.push(format!(
+            "{:>18} ideal lap {}, latest lap {}, rejoining P{} by the game's reckoning",
+            "Pit window", window.idealLap, window.latestLap, window.rejoinPosition
+        ));
+    }
+
+    if let Some(ahead) = call.ahead {
+        let undercut = match call.undercut() {
+            Some(margin) if margin > 0.0 => {
+                format!("undercut works, out {} ahead", seconds(margin)).green()
+            }
+            Some(margin) => format!("undercut falls {} short", seconds(margin)).red(),
+            None => "no pace for them yet".dimmed(),
+        };
+        // Only worth a mention when stopping first doesn't work.
+        let overcut = match (call.overcut(), call.undercut()) {
+            (Some(margin), Some(undercut)) if margin > 0.0 && undercut <= 0.0 => {
+                format!(", but staying out gets by if they stop first, by {}", seconds(margin))
+            }
+            _ => String::new(),
+        };
+        lines.push(format!(
+            "{:>18} {} {} up the road, {undercut}{overcut}",
+            "Ahead",
+            name(ahead.idx),
+            seconds(ahead.gap)
+        ));
+    }
+
+    if let Some(behind) = call.behind {
+        let covered = match call.covered() {
+            Some(margin) if margin > 0.0 => {
+                format!("stopping now is covered, still {} ahead after they stop", seconds(margin)).green()
+            }
+            Some(margin) => format!("stopping now they come out {} ahead", seconds(margin)).red(),
+            None => "no pace for them yet".dimmed(),
+        };
+        let threat = match call.threat() {
+            Some(margin) if margin > 0.0 => format!(
+                ", {}",
+                format!("they undercut by {} if they stop first", seconds(margin)).yellow()
+            ),
+            _ => String::new(),
+        };
+        lines.push(format!(
+            "{:>18} {} {} back, {covered}{threat}",
+            "Behind",
+            name(behind.idx),
+            seconds(behind.gap)
+        ));
+    }
+
+    lines
+}
+
+// 0 to 1 as ten blocks.
+fn bar(value: f32, color: Color) -> String {
+    let filled = (value.clamp(0.0, 1.0) * 10.0).round() as usize;
+    format!(
+        "{}{}",
+        "█".repeat(filled).color(color),
+        " ".repeat(10 - filled)
+    )
+}
+
+// Green when whole, yellow then red as it gets worse.
+fn percent(value: u8) -> String {
+    let text = format!("{value:>4}%");
+    match value {
+        0..=9 => text.green().to_string(),
+        10..=29 => text.yellow().to_string(),
+        _ => text.red().to_string(),
+    }
+}
+
+// One value a wheel, FL FR RL RR.
+fn wheels(values: [f32; 4], decimals: usize) -> String {
+    WHEEL_ORDER
+        .iter()
+        .map(|wheel| format!("{:>5.*}", decimals, values[*wheel]))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Each event as a line for the events view, None for the ones that aren't worth one.
+pub fn describe(e: &PacketEvent, page: &Page) -> Option<String> {
+    let name = |idx: u8| {
+        page.car
+            .get(idx as usize)
+            .map_or_else(|| format!("car {idx}"), |car| car.driver.name.clone())
+    };
+    let details = e.eventDetails;
+
+    // SAFETY: the union is read as the variant the event code says it holds.
+    let text = unsafe {
+        match e.eventType() {
+            EventType::SessionStarted => "Session started".to_string(),
+            EventType::SessionEnded => "Session ended".to_string(),
+            EventType::FastestLap => {
+                let d = details.fastestLap;
+                format!("Fastest lap {} {:.3}", name(d.vehicleIdx), { d.lapTime })
+            }
+            EventType::Retirement => format!("Retirement {}", name(details.retirement.vehicleIdx)),
+            EventType::DRSEnabled => "DRS enabled".to_string(),
+            EventType::DRSDisabled => "DRS disabled".to_string(),
+            EventType::TeamMateInPits => {
+                format!(
+                    "Team mate in the pits {}",
+                    name(details.teamMateInPits.vehicleIdx)
+                )
+            }
+            EventType::ChequeredFlag => "Chequered flag".to_string(),
+            EventType::RaceWinner => format!("Race winner {}", name(details.raceWinner.vehicleIdx)),
+            EventType::Penalty => {
+                let d = details.penalty;
+                format!(
+                    "Penalty {} {:?} for {:?}",
+                    name(d.vehicleIdx),
+                    { d.penaltyType },
+                    { d.infringementType }
+                )
+            }
+            EventType::SpeedTrap => {
+                let d = details.speedTrap;
+                format!("Speed trap {} {:.1}kph", name(d.vehicleIdx), { d.speed })
+            }
+            EventType::StartLights => format!("{} lights", { details.startLights.numLights }),
+            EventType::LightsOut => "Lights out".to_string(),
+            EventType::DriveThroughPenaltyServed => {
+                format!(
+                    "Drive through served {}",
+                    name(details.driveThroughPenaltyServed.vehicleIdx)
+                )
+            }
+            EventType::StopGoPenaltyServed => {
+                format!(
+                    "Stop go served {}",
+                    name(details.stopGoPenaltyServed.vehicleIdx)
+                )
+            }
+            EventType::Flashback => "Flashback".to_string(),
+            EventType::RedFlag => "Red flag".to_string(),
+            EventType::Overtake => {
+                let d = details.overtake;
+                format!(
+                    "Overtake {} on {}",
+                    name(d.overtakingVehicleIdx),
+                    name(d.beingOvertakenVehicleIdx)
+                )
+            }
+            EventType::SafetyCar => {
+                let d = details.safetyCar;
+                format!(
+                    "Safety car {:?} {}",
+                    { d.safetyCarType },
+                    match d.eventType {
+                        0 => "deployed",
+                        1 => "returning",
+                        2 => "returned",
+                        _ => "resume race",
+                    }
+                )
+            }
+            EventType::Collision => {
+                let d = details.collision;
+                format!(
+                    "Collision {} and {}",
+                    name(d.vehicle1Idx),
+                    name(d.vehicle2Idx)
+                )
+            }
+            EventType::Buttons | EventType::Poisoned => return None,
+        }
+    };
+
+    let time = e.header.sessionTime as u32;
+    Some(format!("{:02}:{:02} {text}", time / 60, time % 60))
+}
@@ -0,0 +1,180 @@
+use pitwall::packet::*;
+use colored::*;
+use std::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// Which rubber the conditions call for.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Rubber {
+    #[default]
+    Slick,
+    Inter,
+    Wet,
+}
+
+impl Rubber {
+    pub fn from_weather(weather: Weather) -> Self {
+        match weather {
+            Weather::RainLight => Rubber::Inter,
+            Weather::RainHeavy | Weather::RainStorm => Rubber::Wet,
+            _ => Rubber::Slick,
+        }
+    }
+}
+
+impl fmt::Display for Rubber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rubber::Slick => write!(f, "{}", "Slicks".red()),
+            Rubber::Inter => write!(f, "{}", "Inters".green()),
+            Rubber::Wet => write!(f, "{}", "Wets".blue()),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Forecast {
+    // PacketSession
+    pub weather: Weather,                // weather
+    pub trackTemperature: i8,            // trackTemperature
+    pub airTemperature: i8,              // airTemperature
+    pub accuracy: Accuracy,              // forecastAccuracy
+    pub samples: Vec<WeatherForecast>,   // weatherForecast, numWeatherForecasts long
+}
+
+impl Forecast {
+    pub fn update(&mut self, s: &PacketSession) {
+        let samples = s.weatherForecast;
+        let numSamples = (s.numWeatherForecasts as usize).min(samples.len());
+
+        self.weather = s.weather;
+        self.trackTemperature = s.trackTemperature;
+        self.airTemperature = s.airTemperature;
+        self.accuracy = s.forecastAccuracy;
+        self.samples = samples[..numSamples].to_vec();
+    }
+
+    // Samples for one session, in time order.
+    pub fn session(&self, session: Session) -> impl Iterator<Item = &WeatherForecast> {
+        self.samples
+            .iter()
+            .filter(move |sample| sample.sessionType as u8 == session as u8)
+    }
+
+    // First sample in this session where the call changes from the rubber we'd be on now.
+    pub fn crossover(&self, session: Session) -> Option<WeatherForecast> {
+        let now = Rubber::from_weather(self.weather);
+
+        self.session(session)
+            .find(|sample| sample.timeOffset > 0 && Rubber::from_weather(sample.weather) != now)
+            .copied()
+    }
+
+    // First upcoming sample in this session to reach the rain threshold, if it isn't already raining that hard.
+    pub fn rainAlert(&self, session: Session, threshold: u8) -> Option<WeatherForecast> {
+        let mut samples = self.session(session);
+        let now = samples.next()?;
+
+        if now.rainPercentage >= threshold {
+            return None;
+        }
+
+        samples.find(|sample| sample.rainPercentage >= threshold).copied()
+    }
+
+    pub fn getAlerts(&self, session: Session, threshold: u8) -> Vec<String> {
+        let mut alerts = vec![];
+
+        if let Some(sample) = self.crossover(session) {
+            alerts.push(format!(
+                "{} from +{}min ({})",
+                Rubber::from_weather(sample.weather),
+                sample.timeOffset,
+                sample.weather
+            ));
+        }
+
+        if let Some(sample) = self.rainAlert(session, threshold) {
+            let rain = format!(" RAIN {}% in {}min ", sample.rainPercentage, sample.timeOffset);
+            alerts.push(format!("{}", rain.white().on_blue().bold()));
+        }
+
+        alerts
+    }
+
+    // One line per session in the weekend, the session we are in is marked up.
+    pub fn getTimeline(&self, current: Session) -> Vec<String> {
+        let crossover = self.crossover(current);
+        let mut sessions: Vec<Session> = vec![];
+
+        for sample in &self.samples {
+            if !sessions.iter().any(|s| *s as u8 == sample.sessionType as u8) {
+                sessions.push(sample.sessionType);
+            }
+        }
+
+        sessions
+            .into_iter()
+            .map(|session| {
+                let isCurrent = session as u8 == current as u8;
+                let samples: Vec<String> = self
+                    .session(session)
+                    .map(|sample| {
+                        let cell = getSample(sample);
+                        let isCrossover = isCurrent
+                            && crossover.is_some_and(|c| c.timeOffset == sample.timeOffset);
+
+                        if isCrossover {
+                            format!("{}", cell.black().on_white())
+                        } else {
+                            cell
+                        }
+                    })
+                    .collect();
+
+                let label = format!("{:>5}", session.to_string());
+                format!(
+                    "{} {}",
+                    if isCurrent { label.bold() } else { label.normal() },
+                    samples.join(" | ")
+                )
+            })
+            .collect()
+    }
+}
+
+fn getSample(sample: &WeatherForecast) -> String {
+    let weather = match sample.weather {
+        Weather::Clear => "Clr".yellow(),
+        Weather::LightCloud => "Cld".white(),
+        Weather::Overcast => "Ovc".bright_black(),
+        Weather::RainLight => "Rn".cyan(),
+        Weather::RainHeavy => "HRn".blue(),
+        Weather::RainStorm => "Stm".magenta(),
+        _ => "???".normal(),
+    };
+
+    format!(
+        "+{:<2} {:>3} {:>2}{}/{:>2}{} {:>3}%",
+        sample.timeOffset,
+        weather,
+        sample.trackTemperature,
+        sample.trackChange,
+        sample.airTemperature,
+        sample.airChange,
+        sample.rainPercentage
+    )
+}
+
+impl fmt::Display for Forecast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} Track {}C Air {}C ({:?} forecast)",
+            self.weather, self.trackTemperature, self.airTemperature, self.accuracy
+        )
+    }
+}
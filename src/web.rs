@@ -0,0 +1,248 @@
+use pitwall::packet::{Object, ToJson};
+use std::io::{self, BufRead, BufReader, Cursor, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tungstenite::Message;
+
+// How long a client has to send its request.
+static TIMEOUT: Duration = Duration::from_secs(5);
+
+// The overlays, built into the binary.
+static TOWER: &str = include_str!("web/tower.html");
+static LOWER_THIRD: &str = include_str!("web/lower-third.html");
+
+// The dashboard's state as JSON, as the main loop last published it.
+// The overlays are sent the session and cars, the rest is only there to be polled.
+#[derive(Debug, Default, Clone)]
+pub struct Snapshot {
+    pub session: String,     // Page::sessionJson
+    pub cars: Vec<String>,   // Page::carsJson, by car index
+    pub laps: Vec<String>,   // Page::lapsJson, by car index
+    pub bests: String,       // Page::bestsJson
+    pub events: Vec<String>, // Page.events, oldest first
+    pub weather: String,     // Page::weatherJson
+}
+
+// Already JSON, written as is.
+struct Raw<'a>(&'a str);
+
+impl ToJson for Raw<'_> {
+    fn json(&self, out: &mut String) {
+        out.push_str(self.0);
+    }
+}
+
+impl Snapshot {
+    // The JSON a GET of `path` is answered with, if there is such an endpoint.
+    fn get(&self, path: &str) -> Option<String> {
+        let list = |items: &[String]| {
+            let items: Vec<Raw> = items.iter().map(|item| Raw(item)).collect();
+            items.as_slice().to_json()
+        };
+
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match segments.as_slice() {
+            ["session"] => Some(self.session.clone()),
+            ["cars"] => Some(list(&self.cars)),
+            ["cars", idx] => self.cars.get(idx.parse::<usize>().ok()?).cloned(),
+            ["cars", idx, "laps"] => self.laps.get(idx.parse::<usize>().ok()?).cloned(),
+            ["bests"] => Some(self.bests.clone()),
+            ["events"] => Some(list(&self.events)),
+            ["weather"] => Some(self.weather.clone()),
+            _ => None,
+        }
+    }
+
+    // {"type":"full","session":{...},"cars":[...]}, what a new overlay starts from.
+    fn full(&self) -> String {
+        let cars: Vec<Raw> = self.cars.iter().map(|car| Raw(car)).collect();
+        let mut out = String::new();
+        Object::new(&mut out)
+            .field("type", "full")
+            .field("session", &Raw(&self.session))
+            .field("cars", cars.as_slice())
+            .end();
+        out
+    }
+
+    // {"type":"diff","session":{...},"cars":{"3":{...}}}, only what changed since `before`.
+    fn diff(&self, before: &Snapshot) -> Option<String> {
+        if self.cars.len() != before.cars.len() {
+            return Some(self.full());
+        }
+
+        let changed: Vec<(String, Raw)> = self
+            .cars
+            .iter()
+            .zip(&before.cars)
+            .enumerate()
+            .filter(|(_, (car, was))| car != was)
+            .map(|(idx, (car, _))| (idx.to_string(), Raw(car)))
+            .collect();
+        let isSessionChanged = self.session != before.session;
+
+        if changed.is_empty() && !isSessionChanged {
+            return None;
+        }
+
+        let mut out = String::new();
+        let mut object = Object::new(&mut out);
+        object.field("type", "diff");
+        if isSessionChanged {
+            object.field("session", &Raw(&self.session));
+        }
+        object.field("cars", &Changed(&changed));
+        object.end();
+        Some(out)
+    }
+}
+
+// Cars by index, as an object so the overlay can merge it.
+struct Changed<'a>(&'a [(String, Raw<'a>)]);
+
+impl ToJson for Changed<'_> {
+    fn json(&self, out: &mut String) {
+        let mut object = Object::new(out);
+        for (idx, car) in self.0 {
+            object.field(idx, car);
+        }
+        object.end();
+    }
+}
+
+// Hands every overlay the changes as the main loop publishes them.
+#[derive(Debug, Default)]
+pub struct Hub {
+    latest: Snapshot,
+    clients: Vec<Sender<String>>,
+}
+
+impl Hub {
+    pub fn publish(&mut self, snapshot: Snapshot) {
+        if let Some(message) = snapshot.diff(&self.latest) {
+            // Overlays that have gone away are dropped here.
+            self.clients
+                .retain(|client| client.send(message.clone()).is_ok());
+        }
+        self.latest = snapshot;
+    }
+
+    fn get(&self, path: &str) -> Option<String> {
+        self.latest.get(path)
+    }
+
+    fn subscribe(&mut self) -> (String, Receiver<String>) {
+        let (tx, rx) = mpsc::channel();
+        self.clients.push(tx);
+        (self.latest.full(), rx)
+    }
+}
+
+// Serve the overlays, the WebSocket at /ws that feeds them, and the JSON API,
+// each connection on its own thread.
+pub fn serve(addr: &str, hub: Arc<Mutex<Hub>>) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+
+    thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let hub = Arc::clone(&hub);
+            thread::spawn(move || {
+                let _ = handle(stream, &hub);
+            });
+        }
+    });
+
+    Ok(())
+}
+
+fn handle(stream: TcpStream, hub: &Mutex<Hub>) -> io::Result<()> {
+    // A client that never sends its request gives up the thread.
+    stream.set_read_timeout(Some(TIMEOUT))?;
+
+    // The request line and headers, kept for the WebSocket handshake to read again.
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    let mut head = request.clone();
+    while reader.read_line(&mut head)? > 2 {}
+    let mut head = head.into_bytes();
+    head.extend(reader.buffer());
+    drop(reader);
+
+    if request.starts_with("GET /ws ") {
+        return feed(Replayed(Cursor::new(head).chain(stream)), hub);
+    }
+
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+    let path = path.split('?').next().unwrap_or("/");
+    match path {
+        "/" | "/tower" => respond(&stream, "200 OK", "text/html; charset=utf-8", TOWER),
+        "/lower-third" => respond(&stream, "200 OK", "text/html; charset=utf-8", LOWER_THIRD),
+        _ => {
+            let json = hub
+                .lock()
+                .map_err(|_| io::Error::other("hub poisoned"))?
+                .get(path);
+            match json {
+                Some(json) => respond(&stream, "200 OK", "application/json", &json),
+                None => respond(
+                    &stream,
+                    "404 Not Found",
+                    "application/json",
+                    "{\"error\":\"Not found\"}",
+                ),
+            }
+        }
+    }
+}
+
+pub fn respond(
+    mut stream: &TcpStream,
+    status: &str,
+    contentType: &str,
+    body: &str,
+) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {contentType}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+// A stream with what was read from it already put back in front.
+struct Replayed(io::Chain<Cursor<Vec<u8>>, TcpStream>);
+
+impl Read for Replayed {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Write for Replayed {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.get_mut().1.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.get_mut().1.flush()
+    }
+}
+
+// The whole state once, then the changes until the overlay goes away.
+fn feed(stream: Replayed, hub: &Mutex<Hub>) -> io::Result<()> {
+    let mut socket = tungstenite::accept(stream).map_err(io::Error::other)?;
+    let (full, rx) = hub
+        .lock()
+        .map_err(|_| io::Error::other("hub poisoned"))?
+        .subscribe();
+
+    for message in std::iter::once(full).chain(rx) {
+        socket
+            .send(Message::text(message))
+            .map_err(io::Error::other)?;
+    }
+    Ok(())
+}